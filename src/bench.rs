@@ -0,0 +1,143 @@
+//! Latency measurements for the current machine
+//!
+//! [`measure`] times parsing, formatting, converting, and matching a set of
+//! key sequences, so an application can check at startup that resolving its
+//! bindings fits within a frame budget, and fall back to a simpler keymap if
+//! not.
+
+use crate::{parse_keys, Error, Key, KeySequence, Keys};
+use std::collections::HashMap;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Total time taken over a number of iterations
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Measurement {
+    pub iterations: u32,
+    pub total: Duration,
+}
+
+impl Measurement {
+    pub fn per_iteration(&self) -> Duration {
+        self.total / self.iterations.max(1)
+    }
+}
+
+/// Time for each stage, where one iteration handles every input once
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Report {
+    /// Parsing every input with [`parse_keys`]
+    pub parse: Measurement,
+    /// Formatting every sequence with `Display`
+    pub format: Measurement,
+    /// Looking up every sequence in a `HashMap` of all sequences
+    pub lookup: Measurement,
+    /// Converting every key from its UI Events `key` value, as received from
+    /// backends such as `keyboard-types` and `winit`
+    pub convert: Measurement,
+    /// Typing every key of every sequence into a [`KeySequence`], and
+    /// [taking](KeySequence::take) each sequence after every key
+    pub matching: Measurement,
+}
+
+/// Stage which took longer than the budget
+#[derive(Clone, Copy, Debug, PartialEq, thiserror::Error)]
+#[error("{stage} took {per_iteration:?}, over budget of {budget:?}")]
+pub struct BudgetExceeded {
+    pub stage: &'static str,
+    pub per_iteration: Duration,
+    pub budget: Duration,
+}
+
+impl Report {
+    /// Check that every stage takes at most `budget` per iteration
+    pub fn check_budget(&self, budget: Duration) -> Result<(), BudgetExceeded> {
+        for (stage, measurement) in [
+            ("parse", self.parse),
+            ("format", self.format),
+            ("lookup", self.lookup),
+            ("convert", self.convert),
+            ("match", self.matching),
+        ] {
+            let per_iteration = measurement.per_iteration();
+            if per_iteration > budget {
+                return Err(BudgetExceeded {
+                    stage,
+                    per_iteration,
+                    budget,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Measure each stage over `iterations` passes of `inputs`
+///
+/// Returns an error if any input does not parse
+pub fn measure(inputs: &[&str], iterations: u32) -> Result<Report, Error> {
+    let keys = inputs
+        .iter()
+        .map(|input| parse_keys(input))
+        .collect::<Result<Vec<Keys>, _>>()?;
+    let map: HashMap<&Keys, usize> = keys.iter().zip(0..).collect();
+    let values: Vec<_> = keys
+        .iter()
+        .flat_map(|keys| keys.as_slice())
+        .filter_map(|key| Some((key.to_w3c_key()?, key.modifiers)))
+        .collect();
+    let limit = keys
+        .iter()
+        .map(|keys| keys.as_slice().len())
+        .max()
+        .unwrap_or_default();
+
+    let parse = time(iterations, || {
+        for input in inputs {
+            let _ = black_box(parse_keys(black_box(input)));
+        }
+    });
+    let format = time(iterations, || {
+        for keys in &keys {
+            black_box(black_box(keys).to_string());
+        }
+    });
+    let lookup = time(iterations, || {
+        for keys in &keys {
+            black_box(map.get(black_box(keys)));
+        }
+    });
+    let convert = time(iterations, || {
+        for (value, modifiers) in &values {
+            black_box(Key::from_w3c_key(black_box(value), *modifiers));
+        }
+    });
+    let matching = time(iterations, || {
+        let mut sequence = KeySequence::with_limit(limit);
+        for key in keys.iter().flat_map(|keys| keys.as_slice()) {
+            sequence.push(*key);
+            for binding in &keys {
+                black_box(sequence.take(black_box(binding)));
+            }
+        }
+    });
+
+    Ok(Report {
+        parse,
+        format,
+        lookup,
+        convert,
+        matching,
+    })
+}
+
+fn time(iterations: u32, mut f: impl FnMut()) -> Measurement {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        f();
+    }
+    Measurement {
+        iterations,
+        total: start.elapsed(),
+    }
+}
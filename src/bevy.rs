@@ -0,0 +1,189 @@
+//! Bevy plugin which sends [`Key`]s as they are pressed, with the modifiers
+//! which are held
+
+use crate::{ConversionError, Key, KeyName, KeySequence, Modifiers};
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::prelude::*;
+use bevy_input::keyboard::KeyCode;
+use bevy_input::{ButtonInput, InputSystem};
+
+/// Physical keys of a US keyboard
+const CODES: &[(KeyName, KeyCode)] = &[
+    (KeyName::A, KeyCode::KeyA),
+    (KeyName::B, KeyCode::KeyB),
+    (KeyName::C, KeyCode::KeyC),
+    (KeyName::D, KeyCode::KeyD),
+    (KeyName::E, KeyCode::KeyE),
+    (KeyName::F, KeyCode::KeyF),
+    (KeyName::G, KeyCode::KeyG),
+    (KeyName::H, KeyCode::KeyH),
+    (KeyName::I, KeyCode::KeyI),
+    (KeyName::J, KeyCode::KeyJ),
+    (KeyName::K, KeyCode::KeyK),
+    (KeyName::L, KeyCode::KeyL),
+    (KeyName::M, KeyCode::KeyM),
+    (KeyName::N, KeyCode::KeyN),
+    (KeyName::O, KeyCode::KeyO),
+    (KeyName::P, KeyCode::KeyP),
+    (KeyName::Q, KeyCode::KeyQ),
+    (KeyName::R, KeyCode::KeyR),
+    (KeyName::S, KeyCode::KeyS),
+    (KeyName::T, KeyCode::KeyT),
+    (KeyName::U, KeyCode::KeyU),
+    (KeyName::V, KeyCode::KeyV),
+    (KeyName::W, KeyCode::KeyW),
+    (KeyName::X, KeyCode::KeyX),
+    (KeyName::Y, KeyCode::KeyY),
+    (KeyName::Z, KeyCode::KeyZ),
+    (KeyName::Number0, KeyCode::Digit0),
+    (KeyName::Number1, KeyCode::Digit1),
+    (KeyName::Number2, KeyCode::Digit2),
+    (KeyName::Number3, KeyCode::Digit3),
+    (KeyName::Number4, KeyCode::Digit4),
+    (KeyName::Number5, KeyCode::Digit5),
+    (KeyName::Number6, KeyCode::Digit6),
+    (KeyName::Number7, KeyCode::Digit7),
+    (KeyName::Number8, KeyCode::Digit8),
+    (KeyName::Number9, KeyCode::Digit9),
+    (KeyName::Dash, KeyCode::Minus),
+    (KeyName::Equals, KeyCode::Equal),
+    (KeyName::BracketLeft, KeyCode::BracketLeft),
+    (KeyName::BracketRight, KeyCode::BracketRight),
+    (KeyName::Backslash, KeyCode::Backslash),
+    (KeyName::Semicolon, KeyCode::Semicolon),
+    (KeyName::SingleQuote, KeyCode::Quote),
+    (KeyName::Comma, KeyCode::Comma),
+    (KeyName::Period, KeyCode::Period),
+    (KeyName::ForwardSlash, KeyCode::Slash),
+    (KeyName::Backtick, KeyCode::Backquote),
+    (KeyName::Space, KeyCode::Space),
+    (KeyName::Enter, KeyCode::Enter),
+    (KeyName::Tab, KeyCode::Tab),
+    (KeyName::Escape, KeyCode::Escape),
+    (KeyName::Backspace, KeyCode::Backspace),
+    (KeyName::Delete, KeyCode::Delete),
+    (KeyName::Insert, KeyCode::Insert),
+    (KeyName::Home, KeyCode::Home),
+    (KeyName::End, KeyCode::End),
+    (KeyName::PageUp, KeyCode::PageUp),
+    (KeyName::PageDown, KeyCode::PageDown),
+    (KeyName::Up, KeyCode::ArrowUp),
+    (KeyName::Down, KeyCode::ArrowDown),
+    (KeyName::Left, KeyCode::ArrowLeft),
+    (KeyName::Right, KeyCode::ArrowRight),
+    (KeyName::F1, KeyCode::F1),
+    (KeyName::F2, KeyCode::F2),
+    (KeyName::F3, KeyCode::F3),
+    (KeyName::F4, KeyCode::F4),
+    (KeyName::F5, KeyCode::F5),
+    (KeyName::F6, KeyCode::F6),
+    (KeyName::F7, KeyCode::F7),
+    (KeyName::F8, KeyCode::F8),
+    (KeyName::F9, KeyCode::F9),
+    (KeyName::F10, KeyCode::F10),
+    (KeyName::F11, KeyCode::F11),
+    (KeyName::F12, KeyCode::F12),
+    (KeyName::CapsLock, KeyCode::CapsLock),
+    (KeyName::LeftShift, KeyCode::ShiftLeft),
+    (KeyName::RightShift, KeyCode::ShiftRight),
+    (KeyName::LeftControl, KeyCode::ControlLeft),
+    (KeyName::RightControl, KeyCode::ControlRight),
+    (KeyName::LeftAlt, KeyCode::AltLeft),
+    (KeyName::RightAlt, KeyCode::AltRight),
+    (KeyName::LeftSuper, KeyCode::SuperLeft),
+    (KeyName::RightSuper, KeyCode::SuperRight),
+];
+
+/// Modifier keys, which change the modifiers of other keys
+const MODIFIER_KEYS: &[KeyCode] = &[
+    KeyCode::ShiftLeft,
+    KeyCode::ShiftRight,
+    KeyCode::ControlLeft,
+    KeyCode::ControlRight,
+    KeyCode::AltLeft,
+    KeyCode::AltRight,
+    KeyCode::SuperLeft,
+    KeyCode::SuperRight,
+];
+
+/// Plugin which sends a [`KeyPressed`] event for each key which is pressed,
+/// other than modifier keys, and tracks them in a [`KeySequence`] resource
+///
+/// Keys are physical keys with the modifiers held, so shift and `1` is
+/// `<S-1>`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct KeysPlugin;
+
+impl Plugin for KeysPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<KeyPressed>()
+            .init_resource::<KeySequence>()
+            .add_systems(PreUpdate, send_key_presses.after(InputSystem));
+    }
+}
+
+/// Key which was pressed this frame
+#[derive(Clone, Copy, Debug, Eq, Event, Hash, PartialEq)]
+pub struct KeyPressed(pub Key);
+
+impl Resource for KeySequence {}
+
+/// Keys which were pressed this frame with the modifiers which are held, in
+/// no particular order
+pub fn key_presses(input: &ButtonInput<KeyCode>) -> impl Iterator<Item = Key> + '_ {
+    let modifiers = input.into();
+    input
+        .get_just_pressed()
+        .filter(|code| !MODIFIER_KEYS.contains(code))
+        .filter_map(move |code| {
+            let name = KeyName::try_from(*code).ok()?;
+            Some(Key { modifiers, name })
+        })
+}
+
+fn send_key_presses(
+    input: Res<ButtonInput<KeyCode>>,
+    mut events: EventWriter<KeyPressed>,
+    mut sequence: ResMut<KeySequence>,
+) {
+    for key in key_presses(&input) {
+        sequence.push(key);
+        events.send(KeyPressed(key));
+    }
+}
+
+impl TryFrom<KeyCode> for KeyName {
+    type Error = ConversionError;
+    fn try_from(code: KeyCode) -> Result<Self, Self::Error> {
+        CODES
+            .iter()
+            .find(|(_, known)| *known == code)
+            .map(|(name, _)| *name)
+            .ok_or_else(|| ConversionError::UnsupportedKey(format!("{:?}", code)))
+    }
+}
+
+impl TryFrom<KeyName> for KeyCode {
+    type Error = ConversionError;
+    fn try_from(name: KeyName) -> Result<Self, Self::Error> {
+        CODES
+            .iter()
+            .find(|(known, _)| *known == name)
+            .map(|(_, code)| *code)
+            .ok_or_else(|| ConversionError::UnsupportedKey(name.to_string()))
+    }
+}
+
+/// Modifiers of the modifier keys which are held
+impl From<&ButtonInput<KeyCode>> for Modifiers {
+    fn from(input: &ButtonInput<KeyCode>) -> Self {
+        let held = |left, right| input.any_pressed([left, right]);
+        Modifiers {
+            shift: held(KeyCode::ShiftLeft, KeyCode::ShiftRight),
+            control: held(KeyCode::ControlLeft, KeyCode::ControlRight),
+            alt: held(KeyCode::AltLeft, KeyCode::AltRight),
+            super_key: held(KeyCode::SuperLeft, KeyCode::SuperRight),
+            primary: false,
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use crate::{parse_key, Error, Key, KeyName, Keys, Modifiers};
+
+/// Builds [`Keys`] one key at a time, as a middle ground between a `Vec`
+/// of [`Key`] and parsing notation
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct KeysBuilder {
+    keys: Vec<Key>,
+}
+
+impl Keys {
+    pub fn builder() -> KeysBuilder {
+        KeysBuilder::new()
+    }
+}
+
+impl KeysBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a key with no modifiers
+    pub fn key(self, name: KeyName) -> Self {
+        self.with(Key {
+            modifiers: Modifiers::default(),
+            name,
+        })
+    }
+
+    /// Add a key
+    pub fn with(mut self, key: Key) -> Self {
+        self.keys.push(key);
+        self
+    }
+
+    /// Add the key for a character, where an uppercase letter is shifted
+    pub fn char(self, ch: char) -> Self {
+        self.modified(ch, Modifiers::default())
+    }
+
+    /// Add the key for a character with control held, such as `<C-a>`
+    pub fn ctrl(self, ch: char) -> Self {
+        let modifiers = Modifiers {
+            control: true,
+            ..Default::default()
+        };
+        self.modified(ch, modifiers)
+    }
+
+    /// Add the key for a character with alt held, such as `<M-a>`
+    pub fn alt(self, ch: char) -> Self {
+        let modifiers = Modifiers {
+            alt: true,
+            ..Default::default()
+        };
+        self.modified(ch, modifiers)
+    }
+
+    /// Add the key for a character with super held, such as `<D-a>`
+    pub fn super_key(self, ch: char) -> Self {
+        let modifiers = Modifiers {
+            super_key: true,
+            ..Default::default()
+        };
+        self.modified(ch, modifiers)
+    }
+
+    /// Add the key for a character with modifiers held
+    pub fn modified(self, ch: char, mut modifiers: Modifiers) -> Self {
+        let (name, shift) = KeyName::from_char(ch);
+        modifiers.shift |= shift;
+        self.with(Key { modifiers, name })
+    }
+
+    /// Add a key written in notation, such as `<F5>` or `a`
+    pub fn named(self, input: &str) -> Result<Self, Error> {
+        Ok(self.with(parse_key(input)?))
+    }
+
+    pub fn build(self) -> Keys {
+        Keys(self.keys)
+    }
+}
@@ -0,0 +1,51 @@
+//! Parsing the same notation many times, such as when loading configs
+
+use crate::{parse_keys_with, Error, Keys, ParserOptions};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Memoizing parser, which returns shared [`Keys`] for input it has already
+/// parsed
+///
+/// Only valid input is cached, so invalid input is parsed again each time.
+#[derive(Clone, Debug, Default)]
+pub struct KeysCache {
+    options: ParserOptions,
+    parsed: HashMap<Box<str>, Arc<Keys>>,
+}
+
+impl KeysCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_options(options: ParserOptions) -> Self {
+        Self {
+            options,
+            parsed: HashMap::new(),
+        }
+    }
+
+    /// Parse keys, or return the keys from the last time this input was parsed
+    pub fn parse(&mut self, input: &str) -> Result<Arc<Keys>, Error> {
+        if let Some(keys) = self.parsed.get(input) {
+            return Ok(Arc::clone(keys));
+        }
+        let keys = Arc::new(parse_keys_with(input, &self.options)?);
+        self.parsed.insert(input.into(), Arc::clone(&keys));
+        Ok(keys)
+    }
+
+    /// Number of inputs cached
+    pub fn len(&self) -> usize {
+        self.parsed.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parsed.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.parsed.clear();
+    }
+}
@@ -0,0 +1,104 @@
+//! Data-driven round-trip cases, for checking notation and backend
+//! conversions against every key name
+//!
+//! Backend integrations can run [`check_backend`] with their own conversion
+//! functions, so that a newly added [`KeyName`] is caught wherever it is not
+//! yet handled.
+
+use crate::{parse_keys, Key, KeyName, Keys, Modifiers};
+
+/// A key and its canonical notation
+#[derive(Clone, Debug, PartialEq)]
+pub struct Case {
+    pub key: Key,
+    pub notation: String,
+}
+
+/// A case which did not round-trip
+#[derive(Clone, Debug, PartialEq)]
+pub struct Failure {
+    pub case: Case,
+    pub message: String,
+}
+
+/// Result of checking a backend against every case
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Report {
+    /// Cases which converted, but did not convert back to the same key
+    pub failures: Vec<Failure>,
+    /// Cases which the backend could not convert
+    pub unsupported: Vec<Case>,
+}
+
+/// Every key name, unmodified, with shift, and with control held
+pub fn cases() -> Vec<Case> {
+    let names = KeyName::ALL
+        .iter()
+        .copied()
+        .chain([KeyName::Char('é'), KeyName::Unknown(42)]);
+
+    let mut cases = Vec::new();
+    for name in names {
+        for modifiers in [
+            Modifiers::default(),
+            Modifiers {
+                shift: true,
+                ..Default::default()
+            },
+            Modifiers {
+                control: true,
+                ..Default::default()
+            },
+        ] {
+            let key = Key { modifiers, name };
+            let notation = key.to_string();
+            cases.push(Case { key, notation });
+        }
+    }
+    cases
+}
+
+/// Check that every case parses from its notation, and formats back to it
+pub fn check_notation() -> Vec<Failure> {
+    let mut failures = Vec::new();
+    for case in cases() {
+        let expected = Ok(Keys(vec![case.key]));
+
+        let parsed = parse_keys(&case.notation);
+        if parsed != expected {
+            let message = format!("parsed as {:?}", parsed);
+            failures.push(Failure { case, message });
+            continue;
+        }
+
+        let named = format!("{:#}", case.key);
+        let parsed = parse_keys(&named);
+        if parsed != expected {
+            let message = format!("named notation `{}` parsed as {:?}", named, parsed);
+            failures.push(Failure { case, message });
+        }
+    }
+    failures
+}
+
+/// Check that every key the backend supports converts back to the same key
+///
+/// `into_backend` should return `None` for keys the backend cannot represent
+pub fn check_backend<T>(
+    into_backend: impl Fn(Key) -> Option<T>,
+    from_backend: impl Fn(T) -> Option<Key>,
+) -> Report {
+    let mut report = Report::default();
+    for case in cases() {
+        let Some(converted) = into_backend(case.key) else {
+            report.unsupported.push(case);
+            continue;
+        };
+        let key = from_backend(converted);
+        if key != Some(case.key) {
+            let message = format!("converted back as {:?}", key);
+            report.failures.push(Failure { case, message });
+        }
+    }
+    report
+}
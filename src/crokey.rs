@@ -0,0 +1,35 @@
+//! Conversion to and from [`crokey::KeyCombination`], for key bindings in
+//! terminal applications
+
+use crate::crossterm::{code_from_key, key_from_code};
+use crate::{ConversionError, Key, Modifiers};
+use crokey::{KeyCombination, OneToThree};
+
+/// A combination of one key, read as a crossterm key event, so `shift-?` is
+/// `?`
+///
+/// Combinations of several keys held at once, and hyper and meta, have no
+/// matching [`Key`].
+impl TryFrom<KeyCombination> for Key {
+    type Error = ConversionError;
+    fn try_from(combination: KeyCombination) -> Result<Self, Self::Error> {
+        let unsupported = || ConversionError::UnsupportedKey(combination.to_string());
+        let OneToThree::One(code) = combination.codes else {
+            return Err(unsupported());
+        };
+        let modifiers = Modifiers::try_from(combination.modifiers).map_err(|_| unsupported())?;
+        key_from_code(code, modifiers).ok_or_else(unsupported)
+    }
+}
+
+/// [`Modifiers::primary`] must be resolved first with
+/// [`Key::resolve_platform`], and shift and tab is `BackTab`
+impl TryFrom<Key> for KeyCombination {
+    type Error = ConversionError;
+    fn try_from(key: Key) -> Result<Self, Self::Error> {
+        let modifiers = key.modifiers.try_into()?;
+        let code =
+            code_from_key(key).ok_or_else(|| ConversionError::UnsupportedKey(key.to_string()))?;
+        Ok(KeyCombination::one_key(code, modifiers).normalized())
+    }
+}
@@ -0,0 +1,206 @@
+//! Conversion to and from [`crossterm::event::KeyEvent`], for terminal
+//! applications
+
+use crate::normalize::US_SHIFTED;
+use crate::notation::literal_char;
+use crate::terminal::TerminalKey;
+use crate::{ConversionError, Key, KeyAction, KeyName, Modifiers, ShiftedSymbols};
+use ::crossterm::event::{
+    KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers, ModifierKeyCode,
+};
+
+/// Keys with a crossterm key code other than [`KeyCode::Char`]
+pub(crate) const CODES: &[(KeyName, KeyCode)] = &[
+    (KeyName::Enter, KeyCode::Enter),
+    (KeyName::Tab, KeyCode::Tab),
+    (KeyName::Escape, KeyCode::Esc),
+    (KeyName::Backspace, KeyCode::Backspace),
+    (KeyName::Delete, KeyCode::Delete),
+    (KeyName::Insert, KeyCode::Insert),
+    (KeyName::Home, KeyCode::Home),
+    (KeyName::End, KeyCode::End),
+    (KeyName::PageUp, KeyCode::PageUp),
+    (KeyName::PageDown, KeyCode::PageDown),
+    (KeyName::Up, KeyCode::Up),
+    (KeyName::Down, KeyCode::Down),
+    (KeyName::Left, KeyCode::Left),
+    (KeyName::Right, KeyCode::Right),
+    (KeyName::F1, KeyCode::F(1)),
+    (KeyName::F2, KeyCode::F(2)),
+    (KeyName::F3, KeyCode::F(3)),
+    (KeyName::F4, KeyCode::F(4)),
+    (KeyName::F5, KeyCode::F(5)),
+    (KeyName::F6, KeyCode::F(6)),
+    (KeyName::F7, KeyCode::F(7)),
+    (KeyName::F8, KeyCode::F(8)),
+    (KeyName::F9, KeyCode::F(9)),
+    (KeyName::F10, KeyCode::F(10)),
+    (KeyName::F11, KeyCode::F(11)),
+    (KeyName::F12, KeyCode::F(12)),
+    (KeyName::CapsLock, KeyCode::CapsLock),
+    (
+        KeyName::LeftShift,
+        KeyCode::Modifier(ModifierKeyCode::LeftShift),
+    ),
+    (
+        KeyName::RightShift,
+        KeyCode::Modifier(ModifierKeyCode::RightShift),
+    ),
+    (
+        KeyName::LeftControl,
+        KeyCode::Modifier(ModifierKeyCode::LeftControl),
+    ),
+    (
+        KeyName::RightControl,
+        KeyCode::Modifier(ModifierKeyCode::RightControl),
+    ),
+    (
+        KeyName::LeftAlt,
+        KeyCode::Modifier(ModifierKeyCode::LeftAlt),
+    ),
+    (
+        KeyName::RightAlt,
+        KeyCode::Modifier(ModifierKeyCode::RightAlt),
+    ),
+    (
+        KeyName::LeftSuper,
+        KeyCode::Modifier(ModifierKeyCode::LeftSuper),
+    ),
+    (
+        KeyName::RightSuper,
+        KeyCode::Modifier(ModifierKeyCode::RightSuper),
+    ),
+];
+
+/// Key of a key code held with modifiers, where an uppercase letter or
+/// [`KeyCode::BackTab`] is held with shift
+///
+/// Shift is not kept for symbols which are typed with shift on a US
+/// keyboard, so `shift-?` is `?`.
+pub(crate) fn key_from_code(code: KeyCode, mut modifiers: Modifiers) -> Option<Key> {
+    let name = match code {
+        KeyCode::Char(ch) => {
+            let (name, shift) = KeyName::from_char(ch.to_ascii_lowercase());
+            modifiers.shift |= shift || ch.is_ascii_uppercase();
+            if US_SHIFTED.iter().any(|(_, symbol)| *symbol == name) {
+                modifiers.shift = false;
+            }
+            name
+        }
+        KeyCode::BackTab => {
+            modifiers.shift = true;
+            KeyName::Tab
+        }
+        code => CODES.iter().find(|(_, key_code)| *key_code == code)?.0,
+    };
+    Some(Key { modifiers, name })
+}
+
+/// Key code of a key, where shift and tab is [`KeyCode::BackTab`]
+pub(crate) fn code_from_key(key: Key) -> Option<KeyCode> {
+    match key.name {
+        KeyName::Tab if key.modifiers.shift => Some(KeyCode::BackTab),
+        KeyName::Space => Some(KeyCode::Char(' ')),
+        name => match CODES.iter().find(|(key_name, _)| *key_name == name) {
+            Some((_, code)) => Some(*code),
+            None => literal_char(name).map(KeyCode::Char),
+        },
+    }
+}
+
+/// Key of the event, whether it was pressed, repeated or released
+impl TryFrom<KeyEvent> for Key {
+    type Error = ConversionError;
+    fn try_from(event: KeyEvent) -> Result<Self, Self::Error> {
+        Ok(TerminalKey::try_from(event)?.key)
+    }
+}
+
+/// [`KeyEventKind`] is the action of the key
+impl TryFrom<KeyEvent> for TerminalKey {
+    type Error = ConversionError;
+    fn try_from(event: KeyEvent) -> Result<Self, Self::Error> {
+        let unsupported = || ConversionError::UnsupportedKey(format!("{:?}", event.code));
+        let modifiers = Modifiers::try_from(event.modifiers).map_err(|_| unsupported())?;
+        let key = key_from_code(event.code, modifiers).ok_or_else(unsupported)?;
+        let action = match event.kind {
+            KeyEventKind::Press => KeyAction::Press,
+            KeyEventKind::Repeat => KeyAction::Repeat,
+            KeyEventKind::Release => KeyAction::Release,
+        };
+        Ok(TerminalKey { key, action })
+    }
+}
+
+/// Press of the key, as with [`TerminalKey`]
+impl TryFrom<Key> for KeyEvent {
+    type Error = ConversionError;
+    fn try_from(key: Key) -> Result<Self, Self::Error> {
+        KeyEvent::try_from(TerminalKey {
+            key,
+            action: KeyAction::Press,
+        })
+    }
+}
+
+/// Shifted symbols are written as the symbol, so `<S-1>` is `!`, and a
+/// shifted letter is uppercase with shift, as crossterm reports them
+///
+/// [`Modifiers::primary`] must be resolved first with
+/// [`Key::resolve_platform`].
+impl TryFrom<TerminalKey> for KeyEvent {
+    type Error = ConversionError;
+    fn try_from(terminal_key: TerminalKey) -> Result<Self, Self::Error> {
+        let key = terminal_key.key.normalize_with(ShiftedSymbols::ToSymbol);
+        let mut code = code_from_key(key)
+            .ok_or_else(|| ConversionError::UnsupportedKey(terminal_key.key.to_string()))?;
+        if let KeyCode::Char(ch) = code {
+            if key.modifiers.shift {
+                code = KeyCode::Char(ch.to_ascii_uppercase());
+            }
+        }
+        let kind = match terminal_key.action {
+            KeyAction::Press => KeyEventKind::Press,
+            KeyAction::Repeat => KeyEventKind::Repeat,
+            KeyAction::Release => KeyEventKind::Release,
+        };
+        Ok(KeyEvent {
+            code,
+            modifiers: key.modifiers.try_into()?,
+            kind,
+            state: KeyEventState::NONE,
+        })
+    }
+}
+
+/// Hyper and meta have no matching modifier
+impl TryFrom<KeyModifiers> for Modifiers {
+    type Error = ConversionError;
+    fn try_from(mods: KeyModifiers) -> Result<Self, Self::Error> {
+        if mods.intersects(KeyModifiers::HYPER | KeyModifiers::META) {
+            return Err(ConversionError::UnsupportedKey(mods.to_string()));
+        }
+        Ok(Modifiers {
+            shift: mods.contains(KeyModifiers::SHIFT),
+            control: mods.contains(KeyModifiers::CONTROL),
+            alt: mods.contains(KeyModifiers::ALT),
+            super_key: mods.contains(KeyModifiers::SUPER),
+            primary: false,
+        })
+    }
+}
+
+/// [`Modifiers::primary`] must be resolved first with
+/// [`Modifiers::resolve_platform`]
+impl TryFrom<Modifiers> for KeyModifiers {
+    type Error = ConversionError;
+    fn try_from(modifiers: Modifiers) -> Result<Self, Self::Error> {
+        let modifiers = modifiers.require_resolved()?;
+        let mut mods = KeyModifiers::empty();
+        mods.set(KeyModifiers::SHIFT, modifiers.shift);
+        mods.set(KeyModifiers::CONTROL, modifiers.control);
+        mods.set(KeyModifiers::ALT, modifiers.alt);
+        mods.set(KeyModifiers::SUPER, modifiers.super_key);
+        Ok(mods)
+    }
+}
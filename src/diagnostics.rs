@@ -0,0 +1,47 @@
+//! [`miette`] diagnostics for parse errors, with labeled spans
+
+use crate::{Error, SpannedError};
+use miette::{Diagnostic, LabeledSpan};
+use std::fmt::Display;
+
+/// Attach the parsed input as source code to render the error, such as with
+/// `miette::Report::new(error).with_source_code(input)`
+impl Diagnostic for SpannedError {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        let code = match self.error {
+            Error::NoKeyName => "keys::no_key_name",
+            Error::InvalidKeyName(_) => "keys::invalid_key_name",
+            Error::InvalidKeyModifier(_) => "keys::invalid_key_modifier",
+            Error::UnexpectedGroupOpen => "keys::unexpected_group_open",
+            Error::UnexpectedGroupClose => "keys::unexpected_group_close",
+            Error::UnexpectedEnd => "keys::unexpected_end",
+            Error::IncompleteGroup(_) => "keys::incomplete_group",
+            Error::InvalidCharCode(_) => "keys::invalid_char_code",
+            Error::TrailingEscape => "keys::trailing_escape",
+            Error::LimitExceeded => "keys::limit_exceeded",
+        };
+        Some(Box::new(code))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        let suggestion = self.error.suggestion()?;
+        Some(Box::new(format!("did you mean `{}`?", suggestion)))
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let label = match self.error {
+            Error::InvalidKeyName(_) => "unknown key name",
+            Error::InvalidKeyModifier(_) => "unknown modifier",
+            Error::UnexpectedGroupOpen => "group opened inside another group",
+            Error::UnexpectedGroupClose => "no group to close",
+            Error::UnexpectedEnd => "group is never closed",
+            Error::IncompleteGroup(_) => "group needs a modifier and a key name",
+            Error::InvalidCharCode(_) => "not a valid character code",
+            Error::TrailingEscape => "escapes nothing",
+            Error::LimitExceeded => "over the limit",
+            _ => "here",
+        };
+        let span = LabeledSpan::at(self.span.clone(), label);
+        Some(Box::new(std::iter::once(span)))
+    }
+}
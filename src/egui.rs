@@ -0,0 +1,224 @@
+//! Conversion to and from [`egui`] key events, and matching of shortcuts in
+//! [`egui::InputState`]
+
+use crate::normalize::US_SHIFTED;
+use crate::{ConversionError, Key, KeyName, Modifiers, ShiftedSymbols};
+use egui::{Event, InputState, KeyboardShortcut};
+
+/// Keys of egui, which are logical keys
+const KEYS: &[(KeyName, egui::Key)] = &[
+    (KeyName::A, egui::Key::A),
+    (KeyName::B, egui::Key::B),
+    (KeyName::C, egui::Key::C),
+    (KeyName::D, egui::Key::D),
+    (KeyName::E, egui::Key::E),
+    (KeyName::F, egui::Key::F),
+    (KeyName::G, egui::Key::G),
+    (KeyName::H, egui::Key::H),
+    (KeyName::I, egui::Key::I),
+    (KeyName::J, egui::Key::J),
+    (KeyName::K, egui::Key::K),
+    (KeyName::L, egui::Key::L),
+    (KeyName::M, egui::Key::M),
+    (KeyName::N, egui::Key::N),
+    (KeyName::O, egui::Key::O),
+    (KeyName::P, egui::Key::P),
+    (KeyName::Q, egui::Key::Q),
+    (KeyName::R, egui::Key::R),
+    (KeyName::S, egui::Key::S),
+    (KeyName::T, egui::Key::T),
+    (KeyName::U, egui::Key::U),
+    (KeyName::V, egui::Key::V),
+    (KeyName::W, egui::Key::W),
+    (KeyName::X, egui::Key::X),
+    (KeyName::Y, egui::Key::Y),
+    (KeyName::Z, egui::Key::Z),
+    (KeyName::Number0, egui::Key::Num0),
+    (KeyName::Number1, egui::Key::Num1),
+    (KeyName::Number2, egui::Key::Num2),
+    (KeyName::Number3, egui::Key::Num3),
+    (KeyName::Number4, egui::Key::Num4),
+    (KeyName::Number5, egui::Key::Num5),
+    (KeyName::Number6, egui::Key::Num6),
+    (KeyName::Number7, egui::Key::Num7),
+    (KeyName::Number8, egui::Key::Num8),
+    (KeyName::Number9, egui::Key::Num9),
+    (KeyName::Dash, egui::Key::Minus),
+    (KeyName::Equals, egui::Key::Equals),
+    (KeyName::Plus, egui::Key::Plus),
+    (KeyName::BracketLeft, egui::Key::OpenBracket),
+    (KeyName::BracketRight, egui::Key::CloseBracket),
+    (KeyName::BraceLeft, egui::Key::OpenCurlyBracket),
+    (KeyName::BraceRight, egui::Key::CloseCurlyBracket),
+    (KeyName::Backslash, egui::Key::Backslash),
+    (KeyName::Pipe, egui::Key::Pipe),
+    (KeyName::Semicolon, egui::Key::Semicolon),
+    (KeyName::Colon, egui::Key::Colon),
+    (KeyName::SingleQuote, egui::Key::Quote),
+    (KeyName::Comma, egui::Key::Comma),
+    (KeyName::Period, egui::Key::Period),
+    (KeyName::ForwardSlash, egui::Key::Slash),
+    (KeyName::Question, egui::Key::Questionmark),
+    (KeyName::Bang, egui::Key::Exclamationmark),
+    (KeyName::Backtick, egui::Key::Backtick),
+    (KeyName::Space, egui::Key::Space),
+    (KeyName::Enter, egui::Key::Enter),
+    (KeyName::Tab, egui::Key::Tab),
+    (KeyName::Escape, egui::Key::Escape),
+    (KeyName::Backspace, egui::Key::Backspace),
+    (KeyName::Delete, egui::Key::Delete),
+    (KeyName::Insert, egui::Key::Insert),
+    (KeyName::Home, egui::Key::Home),
+    (KeyName::End, egui::Key::End),
+    (KeyName::PageUp, egui::Key::PageUp),
+    (KeyName::PageDown, egui::Key::PageDown),
+    (KeyName::Up, egui::Key::ArrowUp),
+    (KeyName::Down, egui::Key::ArrowDown),
+    (KeyName::Left, egui::Key::ArrowLeft),
+    (KeyName::Right, egui::Key::ArrowRight),
+    (KeyName::F1, egui::Key::F1),
+    (KeyName::F2, egui::Key::F2),
+    (KeyName::F3, egui::Key::F3),
+    (KeyName::F4, egui::Key::F4),
+    (KeyName::F5, egui::Key::F5),
+    (KeyName::F6, egui::Key::F6),
+    (KeyName::F7, egui::Key::F7),
+    (KeyName::F8, egui::Key::F8),
+    (KeyName::F9, egui::Key::F9),
+    (KeyName::F10, egui::Key::F10),
+    (KeyName::F11, egui::Key::F11),
+    (KeyName::F12, egui::Key::F12),
+];
+
+/// Logical key of a key event, whether it was pressed or released
+///
+/// Shift is not kept for symbols such as `?`, which egui reports with
+/// shift, and shifted digits become the symbol they type on a US keyboard,
+/// as with [`ShiftedSymbols::ToSymbol`].
+impl TryFrom<&Event> for Key {
+    type Error = ConversionError;
+    fn try_from(event: &Event) -> Result<Self, Self::Error> {
+        let Event::Key { key, modifiers, .. } = event else {
+            return Err(ConversionError::NoKeycode);
+        };
+        let name = KeyName::try_from(*key)?;
+        let mut modifiers = Modifiers::from(*modifiers);
+        if US_SHIFTED.iter().any(|(_, symbol)| *symbol == name) {
+            modifiers.shift = false;
+        }
+        Ok(Key { modifiers, name }.normalize_with(ShiftedSymbols::ToSymbol))
+    }
+}
+
+/// Shortcut of the key, which egui matches with extra shift and alt held
+///
+/// [`Modifiers::primary`] is [`egui::Modifiers::command`].
+impl TryFrom<Key> for KeyboardShortcut {
+    type Error = ConversionError;
+    fn try_from(key: Key) -> Result<Self, Self::Error> {
+        let unsupported = || ConversionError::UnsupportedKey(key.to_string());
+        let normalized = key.normalize_with(ShiftedSymbols::ToSymbol);
+        let logical_key = egui::Key::try_from(normalized.name).map_err(|_| unsupported())?;
+        Ok(KeyboardShortcut::new(
+            normalized.modifiers.into(),
+            logical_key,
+        ))
+    }
+}
+
+impl TryFrom<egui::Key> for KeyName {
+    type Error = ConversionError;
+    fn try_from(key: egui::Key) -> Result<Self, Self::Error> {
+        KEYS.iter()
+            .find(|(_, known)| *known == key)
+            .map(|(name, _)| *name)
+            .ok_or_else(|| ConversionError::UnsupportedKey(key.name().to_string()))
+    }
+}
+
+impl TryFrom<KeyName> for egui::Key {
+    type Error = ConversionError;
+    fn try_from(name: KeyName) -> Result<Self, Self::Error> {
+        KEYS.iter()
+            .find(|(known, _)| *known == name)
+            .map(|(_, key)| *key)
+            .ok_or_else(|| ConversionError::UnsupportedKey(name.to_string()))
+    }
+}
+
+/// The Mac command key is super, and [`egui::Modifiers::command`], which
+/// is the same as control or command, is not kept
+impl From<egui::Modifiers> for Modifiers {
+    fn from(modifiers: egui::Modifiers) -> Self {
+        Modifiers {
+            shift: modifiers.shift,
+            control: modifiers.ctrl,
+            alt: modifiers.alt,
+            super_key: modifiers.mac_cmd,
+            primary: false,
+        }
+    }
+}
+
+/// [`Modifiers::primary`] is [`egui::Modifiers::command`], which egui
+/// matches with control, or command on a Mac
+impl From<Modifiers> for egui::Modifiers {
+    fn from(modifiers: Modifiers) -> Self {
+        egui::Modifiers {
+            alt: modifiers.alt,
+            ctrl: modifiers.control,
+            shift: modifiers.shift,
+            mac_cmd: modifiers.super_key,
+            command: modifiers.primary,
+        }
+    }
+}
+
+/// Matching of keys pressed this frame, such as for shortcuts written as
+/// `<C-S-p>`
+pub trait ConsumeKey {
+    /// Whether the key was pressed this frame with exactly its modifiers,
+    /// removing the press so it only matches once
+    ///
+    /// [`Modifiers::primary`] matches [`egui::Modifiers::command`], which
+    /// egui sets with control, or command on a Mac.
+    fn consume(&mut self, key: Key) -> bool;
+}
+
+impl ConsumeKey for InputState {
+    fn consume(&mut self, key: Key) -> bool {
+        let key = key.normalize_with(ShiftedSymbols::ToSymbol);
+        let position = self.events.iter().position(|event| {
+            let Event::Key {
+                pressed: true,
+                modifiers,
+                ..
+            } = event
+            else {
+                return false;
+            };
+            Key::try_from(event).is_ok_and(|pressed| {
+                pressed == key || (modifiers.command && with_primary(pressed, modifiers) == key)
+            })
+        });
+        match position {
+            Some(index) => {
+                self.events.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Key with the modifier which egui reads as command replaced by
+/// [`Modifiers::primary`]
+fn with_primary(mut key: Key, modifiers: &egui::Modifiers) -> Key {
+    if modifiers.mac_cmd {
+        key.modifiers.super_key = false;
+    } else {
+        key.modifiers.control = false;
+    }
+    key.modifiers.primary = true;
+    key
+}
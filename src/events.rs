@@ -0,0 +1,164 @@
+//! Press and release events of single keys, as seen by backends which report
+//! modifier keys separately, such as `LeftShift` down then `a` down
+
+use crate::{ConversionError, Key, KeyName, Keys, Modifier, Modifiers};
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum KeyAction {
+    Press,
+    /// Key is held, and the OS repeated the press
+    Repeat,
+    Release,
+}
+
+/// Which key of a backend event is used, where the event has both the key
+/// of the keyboard layout and the physical key
+///
+/// If the chosen key has no [`KeyName`], such as a dead key, the other is
+/// used.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum KeyPolicy {
+    /// Key of the keyboard layout, so bindings follow the letters printed on
+    /// the keys
+    #[default]
+    Logical,
+    /// Key at the position on a US keyboard, so bindings stay in place with
+    /// any keyboard layout
+    Physical,
+}
+
+/// Press or release of a single key, with no modifiers
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct KeyEvent {
+    pub name: KeyName,
+    pub action: KeyAction,
+}
+
+/// Merges key events into [`Key`]s, tracking which modifier keys are held
+///
+/// Left and right modifier keys are tracked separately, so releasing one
+/// shift key while the other is held keeps shift held.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ModifierFolder {
+    left: Modifiers,
+    right: Modifiers,
+}
+
+impl ModifierFolder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Modifiers which are currently held
+    pub fn modifiers(&self) -> Modifiers {
+        Modifiers {
+            shift: self.left.shift || self.right.shift,
+            control: self.left.control || self.right.control,
+            alt: self.left.alt || self.right.alt,
+            super_key: self.left.super_key || self.right.super_key,
+            primary: false,
+        }
+    }
+
+    /// Track a key event, returning the key if a key other than a modifier
+    /// key was pressed or repeated
+    pub fn fold(&mut self, event: KeyEvent) -> Option<Key> {
+        let Some((modifier, is_left)) = modifier_key(event.name) else {
+            return match event.action {
+                KeyAction::Press | KeyAction::Repeat => Some(Key {
+                    modifiers: self.modifiers(),
+                    name: event.name,
+                }),
+                KeyAction::Release => None,
+            };
+        };
+
+        let side = if is_left {
+            &mut self.left
+        } else {
+            &mut self.right
+        };
+        let held = match modifier {
+            Modifier::Control => &mut side.control,
+            Modifier::Alt => &mut side.alt,
+            Modifier::Shift => &mut side.shift,
+            Modifier::Super => &mut side.super_key,
+        };
+        *held = event.action != KeyAction::Release;
+        None
+    }
+
+    /// Release every modifier, such as when the window loses focus
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+impl Key {
+    /// Events which type the key, where left modifier keys are pressed
+    /// before the key and released after it
+    ///
+    /// [`Modifiers::primary`] must be resolved first with
+    /// [`Key::resolve_platform`].
+    pub fn to_events(&self) -> Result<Vec<KeyEvent>, ConversionError> {
+        let Modifiers {
+            shift,
+            control,
+            alt,
+            super_key,
+            ..
+        } = self.modifiers.require_resolved()?;
+
+        let modifier_keys: Vec<_> = [
+            (control, KeyName::LeftControl),
+            (alt, KeyName::LeftAlt),
+            (shift, KeyName::LeftShift),
+            (super_key, KeyName::LeftSuper),
+        ]
+        .into_iter()
+        .filter(|(is_held, _)| *is_held)
+        .map(|(_, name)| name)
+        .collect();
+
+        let event = |name, action| KeyEvent { name, action };
+        let mut events: Vec<_> = modifier_keys
+            .iter()
+            .map(|name| event(*name, KeyAction::Press))
+            .collect();
+        events.push(event(self.name, KeyAction::Press));
+        events.push(event(self.name, KeyAction::Release));
+        events.extend(
+            modifier_keys
+                .iter()
+                .rev()
+                .map(|name| event(*name, KeyAction::Release)),
+        );
+        Ok(events)
+    }
+}
+
+impl Keys {
+    /// Events which type each key in turn, as with [`Key::to_events`]
+    pub fn to_events(&self) -> Result<Vec<KeyEvent>, ConversionError> {
+        let mut events = Vec::new();
+        for key in &self.0 {
+            events.extend(key.to_events()?);
+        }
+        Ok(events)
+    }
+}
+
+/// Modifier of a modifier key, and whether it is the left key
+fn modifier_key(name: KeyName) -> Option<(Modifier, bool)> {
+    Some(match name {
+        KeyName::LeftShift => (Modifier::Shift, true),
+        KeyName::RightShift => (Modifier::Shift, false),
+        KeyName::LeftControl => (Modifier::Control, true),
+        KeyName::RightControl => (Modifier::Control, false),
+        KeyName::LeftAlt => (Modifier::Alt, true),
+        KeyName::RightAlt => (Modifier::Alt, false),
+        KeyName::LeftSuper => (Modifier::Super, true),
+        KeyName::RightSuper => (Modifier::Super, false),
+        _ => return None,
+    })
+}
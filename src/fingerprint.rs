@@ -0,0 +1,20 @@
+use siphasher::sip::SipHasher13;
+use std::hash::Hasher;
+
+use crate::Keys;
+
+impl Keys {
+    /// Salted fingerprint of the keys, which is stable across runs, for
+    /// counting how often keys are used without storing them
+    ///
+    /// Keys which are the same once [normalized](Keys::normalize) have the
+    /// same fingerprint. The fingerprint is a SipHash-1-3 of the notation of
+    /// the keys, keyed with the salt, so it changes if the notation of any of
+    /// the keys changes in another version. Keep the salt secret: without it,
+    /// fingerprints cannot be reversed by trying every short sequence.
+    pub fn fingerprint(&self, salt: &[u8; 16]) -> u64 {
+        let mut hasher = SipHasher13::new_with_key(salt);
+        hasher.write(self.normalize().to_string().as_bytes());
+        hasher.finish()
+    }
+}
@@ -0,0 +1,148 @@
+use crate::notation::Notation;
+use crate::{
+    parse_keys, parse_keys_with, ConversionError, Error, Key, Keys, Modifier, Modifiers,
+    ParserOptions,
+};
+use std::fmt;
+
+/// Options for writing keys in Vim-style notation
+///
+/// Other dialects are written with their [`Notation`], which this also
+/// implements, so either can be passed to [`Keys::format`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct FormatOptions {
+    /// Write shifted letters in uppercase, such as `A`, rather than as
+    /// `<S-a>`
+    pub uppercase: bool,
+    /// Write symbols as Vim-style named keys, such as `<lt>` and `<Bar>`,
+    /// rather than as `\<` and `|`, as with `{:#}`
+    pub named_symbols: bool,
+    /// Written between keys, such as `" "` for `<C-x> <C-s>`
+    ///
+    /// Keys can only be parsed back if this is empty or whitespace.
+    pub separator: &'static str,
+    /// Order that modifiers are written in, such as `<C-M-S-a>`
+    ///
+    /// [`Modifiers::primary`] is written after [`Modifier::Super`]. Modifiers
+    /// missing from the order are written last, in the default order.
+    pub modifier_order: [Modifier; 4],
+}
+
+/// Default order of modifiers, as in `<C-M-D-S-a>`
+const DEFAULT_ORDER: [Modifier; 4] = [
+    Modifier::Control,
+    Modifier::Alt,
+    Modifier::Super,
+    Modifier::Shift,
+];
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            uppercase: true,
+            named_symbols: false,
+            separator: "",
+            modifier_order: DEFAULT_ORDER,
+        }
+    }
+}
+
+impl Key {
+    /// Write the key in Vim-style notation with options
+    pub fn format_with(&self, options: &FormatOptions) -> String {
+        let mut output = String::new();
+        self.write_with(&mut output, options)
+            .expect("writing to a string cannot fail");
+        output
+    }
+
+    pub(crate) fn write_with(
+        &self,
+        f: &mut impl fmt::Write,
+        options: &FormatOptions,
+    ) -> fmt::Result {
+        let Modifiers {
+            shift,
+            control,
+            alt,
+            super_key,
+            primary,
+        } = self.modifiers;
+
+        // Shift is implied by the uppercase name, if the key has one
+        let upper = if shift && options.uppercase {
+            self.name.upper_str()
+        } else {
+            None
+        };
+        let shift = shift && upper.is_none();
+        let named = match upper {
+            None if options.named_symbols => self.name.named_str(),
+            _ => None,
+        };
+        let is_group = shift
+            || control
+            || alt
+            || super_key
+            || primary
+            || named.is_some()
+            || !self.name.is_printable();
+
+        if is_group {
+            f.write_str("<")?;
+        }
+        let order = options.modifier_order.iter().chain(&DEFAULT_ORDER);
+        for (i, modifier) in order.clone().enumerate() {
+            // Each modifier is written at its first position
+            if order.clone().take(i).any(|earlier| earlier == modifier) {
+                continue;
+            }
+            let (is_held, notation) = match modifier {
+                Modifier::Control => (control, "C-"),
+                Modifier::Alt => (alt, "M-"),
+                Modifier::Super => (super_key, "D-"),
+                Modifier::Shift => (shift, "S-"),
+            };
+            if is_held {
+                f.write_str(notation)?;
+            }
+            if *modifier == Modifier::Super && primary {
+                f.write_str("P-")?;
+            }
+        }
+        match upper.or(named) {
+            Some(name) => f.write_str(name)?,
+            None => write!(f, "{}", self.name)?,
+        }
+        if is_group {
+            f.write_str(">")?;
+        }
+        Ok(())
+    }
+}
+
+impl Keys {
+    /// Write the keys in Vim-style notation with options
+    pub fn format_with(&self, options: &FormatOptions) -> String {
+        let keys: Vec<_> = self.0.iter().map(|key| key.format_with(options)).collect();
+        keys.join(options.separator)
+    }
+}
+
+impl Notation for FormatOptions {
+    fn parse(&self, input: &str) -> Result<Keys, Error> {
+        if self.separator.trim().is_empty() && !self.separator.is_empty() {
+            let options = ParserOptions {
+                whitespace_separated: true,
+                ..Default::default()
+            };
+            parse_keys_with(input, &options)
+        } else {
+            parse_keys(input)
+        }
+    }
+
+    fn format(&self, keys: &Keys) -> Result<String, ConversionError> {
+        Ok(keys.format_with(self))
+    }
+}
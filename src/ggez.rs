@@ -1,17 +1,23 @@
-use crate::{Key, Modifiers};
+use crate::{ConversionError, Key, Modifiers, ShiftedSymbols};
 use ggez::input::keyboard::{KeyInput, KeyMods};
 
+/// With shift held, digits and symbols become the symbol they type on a US
+/// keyboard, so shift and `1` is `!`, as with [`ShiftedSymbols::ToSymbol`]
 impl TryFrom<KeyInput> for Key {
-    type Error = ();
+    type Error = ConversionError;
     fn try_from(input: KeyInput) -> Result<Self, Self::Error> {
-        let name = input.keycode.ok_or(())?.try_into()?;
+        let name = input
+            .keycode
+            .ok_or(ConversionError::NoKeycode)?
+            .try_into()?;
         let modifiers = input.mods.try_into()?;
-        Ok(Key { name, modifiers })
+        Ok(Key { name, modifiers }.normalize_with(ShiftedSymbols::ToSymbol))
     }
 }
 
+/// Fails if the logo key is held, which has no matching modifier
 impl TryFrom<KeyMods> for Modifiers {
-    type Error = ();
+    type Error = ConversionError;
     fn try_from(mods: KeyMods) -> Result<Self, Self::Error> {
         let mut modifiers = Modifiers::default();
 
@@ -25,7 +31,7 @@ impl TryFrom<KeyMods> for Modifiers {
             modifiers.alt = true;
         }
         if mods.contains(KeyMods::LOGO) {
-            return Err(());
+            return Err(ConversionError::UnsupportedKey("LOGO".to_string()));
         }
 
         Ok(modifiers)
@@ -0,0 +1,169 @@
+//! The key notation grammar, as data
+//!
+//! [`RULES`] describes the syntax accepted by [`parse_keys`](crate::parse_keys)
+//! with default options. It only covers syntax, so a string can be valid
+//! syntax and still have an unknown key name or modifier.
+//!
+//! [`ebnf`] renders the rules as EBNF, and [`is_valid_syntax`] is a reference
+//! recognizer which interprets them directly, so other implementations can be
+//! checked against the same data.
+
+use std::fmt::Write;
+
+/// Expression on the right-hand side of a rule
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Expr {
+    /// Exact text
+    Literal(&'static str),
+    /// Any single character not in the string
+    AnyCharExcept(&'static str),
+    /// Another rule, by name
+    Rule(&'static str),
+    /// Each expression in order
+    Sequence(&'static [Expr]),
+    /// Any one of the expressions
+    Choice(&'static [Expr]),
+    /// Zero or more of the expression
+    Repeat(&'static Expr),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rule {
+    pub name: &'static str,
+    pub expr: Expr,
+}
+
+/// Grammar rules, starting with the rule for a whole key sequence
+pub const RULES: &[Rule] = &[
+    Rule {
+        name: "keys",
+        expr: Expr::Repeat(&Expr::Rule("key")),
+    },
+    Rule {
+        name: "key",
+        expr: Expr::Choice(&[
+            Expr::Rule("group"),
+            Expr::Rule("escaped"),
+            Expr::AnyCharExcept("<>\\"),
+        ]),
+    },
+    Rule {
+        name: "escaped",
+        expr: Expr::Sequence(&[Expr::Literal("\\"), Expr::AnyCharExcept("")]),
+    },
+    // Empty parts between dashes are ignored, so `<C--a>` is `<C-a>`
+    Rule {
+        name: "group",
+        expr: Expr::Sequence(&[
+            Expr::Literal("<"),
+            Expr::Repeat(&Expr::Literal("-")),
+            Expr::Rule("part"),
+            Expr::Repeat(&Expr::Sequence(&[
+                Expr::Literal("-"),
+                Expr::Repeat(&Expr::Literal("-")),
+                Expr::Rule("part"),
+            ])),
+            Expr::Repeat(&Expr::Literal("-")),
+            Expr::Literal(">"),
+        ]),
+    },
+    Rule {
+        name: "part",
+        expr: Expr::Sequence(&[
+            Expr::Rule("part_char"),
+            Expr::Repeat(&Expr::Rule("part_char")),
+        ]),
+    },
+    Rule {
+        name: "part_char",
+        expr: Expr::Choice(&[Expr::Rule("escaped"), Expr::AnyCharExcept("<>-\\")]),
+    },
+];
+
+/// Render the grammar as EBNF
+pub fn ebnf() -> String {
+    let mut output = String::new();
+    for rule in RULES {
+        writeln!(output, "{} = {} ;", rule.name, render(rule.expr)).expect("write to string");
+    }
+    output
+}
+
+fn render(expr: Expr) -> String {
+    match expr {
+        Expr::Literal(text) => format!("{:?}", text),
+        Expr::AnyCharExcept("") => "? any character ?".to_string(),
+        Expr::AnyCharExcept(chars) => format!("? any character except {:?} ?", chars),
+        Expr::Rule(name) => name.to_string(),
+        Expr::Sequence(exprs) => {
+            let exprs: Vec<_> = exprs.iter().map(|expr| render(*expr)).collect();
+            exprs.join(" , ")
+        }
+        Expr::Choice(exprs) => {
+            let exprs: Vec<_> = exprs.iter().map(|expr| render(*expr)).collect();
+            format!("( {} )", exprs.join(" | "))
+        }
+        Expr::Repeat(expr) => format!("{{ {} }}", render(*expr)),
+    }
+}
+
+/// Whether the input is valid key notation syntax, according to [`RULES`]
+pub fn is_valid_syntax(input: &str) -> bool {
+    let chars: Vec<char> = input.chars().collect();
+    let start = Expr::Rule(RULES[0].name);
+    matches(start, &chars, 0).contains(&chars.len())
+}
+
+/// Every position the expression can end at, when starting at `start`
+fn matches(expr: Expr, chars: &[char], start: usize) -> Vec<usize> {
+    match expr {
+        Expr::Literal(text) => {
+            let text: Vec<char> = text.chars().collect();
+            let end = start + text.len();
+            if chars.get(start..end) == Some(&text[..]) {
+                vec![end]
+            } else {
+                vec![]
+            }
+        }
+        Expr::AnyCharExcept(except) => match chars.get(start) {
+            Some(ch) if !except.contains(*ch) => vec![start + 1],
+            _ => vec![],
+        },
+        Expr::Rule(name) => {
+            let rule = RULES
+                .iter()
+                .find(|rule| rule.name == name)
+                .expect("rule should exist");
+            matches(rule.expr, chars, start)
+        }
+        Expr::Sequence(exprs) => {
+            let mut ends = vec![start];
+            for expr in exprs {
+                ends = dedup(ends.iter().flat_map(|end| matches(*expr, chars, *end)));
+            }
+            ends
+        }
+        Expr::Choice(exprs) => dedup(exprs.iter().flat_map(|expr| matches(*expr, chars, start))),
+        Expr::Repeat(expr) => {
+            let mut ends = vec![start];
+            let mut frontier = vec![start];
+            while !frontier.is_empty() {
+                let next = frontier
+                    .iter()
+                    .flat_map(|end| matches(*expr, chars, *end))
+                    .filter(|end| !ends.contains(end));
+                frontier = dedup(next);
+                ends.extend(&frontier);
+            }
+            ends
+        }
+    }
+}
+
+fn dedup(ends: impl Iterator<Item = usize>) -> Vec<usize> {
+    let mut ends: Vec<usize> = ends.collect();
+    ends.sort_unstable();
+    ends.dedup();
+    ends
+}
@@ -0,0 +1,174 @@
+//! Conversion from [`iced_core`] keyboard events, and subscriptions which
+//! send keys or the messages of key bindings
+
+use crate::{ConversionError, Key, KeyName, KeySequence, Keys, Modifiers, ShiftedSymbols};
+use iced_core::event::{self, Event};
+use iced_core::keyboard::{self, key::Named};
+use iced_futures::futures::{future, StreamExt};
+use iced_futures::subscription::{self, EventStream, Hasher, Recipe};
+use iced_futures::{boxed_stream, BoxStream, MaybeSend, Subscription};
+use std::hash::Hash;
+
+/// Keys which are not characters
+const NAMED: &[(KeyName, Named)] = &[
+    (KeyName::Enter, Named::Enter),
+    (KeyName::Tab, Named::Tab),
+    (KeyName::Space, Named::Space),
+    (KeyName::Escape, Named::Escape),
+    (KeyName::Backspace, Named::Backspace),
+    (KeyName::Delete, Named::Delete),
+    (KeyName::Insert, Named::Insert),
+    (KeyName::Home, Named::Home),
+    (KeyName::End, Named::End),
+    (KeyName::PageUp, Named::PageUp),
+    (KeyName::PageDown, Named::PageDown),
+    (KeyName::Up, Named::ArrowUp),
+    (KeyName::Down, Named::ArrowDown),
+    (KeyName::Left, Named::ArrowLeft),
+    (KeyName::Right, Named::ArrowRight),
+    (KeyName::F1, Named::F1),
+    (KeyName::F2, Named::F2),
+    (KeyName::F3, Named::F3),
+    (KeyName::F4, Named::F4),
+    (KeyName::F5, Named::F5),
+    (KeyName::F6, Named::F6),
+    (KeyName::F7, Named::F7),
+    (KeyName::F8, Named::F8),
+    (KeyName::F9, Named::F9),
+    (KeyName::F10, Named::F10),
+    (KeyName::F11, Named::F11),
+    (KeyName::F12, Named::F12),
+    (KeyName::CapsLock, Named::CapsLock),
+    // Keys do not say which side, so left is used
+    (KeyName::LeftShift, Named::Shift),
+    (KeyName::LeftControl, Named::Control),
+    (KeyName::LeftAlt, Named::Alt),
+    (KeyName::LeftSuper, Named::Super),
+];
+
+impl Key {
+    /// Key of an iced key press, such as from
+    /// [`on_key_press`](iced_futures::keyboard::on_key_press)
+    ///
+    /// The key is not changed by shift, so shift and `1` is
+    /// `<S-1>`, which becomes `!`, as with [`ShiftedSymbols::ToSymbol`].
+    pub fn from_iced(
+        key: &keyboard::Key,
+        modifiers: keyboard::Modifiers,
+    ) -> Result<Key, ConversionError> {
+        let unsupported = || ConversionError::UnsupportedKey(format!("{:?}", key));
+        let mut modifiers = Modifiers::from(modifiers);
+        let name = match key {
+            keyboard::Key::Character(text) => {
+                let mut chars = text.chars();
+                let (Some(ch), None) = (chars.next(), chars.next()) else {
+                    return Err(unsupported());
+                };
+                let (name, shift) = KeyName::from_char(ch);
+                modifiers.shift |= shift;
+                name
+            }
+            keyboard::Key::Named(named) => NAMED
+                .iter()
+                .find(|(_, known)| known == named)
+                .map(|(name, _)| *name)
+                .ok_or_else(unsupported)?,
+            keyboard::Key::Unidentified => return Err(unsupported()),
+        };
+        Ok(Key { modifiers, name }.normalize_with(ShiftedSymbols::ToSymbol))
+    }
+}
+
+/// Logo is super
+impl From<keyboard::Modifiers> for Modifiers {
+    fn from(modifiers: keyboard::Modifiers) -> Self {
+        Modifiers {
+            shift: modifiers.shift(),
+            control: modifiers.control(),
+            alt: modifiers.alt(),
+            super_key: modifiers.logo(),
+            primary: false,
+        }
+    }
+}
+
+/// [`Modifiers::primary`] must be resolved first with
+/// [`Modifiers::resolve_platform`]
+impl TryFrom<Modifiers> for keyboard::Modifiers {
+    type Error = ConversionError;
+    fn try_from(modifiers: Modifiers) -> Result<Self, Self::Error> {
+        let modifiers = modifiers.require_resolved()?;
+        let mut mods = keyboard::Modifiers::empty();
+        mods.set(keyboard::Modifiers::SHIFT, modifiers.shift);
+        mods.set(keyboard::Modifiers::CTRL, modifiers.control);
+        mods.set(keyboard::Modifiers::ALT, modifiers.alt);
+        mods.set(keyboard::Modifiers::LOGO, modifiers.super_key);
+        Ok(mods)
+    }
+}
+
+/// Keys which are pressed, other than those which a widget used
+pub fn key_presses() -> Subscription<Key> {
+    iced_futures::keyboard::on_key_press(|key, modifiers| Key::from_iced(&key, modifiers).ok())
+}
+
+/// Messages of key bindings, sent when their keys are typed in order
+///
+/// Typed keys are tracked in a [`KeySequence`], which is cleared when a
+/// binding matches. If the keys of several bindings were typed, the first
+/// binding is used. Keys with [`Modifiers::primary`] never match, so they
+/// must be resolved first with [`Keys::resolve_platform`].
+pub fn key_bindings<M>(bindings: impl IntoIterator<Item = (Keys, M)>) -> Subscription<M>
+where
+    M: Clone + Hash + MaybeSend + 'static,
+{
+    subscription::from_recipe(KeyBindings {
+        bindings: bindings.into_iter().collect(),
+    })
+}
+
+struct KeyBindings<M> {
+    bindings: Vec<(Keys, M)>,
+}
+
+impl<M> Recipe for KeyBindings<M>
+where
+    M: Clone + Hash + MaybeSend + 'static,
+{
+    type Output = M;
+
+    fn hash(&self, state: &mut Hasher) {
+        std::any::TypeId::of::<Self>().hash(state);
+        self.bindings.hash(state);
+    }
+
+    fn stream(self: Box<Self>, input: EventStream) -> BoxStream<M> {
+        let limit = self
+            .bindings
+            .iter()
+            .map(|(keys, _)| keys.as_slice().len())
+            .max()
+            .unwrap_or_default();
+        let mut sequence = KeySequence::with_limit(limit);
+        boxed_stream(input.filter_map(move |event| {
+            let subscription::Event::Interaction {
+                event: Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. }),
+                status: event::Status::Ignored,
+                ..
+            } = event
+            else {
+                return future::ready(None);
+            };
+            let Ok(key) = Key::from_iced(&key, modifiers) else {
+                return future::ready(None);
+            };
+            sequence.push(key);
+            let message = self
+                .bindings
+                .iter()
+                .find(|(keys, _)| sequence.take(keys))
+                .map(|(_, message)| message.clone());
+            future::ready(message)
+        }))
+    }
+}
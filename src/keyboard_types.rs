@@ -0,0 +1,80 @@
+//! Conversion to and from [`keyboard_types`], which uses the W3C UI Events
+//! values
+
+use crate::{ConversionError, Key, KeyName, Modifiers};
+use keyboard_types::{Code, KeyboardEvent};
+
+/// Logical key of the event with its modifiers, so shift and `1` is `!`
+impl TryFrom<&KeyboardEvent> for Key {
+    type Error = ConversionError;
+    fn try_from(event: &KeyboardEvent) -> Result<Self, Self::Error> {
+        Key::from_w3c_key(&event.key.to_string(), event.modifiers.into())
+            .ok_or_else(|| ConversionError::UnsupportedKey(event.key.to_string()))
+    }
+}
+
+/// Logical key value, where modifiers other than shift are not kept
+impl TryFrom<Key> for keyboard_types::Key {
+    type Error = ConversionError;
+    fn try_from(key: Key) -> Result<Self, Self::Error> {
+        key.to_w3c_key()
+            .and_then(|value| value.parse().ok())
+            .ok_or_else(|| ConversionError::UnsupportedKey(key.to_string()))
+    }
+}
+
+/// Logical key value with no modifiers, other than shift for characters, so
+/// `A` is `<S-a>`
+impl TryFrom<keyboard_types::Key> for Key {
+    type Error = ConversionError;
+    fn try_from(key: keyboard_types::Key) -> Result<Self, Self::Error> {
+        Key::from_w3c_key(&key.to_string(), Modifiers::default())
+            .ok_or_else(|| ConversionError::UnsupportedKey(key.to_string()))
+    }
+}
+
+impl TryFrom<Code> for KeyName {
+    type Error = ConversionError;
+    fn try_from(code: Code) -> Result<Self, Self::Error> {
+        KeyName::from_w3c_code(&code.to_string())
+            .ok_or_else(|| ConversionError::UnsupportedKey(code.to_string()))
+    }
+}
+
+impl TryFrom<KeyName> for Code {
+    type Error = ConversionError;
+    fn try_from(name: KeyName) -> Result<Self, Self::Error> {
+        name.to_w3c_code()
+            .and_then(|code| code.parse().ok())
+            .ok_or_else(|| ConversionError::UnsupportedKey(name.to_string()))
+    }
+}
+
+impl From<keyboard_types::Modifiers> for Modifiers {
+    fn from(mods: keyboard_types::Modifiers) -> Self {
+        use keyboard_types::Modifiers as Mods;
+        Modifiers {
+            shift: mods.contains(Mods::SHIFT),
+            control: mods.contains(Mods::CONTROL),
+            alt: mods.contains(Mods::ALT),
+            super_key: mods.contains(Mods::META),
+            primary: false,
+        }
+    }
+}
+
+/// [`Modifiers::super_key`] is `Meta`, and [`Modifiers::primary`] must be
+/// resolved first with [`Modifiers::resolve_platform`]
+impl TryFrom<Modifiers> for keyboard_types::Modifiers {
+    type Error = ConversionError;
+    fn try_from(modifiers: Modifiers) -> Result<Self, Self::Error> {
+        use keyboard_types::Modifiers as Mods;
+        let modifiers = modifiers.require_resolved()?;
+        let mut mods = Mods::empty();
+        mods.set(Mods::SHIFT, modifiers.shift);
+        mods.set(Mods::CONTROL, modifiers.control);
+        mods.set(Mods::ALT, modifiers.alt);
+        mods.set(Mods::META, modifiers.super_key);
+        Ok(mods)
+    }
+}
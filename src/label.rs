@@ -0,0 +1,272 @@
+//! Labels of keys for showing to users, such as `⌘⇧P` or `Ctrl+Shift+P` in
+//! a menu
+
+use crate::notation::literal_char;
+use crate::{Key, KeyName, Keys, Modifiers, Platform};
+
+/// Modifier of a [`Key`], other than [`Modifiers::primary`]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Modifier {
+    Control,
+    Alt,
+    Shift,
+    Super,
+}
+
+/// Translated labels of modifiers and keys, where `None` uses the English
+/// label
+///
+/// Modifiers and most named keys are shown as symbols on macOS, which are not
+/// translated.
+pub trait KeyLabels {
+    /// Label of a modifier, such as `Strg` for [`Modifier::Control`]
+    fn modifier(&self, _modifier: Modifier) -> Option<&str> {
+        None
+    }
+
+    /// Label of a key, such as `Leertaste` for [`KeyName::Space`]
+    fn key(&self, _name: KeyName) -> Option<&str> {
+        None
+    }
+}
+
+impl<L: KeyLabels + ?Sized> KeyLabels for &L {
+    fn modifier(&self, modifier: Modifier) -> Option<&str> {
+        (**self).modifier(modifier)
+    }
+    fn key(&self, name: KeyName) -> Option<&str> {
+        (**self).key(name)
+    }
+}
+
+/// English labels, such as `Ctrl+Shift+P`
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct English;
+
+impl KeyLabels for English {}
+
+/// German labels, such as `Strg+Umschalt+P`
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct German;
+
+impl KeyLabels for German {
+    fn modifier(&self, modifier: Modifier) -> Option<&str> {
+        match modifier {
+            Modifier::Control => Some("Strg"),
+            Modifier::Shift => Some("Umschalt"),
+            Modifier::Alt | Modifier::Super => None,
+        }
+    }
+
+    fn key(&self, name: KeyName) -> Option<&str> {
+        Some(match name {
+            KeyName::Space => "Leertaste",
+            KeyName::Enter => "Eingabe",
+            KeyName::Backspace => "Rücktaste",
+            KeyName::Delete => "Entf",
+            KeyName::Insert => "Einfg",
+            KeyName::Home => "Pos1",
+            KeyName::End => "Ende",
+            KeyName::PageUp => "Bild auf",
+            KeyName::PageDown => "Bild ab",
+            KeyName::Up => "Oben",
+            KeyName::Down => "Unten",
+            KeyName::Left => "Links",
+            KeyName::Right => "Rechts",
+            KeyName::CapsLock => "Feststelltaste",
+            _ => return None,
+        })
+    }
+}
+
+/// French labels, such as `Ctrl+Maj+P`
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct French;
+
+impl KeyLabels for French {
+    fn modifier(&self, modifier: Modifier) -> Option<&str> {
+        match modifier {
+            Modifier::Shift => Some("Maj"),
+            Modifier::Control | Modifier::Alt | Modifier::Super => None,
+        }
+    }
+
+    fn key(&self, name: KeyName) -> Option<&str> {
+        Some(match name {
+            KeyName::Space => "Espace",
+            KeyName::Enter => "Entrée",
+            KeyName::Escape => "Échap",
+            KeyName::Backspace => "Retour arrière",
+            KeyName::Delete => "Suppr",
+            KeyName::Insert => "Inser",
+            KeyName::Home => "Origine",
+            KeyName::End => "Fin",
+            KeyName::PageUp => "Page préc",
+            KeyName::PageDown => "Page suiv",
+            KeyName::Up => "Haut",
+            KeyName::Down => "Bas",
+            KeyName::Left => "Gauche",
+            KeyName::Right => "Droite",
+            KeyName::CapsLock => "Verr Maj",
+            _ => return None,
+        })
+    }
+}
+
+impl Keys {
+    /// Label of the keys for the platform, where keys are separated by spaces
+    pub fn label(&self, platform: Platform) -> String {
+        self.label_with(platform, English)
+    }
+
+    /// Label of the keys for the platform, in a language
+    pub fn label_with(&self, platform: Platform, labels: impl KeyLabels) -> String {
+        let labels: Vec<_> = self
+            .0
+            .iter()
+            .map(|key| key.label_with(platform, &labels))
+            .collect();
+        labels.join(" ")
+    }
+}
+
+impl Key {
+    /// Label of the key for the platform, such as `⌘⇧P` on macOS,
+    /// `Ctrl+Alt+Del` on Windows, or `Ctrl+Alt+Delete` on Linux
+    pub fn label(&self, platform: Platform) -> String {
+        self.label_with(platform, English)
+    }
+
+    /// Label of the key for the platform, in a language
+    pub fn label_with(&self, platform: Platform, labels: impl KeyLabels) -> String {
+        let Modifiers {
+            shift,
+            control,
+            alt,
+            super_key,
+            ..
+        } = self.resolve_platform(platform).modifiers;
+
+        let mut output = String::new();
+        if platform == Platform::MacOs {
+            for (is_held, symbol) in [(control, '⌃'), (alt, '⌥'), (shift, '⇧'), (super_key, '⌘')]
+            {
+                if is_held {
+                    output.push(symbol);
+                }
+            }
+        } else {
+            for (is_held, modifier) in [
+                (control, Modifier::Control),
+                (alt, Modifier::Alt),
+                (shift, Modifier::Shift),
+                (super_key, Modifier::Super),
+            ] {
+                if is_held {
+                    let label = labels
+                        .modifier(modifier)
+                        .unwrap_or_else(|| modifier_label(modifier, platform));
+                    output.push_str(label);
+                    output.push('+');
+                }
+            }
+        }
+
+        // Symbols on macOS are not translated
+        let is_symbol = platform == Platform::MacOs && mac_symbol(self.name).is_some();
+        match labels.key(self.name) {
+            Some(label) if !is_symbol => output.push_str(label),
+            _ => output.push_str(&name_label(self.name, platform)),
+        }
+        output
+    }
+}
+
+fn modifier_label(modifier: Modifier, platform: Platform) -> &'static str {
+    match modifier {
+        Modifier::Control => "Ctrl",
+        Modifier::Alt => "Alt",
+        Modifier::Shift => "Shift",
+        Modifier::Super if platform == Platform::Windows => "Win",
+        Modifier::Super => "Super",
+    }
+}
+
+/// Symbol of a key on macOS, such as `↩`
+fn mac_symbol(name: KeyName) -> Option<&'static str> {
+    Some(match name {
+        KeyName::Enter => "↩",
+        KeyName::Tab => "⇥",
+        KeyName::Escape => "⎋",
+        KeyName::Backspace => "⌫",
+        KeyName::Delete => "⌦",
+        KeyName::Home => "↖",
+        KeyName::End => "↘",
+        KeyName::PageUp => "⇞",
+        KeyName::PageDown => "⇟",
+        KeyName::Up => "↑",
+        KeyName::Down => "↓",
+        KeyName::Left => "←",
+        KeyName::Right => "→",
+        KeyName::CapsLock => "⇪",
+        _ => return None,
+    })
+}
+
+fn name_label(name: KeyName, platform: Platform) -> String {
+    let is_mac = platform == Platform::MacOs;
+    let is_windows = platform == Platform::Windows;
+    if let Some(symbol) = mac_symbol(name).filter(|_| is_mac) {
+        return symbol.to_string();
+    }
+    let label = match name {
+        // Abbreviated, as in Windows menus
+        KeyName::Delete if is_windows => "Del",
+        KeyName::Insert if is_windows => "Ins",
+        KeyName::PageUp if is_windows => "PgUp",
+        KeyName::PageDown if is_windows => "PgDn",
+        KeyName::Space => "Space",
+        KeyName::Enter => "Enter",
+        KeyName::Tab => "Tab",
+        KeyName::Escape => "Esc",
+        KeyName::Backspace => "Backspace",
+        KeyName::Delete => "Delete",
+        KeyName::Insert => "Insert",
+        KeyName::Home => "Home",
+        KeyName::End => "End",
+        KeyName::PageUp => "Page Up",
+        KeyName::PageDown => "Page Down",
+        KeyName::Up => "Up",
+        KeyName::Down => "Down",
+        KeyName::Left => "Left",
+        KeyName::Right => "Right",
+        KeyName::CapsLock => "Caps Lock",
+        KeyName::LeftShift if is_mac => "Left ⇧",
+        KeyName::RightShift if is_mac => "Right ⇧",
+        KeyName::LeftControl if is_mac => "Left ⌃",
+        KeyName::RightControl if is_mac => "Right ⌃",
+        KeyName::LeftAlt if is_mac => "Left ⌥",
+        KeyName::RightAlt if is_mac => "Right ⌥",
+        KeyName::LeftSuper if is_mac => "Left ⌘",
+        KeyName::RightSuper if is_mac => "Right ⌘",
+        KeyName::LeftShift => "Left Shift",
+        KeyName::RightShift => "Right Shift",
+        KeyName::LeftControl => "Left Ctrl",
+        KeyName::RightControl => "Right Ctrl",
+        KeyName::LeftAlt => "Left Alt",
+        KeyName::RightAlt => "Right Alt",
+        KeyName::LeftSuper if is_windows => "Left Win",
+        KeyName::RightSuper if is_windows => "Right Win",
+        KeyName::LeftSuper => "Left Super",
+        KeyName::RightSuper => "Right Super",
+        KeyName::Unknown(code) => return format!("Unknown:{}", code),
+        _ => match (name.upper_str(), literal_char(name)) {
+            // Letters are shown in uppercase, as on the keyboard
+            (Some(upper), _) => upper,
+            (_, Some(ch)) => return ch.to_string(),
+            // `F5`
+            _ => name.named_str().expect("named key"),
+        },
+    };
+    label.to_string()
+}
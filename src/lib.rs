@@ -1,22 +1,88 @@
+#[cfg(feature = "bench-api")]
+pub mod bench;
+#[cfg(feature = "bevy")]
+pub mod bevy;
+mod builder;
+mod cache;
+#[cfg(feature = "conformance")]
+pub mod conformance;
+#[cfg(feature = "crokey")]
+mod crokey;
+#[cfg(feature = "crossterm")]
+mod crossterm;
+#[cfg(feature = "diagnostics")]
+mod diagnostics;
+#[cfg(feature = "egui")]
+mod egui;
+mod events;
+mod fingerprint;
+mod format;
 #[cfg(feature = "ggez")]
 mod ggez;
+pub mod grammar;
+#[cfg(feature = "iced")]
+pub mod iced;
+#[cfg(feature = "keyboard-types")]
+mod keyboard_types;
+mod label;
+#[cfg(feature = "macroquad")]
+pub mod macroquad;
+mod normalize;
+pub mod notation;
+mod partial;
+mod platform;
+#[cfg(feature = "sdl2")]
+mod sdl2;
+mod sequence;
+mod suggest;
+pub mod terminal;
 #[cfg(test)]
 mod tests;
+pub mod token;
+mod vim_map;
+mod w3c;
+#[cfg(feature = "winit")]
+mod winit;
 
-#[derive(Clone, Debug, PartialEq)]
+pub use builder::KeysBuilder;
+pub use cache::KeysCache;
+#[cfg(feature = "egui")]
+pub use egui::ConsumeKey;
+pub use events::{KeyAction, KeyEvent, KeyPolicy, ModifierFolder};
+pub use format::FormatOptions;
+pub use grammar::is_valid_syntax;
+pub use label::{English, French, German, KeyLabels, Modifier};
+pub use normalize::ShiftedSymbols;
+pub use partial::{parse_keys_partial, PartialParse};
+pub use platform::Platform;
+pub use sequence::KeySequence;
+pub use token::tokenize;
+pub use vim_map::{parse_map_command, MapCommand, MapMode};
+
+use notation::Notation;
+use std::borrow::Cow;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct Keys(Vec<Key>);
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct Key {
     pub modifiers: Modifiers,
     pub name: KeyName,
 }
 
-#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
 pub struct Modifiers {
     pub shift: bool,
     pub control: bool,
     pub alt: bool,
+    pub super_key: bool,
+    /// Super on macOS and control elsewhere, written as `<P-s>`, until
+    /// resolved with [`Keys::resolve_platform`]
+    pub primary: bool,
 }
 
 macro_rules! define_key_name {
@@ -24,38 +90,116 @@ macro_rules! define_key_name {
         $ident:ident,
         $($lower:literal)?,
         $($upper:literal)?,
+        $($named:literal)?,
         $($ggez:ident)?
     );* $(;)? ) =>{
-        #[derive(Clone, Copy, Debug, PartialEq)]
+        #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+        #[non_exhaustive]
         pub enum KeyName {
-            $( $ident ),*
+            $( $ident, )*
+            /// Any other character, such as `é` or `ß`
+            Char(char),
+            /// Raw backend keycode with no matching key name, written as
+            /// `<Unknown:123>`
+            Unknown(u32),
         }
 
         impl KeyName {
+            /// Every key name, other than `Char` and `Unknown`
+            #[allow(dead_code)]
+            pub(crate) const ALL: &'static [KeyName] = &[ $( KeyName::$ident ),* ];
+
+            #[allow(clippy::should_implement_trait)]
             pub fn from_str(value: &str) -> Option<(Self, bool)> {
                 Some(match value {
                     $(
                         $( $lower => (KeyName::$ident, false), )?
                         $( $upper => (KeyName::$ident, true), )?
                     )*
-                    _ => return None,
+                    _ => {
+                        let mut chars = value.chars();
+                        match (chars.next(), chars.next()) {
+                            (Some(ch), None) if !ch.is_ascii() => (KeyName::Char(ch), false),
+                            _ => return None,
+                        }
+                    }
+                })
+            }
+
+            /// Key name for a Vim-style named key, such as `lt` or `Space`
+            ///
+            /// Names are case-insensitive
+            pub fn from_name(value: &str) -> Option<Self> {
+                Some(match value {
+                    $( $( _ if value.eq_ignore_ascii_case($named) => KeyName::$ident, )? )*
+                    _ => match value.split_once(':') {
+                        Some((prefix, code)) if prefix.eq_ignore_ascii_case("Unknown") => {
+                            KeyName::Unknown(code.parse().ok()?)
+                        }
+                        _ => return None,
+                    },
                 })
             }
+
+            /// Key name for a character, and whether it is shifted
+            pub fn from_char(ch: char) -> (Self, bool) {
+                match ch {
+                    ' ' => (KeyName::Space, false),
+                    _ => KeyName::from_str(&escape_notation(ch))
+                        .unwrap_or((KeyName::Char(ch), false)),
+                }
+            }
+
+            fn lower_str(self) -> Option<&'static str> {
+                match self {
+                    $( $( KeyName::$ident => Some($lower), )? )*
+                    _ => None,
+                }
+            }
+
+            /// Position of the variant in declaration order
+            fn index(self) -> u32 {
+                enum Index {
+                    $( $ident, )*
+                    Char,
+                    Unknown,
+                }
+                match self {
+                    $( KeyName::$ident => Index::$ident as u32, )*
+                    KeyName::Char(_) => Index::Char as u32,
+                    KeyName::Unknown(_) => Index::Unknown as u32,
+                }
+            }
+
+            pub(crate) fn named_str(self) -> Option<&'static str> {
+                match self {
+                    $( $( KeyName::$ident => Some($named), )? )*
+                    _ => None,
+                }
+            }
+
+            fn upper_str(self) -> Option<&'static str> {
+                match self {
+                    $( $( KeyName::$ident => Some($upper), )? )*
+                    _ => None,
+                }
+            }
         }
 
         #[cfg(feature = "ggez")]
         mod ggez_key_name {
             use ggez::input::keyboard::KeyCode;
             use super::KeyName;
+            use crate::ConversionError;
             impl TryFrom<KeyCode> for KeyName {
-                type Error = ();
+                type Error = ConversionError;
                 fn try_from(keycode: KeyCode) -> Result<Self, Self::Error> {
-                    Ok(match keycode {
+                    match keycode {
                         $(
-                            $( KeyCode::$ggez => KeyName::$ident, )?
+                            $( KeyCode::$ggez => Ok(KeyName::$ident), )?
                         )*
-                        _ => return Err(()),
-                    })
+                        _ => Err(ConversionError::UnsupportedKey(format!("{:?}", keycode))),
+                    }
                 }
             }
         }
@@ -63,84 +207,272 @@ macro_rules! define_key_name {
 }
 
 define_key_name!(
-    A,            "a", "A", A;
-    B,            "b", "B", B;
-    C,            "c", "C", C;
-    D,            "d", "D", D;
-    E,            "e", "E", E;
-    F,            "f", "F", F;
-    G,            "g", "G", G;
-    H,            "h", "H", H;
-    I,            "i", "I", I;
-    J,            "j", "J", J;
-    K,            "k", "K", K;
-    L,            "l", "L", L;
-    M,            "m", "M", M;
-    N,            "n", "N", N;
-    O,            "o", "O", O;
-    P,            "p", "P", P;
-    Q,            "q", "Q", Q;
-    R,            "r", "R", R;
-    S,            "s", "S", S;
-    T,            "t", "T", T;
-    U,            "u", "U", U;
-    V,            "v", "V", V;
-    W,            "w", "W", W;
-    X,            "x", "X", X;
-    Y,            "y", "Y", Y;
-    Z,            "z", "Z", Z;
-    Number0,      "0",    , Key0;
-    Number1,      "1",    , Key1;
-    Number2,      "2",    , Key2;
-    Number3,      "3",    , Key3;
-    Number4,      "4",    , Key4;
-    Number5,      "5",    , Key5;
-    Number6,      "6",    , Key6;
-    Number7,      "7",    , Key7;
-    Number8,      "8",    , Key8;
-    Number9,      "9",    , Key9;
-    Bang,         "!",    , ;
-    At,           "@",    , ;
-    Pound,        "#",    , ;
-    Dollar,       "$",    , ;
-    Percent,      "%",    , ;
-    Carrot,       "^",    , ;
-    Ampersand,    "&",    , ;
-    Star,         "*",    , ;
-    ParenLeft,    "(",    , ;
-    ParenRight,   ")",    , ;
-    BracketLeft,  "[",    , ;
-    BracketRight, "]",    , ;
-    BraceLeft,    "{",    , ;
-    BraceRight,   "}",    , ;
-    Backtick,     "`",    , ;
-    Tilde,        "~",    , ;
-    Equals,       "=",    , ;
-    Underscore,   "_",    , ;
-    Plus,         "+",    , ;
-    ForwardSlash, "/",    , ;
-    Backslash,    "\\",   , ;
-    Question,     "?",    , ;
-    Pipe,         "|",    , ;
-    SingleQuote,  "'",    , ;
-    DoubleQuote,  "\"",   , ;
-    Comma,        ",",    , ;
-    Period,       ".",    , ;
-    Colon,        ":",    , ;
-    Semicolon,    ";",    , ;
-    Dash,         "\\-",  , ;
-    LessThan,     "\\<",  , ;
-    GreaterThan,  "\\>",  , ;
-    Space,        ,       , Space;
+    A,            "a",   "A", ,         A;
+    B,            "b",   "B", ,         B;
+    C,            "c",   "C", ,         C;
+    D,            "d",   "D", ,         D;
+    E,            "e",   "E", ,         E;
+    F,            "f",   "F", ,         F;
+    G,            "g",   "G", ,         G;
+    H,            "h",   "H", ,         H;
+    I,            "i",   "I", ,         I;
+    J,            "j",   "J", ,         J;
+    K,            "k",   "K", ,         K;
+    L,            "l",   "L", ,         L;
+    M,            "m",   "M", ,         M;
+    N,            "n",   "N", ,         N;
+    O,            "o",   "O", ,         O;
+    P,            "p",   "P", ,         P;
+    Q,            "q",   "Q", ,         Q;
+    R,            "r",   "R", ,         R;
+    S,            "s",   "S", ,         S;
+    T,            "t",   "T", ,         T;
+    U,            "u",   "U", ,         U;
+    V,            "v",   "V", ,         V;
+    W,            "w",   "W", ,         W;
+    X,            "x",   "X", ,         X;
+    Y,            "y",   "Y", ,         Y;
+    Z,            "z",   "Z", ,         Z;
+    Number0,      "0",   ,    ,         Key0;
+    Number1,      "1",   ,    ,         Key1;
+    Number2,      "2",   ,    ,         Key2;
+    Number3,      "3",   ,    ,         Key3;
+    Number4,      "4",   ,    ,         Key4;
+    Number5,      "5",   ,    ,         Key5;
+    Number6,      "6",   ,    ,         Key6;
+    Number7,      "7",   ,    ,         Key7;
+    Number8,      "8",   ,    ,         Key8;
+    Number9,      "9",   ,    ,         Key9;
+    Bang,         "!",   ,    ,         ;
+    At,           "@",   ,    ,         At;
+    Pound,        "#",   ,    ,         ;
+    Dollar,       "$",   ,    ,         ;
+    Percent,      "%",   ,    ,         ;
+    Carrot,       "^",   ,    ,         Caret;
+    Ampersand,    "&",   ,    ,         ;
+    Star,         "*",   ,    ,         Asterisk;
+    ParenLeft,    "(",   ,    ,         ;
+    ParenRight,   ")",   ,    ,         ;
+    BracketLeft,  "[",   ,    ,         LBracket;
+    BracketRight, "]",   ,    ,         RBracket;
+    BraceLeft,    "{",   ,    ,         ;
+    BraceRight,   "}",   ,    ,         ;
+    Backtick,     "`",   ,    ,         Grave;
+    Tilde,        "~",   ,    ,         ;
+    Equals,       "=",   ,    ,         Equals;
+    Underscore,   "_",   ,    ,         Underline;
+    Plus,         "+",   ,    ,         Plus;
+    ForwardSlash, "/",   ,    ,         Slash;
+    Backslash,    "\\\\", ,   "Bslash", Backslash;
+    Question,     "?",   ,    ,         ;
+    Pipe,         "|",   ,    "Bar",    ;
+    SingleQuote,  "'",   ,    ,         Apostrophe;
+    DoubleQuote,  "\"",  ,    ,         ;
+    Comma,        ",",   ,    ,         Comma;
+    Period,       ".",   ,    ,         Period;
+    Colon,        ":",   ,    ,         Colon;
+    Semicolon,    ";",   ,    ,         Semicolon;
+    Dash,         "\\-", ,    "Minus",  Minus;
+    LessThan,     "\\<", ,    "lt",     ;
+    GreaterThan,  "\\>", ,    "gt",     ;
+    Space,        ,      ,    "Space",  Space;
+    Escape,       ,      ,    "Esc",    Escape;
+    Enter,        ,      ,    "CR",     Return;
+    Tab,          ,      ,    "Tab",    Tab;
+    Backspace,    ,      ,    "BS",     Back;
+    Delete,       ,      ,    "Del",    Delete;
+    Insert,       ,      ,    "Insert", Insert;
+    Home,         ,      ,    "Home",   Home;
+    End,          ,      ,    "End",    End;
+    PageUp,       ,      ,    "PageUp", PageUp;
+    PageDown,     ,      ,    "PageDown", PageDown;
+    Up,           ,      ,    "Up",     Up;
+    Down,         ,      ,    "Down",   Down;
+    Left,         ,      ,    "Left",   Left;
+    Right,        ,      ,    "Right",  Right;
+    F1,           ,      ,    "F1",     F1;
+    F2,           ,      ,    "F2",     F2;
+    F3,           ,      ,    "F3",     F3;
+    F4,           ,      ,    "F4",     F4;
+    F5,           ,      ,    "F5",     F5;
+    F6,           ,      ,    "F6",     F6;
+    F7,           ,      ,    "F7",     F7;
+    F8,           ,      ,    "F8",     F8;
+    F9,           ,      ,    "F9",     F9;
+    F10,          ,      ,    "F10",    F10;
+    F11,          ,      ,    "F11",    F11;
+    F12,          ,      ,    "F12",    F12;
+    CapsLock,     ,      ,    "CapsLock", Capital;
+    LeftShift,    ,      ,    "LShift", LShift;
+    RightShift,   ,      ,    "RShift", RShift;
+    LeftControl,  ,      ,    "LCtrl",  LControl;
+    RightControl, ,      ,    "RCtrl",  RControl;
+    LeftAlt,      ,      ,    "LAlt",   LAlt;
+    RightAlt,     ,      ,    "RAlt",   RAlt;
+    LeftSuper,    ,      ,    "LSuper", LWin;
+    RightSuper,   ,      ,    "RSuper", RWin;
 );
 
+impl Key {
+    /// Key packed into a single integer
+    ///
+    /// Bits `0..32` are the character or keycode of `Char` and `Unknown`,
+    /// bits `32..48` are the key name variant, and bits `48..53` are the
+    /// modifiers
+    fn packed(self) -> u64 {
+        let payload = match self.name {
+            KeyName::Char(ch) => ch as u32,
+            KeyName::Unknown(code) => code,
+            _ => 0,
+        };
+        let Modifiers {
+            shift,
+            control,
+            alt,
+            super_key,
+            primary,
+        } = self.modifiers;
+        let modifiers = u64::from(shift)
+            | u64::from(control) << 1
+            | u64::from(alt) << 2
+            | u64::from(super_key) << 3
+            | u64::from(primary) << 4;
+
+        u64::from(payload) | u64::from(self.name.index()) << 32 | modifiers << 48
+    }
+}
+
+/// Hashes as a single `u64`, which is cheap with fast hashers such as
+/// `fxhash` and `ahash`
+///
+/// The value written to the hasher is the same across runs and platforms,
+/// for a given version of this crate. Whether the resulting hash is stable
+/// depends on the hasher.
+impl Hash for Key {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.packed());
+    }
+}
+
+/// Hashes the same as the equivalent [`Key`] without modifiers
+impl Hash for KeyName {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let key = Key {
+            modifiers: Modifiers::default(),
+            name: *self,
+        };
+        key.hash(state);
+    }
+}
+
+impl Keys {
+    /// Parse keys written in any notation, such as
+    /// [`Vim`](notation::Vim)
+    pub fn parse_with(notation: impl Notation, input: &str) -> Result<Keys, Error> {
+        notation.parse(input)
+    }
+
+    /// Format keys in any notation
+    pub fn format(&self, notation: impl Notation) -> Result<String, ConversionError> {
+        notation.format(self)
+    }
+
+    pub fn as_slice(&self) -> &[Key] {
+        &self.0
+    }
+}
+
+impl From<Vec<Key>> for Keys {
+    fn from(keys: Vec<Key>) -> Self {
+        Self(keys)
+    }
+}
+
+impl fmt::Display for Keys {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for key in &self.0 {
+            fmt::Display::fmt(key, f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Formatting with `{:#}` prefers Vim-style named keys, such as `<lt>` and
+/// `<Bar>`, over escaped and literal characters
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let options = FormatOptions {
+            named_symbols: f.alternate(),
+            ..Default::default()
+        };
+        self.write_with(f, &options)
+    }
+}
+
+impl KeyName {
+    /// Whether the name can be written literally, rather than in a group as
+    /// a named key or `Char-0x..`
+    fn is_printable(self) -> bool {
+        match self {
+            KeyName::Space | KeyName::Unknown(_) => false,
+            KeyName::Char(ch) => !ch.is_control() && !ch.is_whitespace(),
+            _ => self.lower_str().is_some(),
+        }
+    }
+}
+
+/// Writes the name as it appears inside a key, without any modifier group
+impl fmt::Display for KeyName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self, self.lower_str()) {
+            (_, Some(lower)) => f.write_str(lower),
+            (KeyName::Char(ch), _) if self.is_printable() => write!(f, "{}", ch),
+            (KeyName::Char(ch), _) => write!(f, "Char-{:#x}", *ch as u32),
+            (KeyName::Unknown(code), _) => write!(f, "Unknown:{}", code),
+            _ => f.write_str(self.named_str().expect("every key name has a notation")),
+        }
+    }
+}
+
+/// Write a character as key notation, escaping it if needed
+///
+/// A literal backslash key is written `\\`.
+pub fn escape_notation(ch: char) -> Cow<'static, str> {
+    match ch {
+        '\\' => Cow::Borrowed("\\\\"),
+        '-' => Cow::Borrowed("\\-"),
+        '<' => Cow::Borrowed("\\<"),
+        '>' => Cow::Borrowed("\\>"),
+        _ => Cow::Owned(ch.to_string()),
+    }
+}
+
+/// Remove the escaping backslashes from key notation
+///
+/// Returns the input unchanged if nothing is escaped.
+pub fn unescape(input: &str) -> Result<Cow<'_, str>, Error> {
+    if !input.contains('\\') {
+        return Ok(Cow::Borrowed(input));
+    }
+    let mut output = String::with_capacity(input.len());
+    let mut chars = input.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '\\' {
+            output.push(chars.next().ok_or(Error::TrailingEscape)?);
+        } else {
+            output.push(ch);
+        }
+    }
+    Ok(Cow::Owned(output))
+}
+
 #[derive(Clone, Debug, thiserror::Error, PartialEq)]
 pub enum Error {
     #[error("Missing key name")]
     NoKeyName,
-    #[error("Invalid key name `{0}`")]
+    #[error("Invalid key name `{0}`{}", did_you_mean(self))]
     InvalidKeyName(String),
-    #[error("Invalid key modifier `{0}`")]
+    #[error("Invalid key modifier `{0}`{}", did_you_mean(self))]
     InvalidKeyModifier(String),
     #[error("Unexpected open of modifier group (`<`)")]
     UnexpectedGroupOpen,
@@ -148,32 +480,377 @@ pub enum Error {
     UnexpectedGroupClose,
     #[error("Unclosed modifier group (missing `>` at end)")]
     UnexpectedEnd,
-    #[error("Modifier group must be include modifer and key name, not `{0}`")]
+    #[error(
+        "Modifier group must be include modifer and key name, not `{0}`{}",
+        did_you_mean(self)
+    )]
     IncompleteGroup(String),
+    #[error("Invalid character code `{0}`")]
+    InvalidCharCode(String),
+    #[error("Nothing to escape after `\\` at end of input")]
+    TrailingEscape,
+    #[error("Input is longer than the parser limits allow")]
+    LimitExceeded,
+}
+
+impl Error {
+    /// Closest valid spelling for an invalid key name or modifier, such as
+    /// `Control` for `Contrl`
+    pub fn suggestion(&self) -> Option<&'static str> {
+        match self {
+            Error::InvalidKeyName(name) | Error::IncompleteGroup(name) => suggest::key_name(name),
+            Error::InvalidKeyModifier(modifier) => suggest::modifier(modifier),
+            _ => None,
+        }
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Error::NoKeyName => ErrorKind::NoKeyName,
+            Error::InvalidKeyName(_) => ErrorKind::InvalidKeyName,
+            Error::InvalidKeyModifier(_) => ErrorKind::InvalidKeyModifier,
+            Error::UnexpectedGroupOpen => ErrorKind::UnexpectedGroupOpen,
+            Error::UnexpectedGroupClose => ErrorKind::UnexpectedGroupClose,
+            Error::UnexpectedEnd => ErrorKind::UnexpectedEnd,
+            Error::IncompleteGroup(_) => ErrorKind::IncompleteGroup,
+            Error::InvalidCharCode(_) => ErrorKind::InvalidCharCode,
+            Error::TrailingEscape => ErrorKind::TrailingEscape,
+            Error::LimitExceeded => ErrorKind::LimitExceeded,
+        }
+    }
+}
+
+fn did_you_mean(error: &Error) -> String {
+    match error.suggestion() {
+        Some(suggestion) => format!(" (did you mean `{}`?)", suggestion),
+        None => String::new(),
+    }
+}
+
+/// Parse error, with the byte range of `input` which caused it
+#[derive(Clone, Debug, thiserror::Error, PartialEq)]
+#[error("{error} (at {}..{})", span.start, span.end)]
+pub struct SpannedError {
+    pub span: Range<usize>,
+    pub error: Error,
+}
+
+impl SpannedError {
+    pub fn new(error: Error, span: Range<usize>) -> Self {
+        Self { span, error }
+    }
+
+    /// Position of the error in a larger source, where `input` is the
+    /// parsed input and starts at `base`
+    pub fn locate(&self, input: &str, base: Position) -> LocatedError {
+        let start = base.advance(&input[..self.span.start]);
+        let end = start.advance(&input[self.span.clone()]);
+        LocatedError {
+            start,
+            end,
+            error: self.error.clone(),
+        }
+    }
+}
+
+/// Position in a larger source, such as a config file
+///
+/// Lines and columns start at 1, and columns count characters
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Position {
+    /// Byte offset in the source
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Default for Position {
+    fn default() -> Self {
+        Self {
+            offset: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+}
+
+impl Position {
+    /// Position after `text`, which starts at this position
+    fn advance(mut self, text: &str) -> Self {
+        self.offset += text.len();
+        for ch in text.chars() {
+            if ch == '\n' {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column += 1;
+            }
+        }
+        self
+    }
+}
+
+/// Parse error, with its position in a larger source
+#[derive(Clone, Debug, thiserror::Error, PartialEq)]
+#[error("{error} (at {}:{})", start.line, start.column)]
+pub struct LocatedError {
+    pub start: Position,
+    pub end: Position,
+    pub error: Error,
+}
+
+/// Kind of an [`Error`], without any of the input
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ErrorKind {
+    NoKeyName,
+    InvalidKeyName,
+    InvalidKeyModifier,
+    UnexpectedGroupOpen,
+    UnexpectedGroupClose,
+    UnexpectedEnd,
+    IncompleteGroup,
+    InvalidCharCode,
+    TrailingEscape,
+    LimitExceeded,
+}
+
+/// Parse error which borrows the part of the input which caused it, rather
+/// than allocating
+#[derive(Clone, Copy, Debug, Eq, PartialEq, thiserror::Error)]
+#[error("{}", self.to_error())]
+pub struct BorrowedError<'a> {
+    pub kind: ErrorKind,
+    /// Part of the input which caused the error
+    pub part: &'a str,
+}
+
+impl<'a> BorrowedError<'a> {
+    pub fn new(kind: ErrorKind, part: &'a str) -> Self {
+        Self { kind, part }
+    }
+
+    pub fn to_error(&self) -> Error {
+        let part = self.part.to_string();
+        match self.kind {
+            ErrorKind::NoKeyName => Error::NoKeyName,
+            ErrorKind::InvalidKeyName => Error::InvalidKeyName(part),
+            ErrorKind::InvalidKeyModifier => Error::InvalidKeyModifier(part),
+            ErrorKind::UnexpectedGroupOpen => Error::UnexpectedGroupOpen,
+            ErrorKind::UnexpectedGroupClose => Error::UnexpectedGroupClose,
+            ErrorKind::UnexpectedEnd => Error::UnexpectedEnd,
+            ErrorKind::IncompleteGroup => Error::IncompleteGroup(part),
+            ErrorKind::InvalidCharCode => Error::InvalidCharCode(part),
+            ErrorKind::TrailingEscape => Error::TrailingEscape,
+            ErrorKind::LimitExceeded => Error::LimitExceeded,
+        }
+    }
+
+    /// `part` must be a slice of `input`
+    fn to_spanned(self, input: &str) -> SpannedError {
+        SpannedError::new(self.to_error(), span_of(input, self.part))
+    }
+}
+
+impl From<BorrowedError<'_>> for Error {
+    fn from(error: BorrowedError<'_>) -> Self {
+        error.to_error()
+    }
+}
+
+/// Error converting between a backend key event and a [`Key`]
+#[derive(Clone, Debug, thiserror::Error, PartialEq)]
+pub enum ConversionError {
+    #[error("Key event has no keycode")]
+    NoKeycode,
+    #[error("Key `{0}` is not supported by the backend")]
+    UnsupportedKey(String),
+    #[error("Primary modifier must be resolved for a platform first, with `resolve_platform`")]
+    UnresolvedPrimary,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParserOptions {
+    /// Ignore whitespace between keys, so sequences can be written as
+    /// `<C-x> <C-s>`. A space key must then be written as `<Space>`
+    pub whitespace_separated: bool,
+    /// Accept full modifier names, such as `<Ctrl-a>` and `<Shift-a>`
+    pub long_modifiers: bool,
+    /// Accept modifiers in any case, such as `<c-a>` and `<CTRL-a>`, rather
+    /// than only `<C-a>` and `<Ctrl-a>`
+    pub case_insensitive_modifiers: bool,
+    /// Accept characters with no key name, such as `é`, as [`KeyName::Char`]
+    ///
+    /// `<Char-..>` notation is accepted either way
+    pub char_keys: bool,
+    /// Maximum number of keys in a sequence
+    pub max_keys: Option<usize>,
+    /// Maximum length of a group, such as `<C-a>`, in bytes
+    pub max_group_len: Option<usize>,
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        Self {
+            whitespace_separated: false,
+            long_modifiers: true,
+            case_insensitive_modifiers: true,
+            char_keys: true,
+            max_keys: None,
+            max_group_len: None,
+        }
+    }
+}
+
+impl ParserOptions {
+    /// Only accept canonical single-letter modifiers (`<C-a>`), and no
+    /// [`KeyName::Char`] literals
+    pub fn strict() -> Self {
+        Self {
+            whitespace_separated: false,
+            long_modifiers: false,
+            case_insensitive_modifiers: false,
+            char_keys: false,
+            max_keys: None,
+            max_group_len: None,
+        }
+    }
 }
 
 pub fn parse_keys(input: &str) -> Result<Keys, Error> {
+    parse_keys_with(input, &ParserOptions::default())
+}
+
+pub fn parse_keys_with(input: &str, options: &ParserOptions) -> Result<Keys, Error> {
+    parse_keys_spanned_with(input, options).map_err(|error| error.error)
+}
+
+/// Parse keys, returning the error with its position in `input`
+pub fn parse_keys_spanned(input: &str) -> Result<Keys, SpannedError> {
+    parse_keys_spanned_with(input, &ParserOptions::default())
+}
+
+fn parse_keys_spanned_with(input: &str, options: &ParserOptions) -> Result<Keys, SpannedError> {
+    parse_keys_borrowed_with(input, options).map_err(|error| error.to_spanned(input))
+}
+
+/// Parse keys from part of a larger source, where `input` starts at `base`
+pub fn parse_keys_at(input: &str, base: Position) -> Result<Keys, LocatedError> {
+    parse_keys_spanned(input).map_err(|error| error.locate(input, base))
+}
+
+/// Parse keys, returning an error which borrows from `input` rather than
+/// allocating
+pub fn parse_keys_borrowed(input: &str) -> Result<Keys, BorrowedError<'_>> {
+    parse_keys_borrowed_with(input, &ParserOptions::default())
+}
+
+fn parse_keys_borrowed_with<'a>(
+    input: &'a str,
+    options: &ParserOptions,
+) -> Result<Keys, BorrowedError<'a>> {
     let mut keys = Vec::new();
-    for key in split_keys(input)? {
-        keys.push(parse_key(&key)?);
+    for part in split_keys(input, options) {
+        keys.push(parse_key_part(part?, options)?);
     }
     Ok(Keys(keys))
 }
 
+/// Parse keys, skipping any invalid keys and returning every error found, in
+/// order of position
+pub fn parse_keys_lenient(input: &str) -> (Keys, Vec<SpannedError>) {
+    let options = ParserOptions::default();
+    let mut errors = Vec::new();
+    let mut keys = Vec::new();
+    let mut rest = input;
+
+    while let Some(part) = next_key_part(rest, &options) {
+        let (part, next) = match part {
+            Ok(split) => split,
+            Err(error) => {
+                let start = span_of(rest, error.part).start;
+                errors.push(error);
+                match error.kind {
+                    // Discard the unclosed group, and start a new one here
+                    ErrorKind::UnexpectedGroupOpen => rest = &rest[start..],
+                    // Skip the stray delimiter
+                    ErrorKind::UnexpectedGroupClose => rest = &rest[start + 1..],
+                    // Unclosed group or escape at the end
+                    _ => break,
+                }
+                continue;
+            }
+        };
+        rest = next;
+        match parse_key_part(part, &options) {
+            Ok(key) => keys.push(key),
+            Err(error) => errors.push(error),
+        }
+    }
+
+    let errors = errors.iter().map(|error| error.to_spanned(input)).collect();
+    (Keys(keys), errors)
+}
+
+/// Parse keys, returning every error found rather than only the first
+pub fn parse_keys_all_errors(input: &str) -> Result<Keys, Vec<SpannedError>> {
+    let (keys, errors) = parse_keys_lenient(input);
+    if errors.is_empty() {
+        Ok(keys)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Parse keys lazily, one at a time
+///
+/// Keys before an error are still yielded, and nothing is yielded after it.
+pub fn parse_keys_iter(input: &str) -> impl Iterator<Item = Result<Key, Error>> + '_ {
+    let options = ParserOptions::default();
+    let mut parts = split_keys(input, &options);
+    let mut is_done = false;
+    std::iter::from_fn(move || {
+        if is_done {
+            return None;
+        }
+        let result = parts
+            .next()?
+            .and_then(|part| parse_key_part(part, &options));
+        is_done = result.is_err();
+        Some(result.map_err(|error| error.to_error()))
+    })
+}
+
 pub fn parse_key(input: &str) -> Result<Key, Error> {
+    parse_key_with(input, &ParserOptions::default())
+}
+
+pub fn parse_key_with(input: &str, options: &ParserOptions) -> Result<Key, Error> {
+    parse_key_part(input, options).map_err(|error| error.to_error())
+}
+
+/// Byte range of `part` within `input`, which it must be a slice of
+fn span_of(input: &str, part: &str) -> Range<usize> {
+    let start = part.as_ptr() as usize - input.as_ptr() as usize;
+    start..start + part.len()
+}
+
+fn parse_key_part<'a>(input: &'a str, options: &ParserOptions) -> Result<Key, BorrowedError<'a>> {
     if input.starts_with('<') && input.ends_with('>') {
         let mut chars = input.chars();
         chars.next();
         chars.next_back();
-        parse_key_with_modifier(chars.as_str())
+        parse_key_with_modifier(chars.as_str(), options)
     } else {
-        parse_key_no_modifier(input)
+        parse_key_no_modifier(input, options)
     }
 }
 
-fn parse_key_no_modifier(input: &str) -> Result<Key, Error> {
-    let Some((name, shift)) = KeyName::from_str(input) else {
-        return Err(Error::InvalidKeyName(input.to_string()));
+fn parse_key_no_modifier<'a>(
+    input: &'a str,
+    options: &ParserOptions,
+) -> Result<Key, BorrowedError<'a>> {
+    let Some((name, shift)) = parse_name(input, options) else {
+        return Err(BorrowedError::new(ErrorKind::InvalidKeyName, input));
     };
 
     let modifiers = Modifiers {
@@ -184,119 +861,216 @@ fn parse_key_no_modifier(input: &str) -> Result<Key, Error> {
     Ok(Key { modifiers, name })
 }
 
-fn parse_key_with_modifier(input: &str) -> Result<Key, Error> {
-    let modifier_strings = split_modifiers(input)?;
-    if modifier_strings.len() < 2 {
-        return Err(Error::IncompleteGroup(input.to_string()));
+fn parse_key_with_modifier<'a>(
+    input: &'a str,
+    options: &ParserOptions,
+) -> Result<Key, BorrowedError<'a>> {
+    // The last part is the key name, and the part before it is either a
+    // modifier or `Char`, so only the last two parts are kept
+    let mut name = None;
+    let mut before_name = None;
+    let mut modifiers = Modifiers::default();
+    // Reported after any error with the key name
+    let mut invalid_modifier = None;
+
+    for part in split_modifiers(input) {
+        let part = part?;
+        if let Some(modifier) = before_name.take() {
+            if !parse_modifier(modifier, &mut modifiers, options) {
+                invalid_modifier.get_or_insert(modifier);
+            }
+        }
+        before_name = name.replace(part);
+    }
+
+    // Named key without modifiers, such as `<lt>`
+    if let (Some(name), None) = (name, before_name) {
+        if let Some(name) = KeyName::from_name(name) {
+            let modifiers = Modifiers::default();
+            return Ok(Key { modifiers, name });
+        }
     }
-    let mut modifier_strings = modifier_strings.into_iter();
 
-    let Some(name) = modifier_strings.next_back() else {
-        return Err(Error::NoKeyName);
+    let (Some(name), Some(before_name)) = (name, before_name) else {
+        return Err(BorrowedError::new(ErrorKind::IncompleteGroup, input));
     };
 
-    let Some((name, shift)) = KeyName::from_str(name) else {
-        return Err(Error::InvalidKeyName(name.to_string()));
+    // `<Char-65>`, `<C-Char-0x41>`
+    let is_char_code = before_name.eq_ignore_ascii_case("Char");
+    let (name, shift) = if is_char_code {
+        parse_char_code(name)?
+    } else if let Some(parsed) = parse_name(name, options) {
+        parsed
+    } else if let Some(named) = KeyName::from_name(name) {
+        (named, false)
+    } else {
+        return Err(BorrowedError::new(ErrorKind::InvalidKeyName, name));
     };
 
-    let mut control = false;
-    let mut alt = false;
-
-    for modifier in modifier_strings {
-        match modifier {
-            "C" => control = true,
-            "M" => alt = true,
-            _ => return Err(Error::InvalidKeyModifier(modifier.to_string())),
-        };
+    if !is_char_code && !parse_modifier(before_name, &mut modifiers, options) {
+        invalid_modifier.get_or_insert(before_name);
     }
-
-    let modifiers = Modifiers {
-        shift,
-        control,
-        alt,
-    };
+    if let Some(modifier) = invalid_modifier {
+        return Err(BorrowedError::new(ErrorKind::InvalidKeyModifier, modifier));
+    }
+    modifiers.shift |= shift;
 
     Ok(Key { modifiers, name })
 }
 
-fn split_modifiers(input: &str) -> Result<Vec<&str>, Error> {
-    let mut keys: Vec<&str> = Vec::new();
-    let mut start = 0;
-    let mut is_escaped = false;
+fn parse_name(input: &str, options: &ParserOptions) -> Option<(KeyName, bool)> {
+    KeyName::from_str(input)
+        .filter(|(name, _)| options.char_keys || !matches!(name, KeyName::Char(_)))
+}
 
-    for (i, ch) in input.char_indices() {
-        if is_escaped {
-            is_escaped = false;
-            continue;
-        }
-        if ch == '\\' {
-            is_escaped = true;
-        } else if ch == '-' {
-            if start != i {
-                keys.push(&input[start..i]);
-            }
-            start = i + 1;
-        }
-    }
+/// Adds the modifier to `modifiers`, returning `false` if it is not valid
+fn parse_modifier(input: &str, modifiers: &mut Modifiers, options: &ParserOptions) -> bool {
+    let (modifier, is_long) = match input.to_ascii_lowercase().as_str() {
+        "c" => (&mut modifiers.control, false),
+        "m" => (&mut modifiers.alt, false),
+        "s" => (&mut modifiers.shift, false),
+        "d" => (&mut modifiers.super_key, false),
+        "p" => (&mut modifiers.primary, false),
+        "ctrl" | "control" => (&mut modifiers.control, true),
+        "alt" => (&mut modifiers.alt, true),
+        "shift" => (&mut modifiers.shift, true),
+        "cmd" | "super" => (&mut modifiers.super_key, true),
+        "primary" => (&mut modifiers.primary, true),
+        _ => return false,
+    };
 
-    if start < input.len() {
-        if is_escaped {
-            panic!("cannot escape end of group");
-        }
-        keys.push(&input[start..]);
+    if is_long && !options.long_modifiers {
+        return false;
+    }
+    // Canonical case is `C` and `Ctrl`
+    let mut chars = input.chars();
+    let is_capitalized =
+        chars.next().is_some_and(|ch| ch.is_uppercase()) && chars.all(|ch| ch.is_lowercase());
+    if !is_capitalized && !options.case_insensitive_modifiers {
+        return false;
     }
 
-    Ok(keys)
+    *modifier = true;
+    true
 }
 
-fn split_keys(input: &str) -> Result<Vec<&str>, Error> {
-    let mut keys: Vec<&str> = Vec::new();
+fn parse_char_code(input: &str) -> Result<(KeyName, bool), BorrowedError<'_>> {
+    let code = match input.strip_prefix("0x").or(input.strip_prefix("0X")) {
+        Some(hex) => u32::from_str_radix(hex, 16),
+        None => input.parse(),
+    };
+    let Some(ch) = code.ok().and_then(char::from_u32) else {
+        return Err(BorrowedError::new(ErrorKind::InvalidCharCode, input));
+    };
+    Ok(KeyName::from_char(ch))
+}
+
+/// Splits the inside of a group on unescaped dashes, skipping empty parts
+fn split_modifiers(input: &str) -> impl Iterator<Item = Result<&str, BorrowedError<'_>>> {
+    let mut chars = input.char_indices();
     let mut start = 0;
-    let mut is_escaped = false;
-    let mut is_group = false;
+    std::iter::from_fn(move || {
+        while let Some((i, ch)) = chars.next() {
+            match ch {
+                '\\' if chars.next().is_none() => {
+                    start = input.len();
+                    let part = &input[i..];
+                    return Some(Err(BorrowedError::new(ErrorKind::TrailingEscape, part)));
+                }
+                '-' => {
+                    let part = &input[start..i];
+                    start = i + 1;
+                    if !part.is_empty() {
+                        return Some(Ok(part));
+                    }
+                }
+                _ => (),
+            }
+        }
+        let part = &input[start..];
+        start = input.len();
+        (!part.is_empty()).then_some(Ok(part))
+    })
+}
 
-    for (mut i, ch) in input.char_indices() {
-        if is_escaped {
-            is_escaped = false;
-            continue;
+/// Splits keys, stopping after the first error
+fn split_keys<'a>(
+    input: &'a str,
+    options: &ParserOptions,
+) -> impl Iterator<Item = Result<&'a str, BorrowedError<'a>>> {
+    let options = options.clone();
+    let mut rest = Some(input);
+    let mut count = 0;
+    std::iter::from_fn(move || match next_key_part(rest?, &options)? {
+        Ok(_) if options.max_keys.is_some_and(|max| count >= max) => {
+            let part = rest.take()?;
+            Some(Err(BorrowedError::new(ErrorKind::LimitExceeded, part)))
         }
-        if ch == '\\' {
-            is_escaped = true;
+        Ok((part, next)) => {
+            rest = Some(next);
+            count += 1;
+            Some(Ok(part))
         }
-        match (is_group, ch) {
-            // Mismatched group delimeters
-            (true, '<') => return Err(Error::UnexpectedGroupOpen),
-            (false, '>') => return Err(Error::UnexpectedGroupClose),
-
-            // Open group
-            (_, '<') => is_group = true,
-            // Close group
-            (_, '>') => {
-                is_group = false;
-                i += 1;
-            }
-
-            // Any character inside group - do not push key yet
-            (true, _) => continue,
-
-            _ => (),
+        Err(error) => {
+            rest = None;
+            Some(Err(error))
         }
+    })
+}
 
-        // Push key based on index
-        if start != i {
-            keys.push(&input[start..i]);
-            start = i;
-        }
-    }
+/// First key in `input`, and the input after it
+fn next_key_part<'a>(
+    input: &'a str,
+    options: &ParserOptions,
+) -> Option<Result<(&'a str, &'a str), BorrowedError<'a>>> {
+    let input = if options.whitespace_separated {
+        input.trim_start()
+    } else {
+        input
+    };
+    let mut chars = input.char_indices();
+    let (_, first) = chars.next()?;
 
-    // Push last key
-    if start < input.len() {
-        // Missing closing delimeter
-        if is_group {
-            return Err(Error::UnexpectedEnd);
+    let end = match first {
+        '<' => loop {
+            match chars.next() {
+                Some((i, ch))
+                    if options
+                        .max_group_len
+                        .is_some_and(|max| i + ch.len_utf8() > max) =>
+                {
+                    let error = BorrowedError::new(ErrorKind::LimitExceeded, &input[..i]);
+                    return Some(Err(error));
+                }
+                Some((_, '\\')) => {
+                    chars.next();
+                }
+                Some((i, '<')) => {
+                    let part = &input[i..i + 1];
+                    let error = BorrowedError::new(ErrorKind::UnexpectedGroupOpen, part);
+                    return Some(Err(error));
+                }
+                Some((i, '>')) => break i + 1,
+                Some(_) => (),
+                None => {
+                    let error = BorrowedError::new(ErrorKind::UnexpectedEnd, input);
+                    return Some(Err(error));
+                }
+            }
+        },
+        '>' => {
+            let error = BorrowedError::new(ErrorKind::UnexpectedGroupClose, &input[..1]);
+            return Some(Err(error));
         }
-        keys.push(&input[start..]);
-    }
+        '\\' => match chars.next() {
+            Some((i, ch)) => i + ch.len_utf8(),
+            None => {
+                let error = BorrowedError::new(ErrorKind::TrailingEscape, input);
+                return Some(Err(error));
+            }
+        },
+        _ => first.len_utf8(),
+    };
 
-    Ok(keys)
+    Some(Ok(input.split_at(end)))
 }
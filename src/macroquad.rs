@@ -0,0 +1,193 @@
+//! Conversion of [`macroquad`] key codes, and keys pressed this frame with
+//! the modifiers which are held
+
+use crate::{ConversionError, Key, KeyName, Modifiers};
+use macroquad::input::{get_keys_pressed, is_key_down, KeyCode};
+use macroquad::miniquad::KeyMods;
+
+/// Physical keys of a US keyboard
+const CODES: &[(KeyName, KeyCode)] = &[
+    (KeyName::A, KeyCode::A),
+    (KeyName::B, KeyCode::B),
+    (KeyName::C, KeyCode::C),
+    (KeyName::D, KeyCode::D),
+    (KeyName::E, KeyCode::E),
+    (KeyName::F, KeyCode::F),
+    (KeyName::G, KeyCode::G),
+    (KeyName::H, KeyCode::H),
+    (KeyName::I, KeyCode::I),
+    (KeyName::J, KeyCode::J),
+    (KeyName::K, KeyCode::K),
+    (KeyName::L, KeyCode::L),
+    (KeyName::M, KeyCode::M),
+    (KeyName::N, KeyCode::N),
+    (KeyName::O, KeyCode::O),
+    (KeyName::P, KeyCode::P),
+    (KeyName::Q, KeyCode::Q),
+    (KeyName::R, KeyCode::R),
+    (KeyName::S, KeyCode::S),
+    (KeyName::T, KeyCode::T),
+    (KeyName::U, KeyCode::U),
+    (KeyName::V, KeyCode::V),
+    (KeyName::W, KeyCode::W),
+    (KeyName::X, KeyCode::X),
+    (KeyName::Y, KeyCode::Y),
+    (KeyName::Z, KeyCode::Z),
+    (KeyName::Number0, KeyCode::Key0),
+    (KeyName::Number1, KeyCode::Key1),
+    (KeyName::Number2, KeyCode::Key2),
+    (KeyName::Number3, KeyCode::Key3),
+    (KeyName::Number4, KeyCode::Key4),
+    (KeyName::Number5, KeyCode::Key5),
+    (KeyName::Number6, KeyCode::Key6),
+    (KeyName::Number7, KeyCode::Key7),
+    (KeyName::Number8, KeyCode::Key8),
+    (KeyName::Number9, KeyCode::Key9),
+    (KeyName::Dash, KeyCode::Minus),
+    (KeyName::Equals, KeyCode::Equal),
+    (KeyName::BracketLeft, KeyCode::LeftBracket),
+    (KeyName::BracketRight, KeyCode::RightBracket),
+    (KeyName::Backslash, KeyCode::Backslash),
+    (KeyName::Semicolon, KeyCode::Semicolon),
+    (KeyName::SingleQuote, KeyCode::Apostrophe),
+    (KeyName::Comma, KeyCode::Comma),
+    (KeyName::Period, KeyCode::Period),
+    (KeyName::ForwardSlash, KeyCode::Slash),
+    (KeyName::Backtick, KeyCode::GraveAccent),
+    (KeyName::Space, KeyCode::Space),
+    (KeyName::Enter, KeyCode::Enter),
+    (KeyName::Tab, KeyCode::Tab),
+    (KeyName::Escape, KeyCode::Escape),
+    (KeyName::Backspace, KeyCode::Backspace),
+    (KeyName::Delete, KeyCode::Delete),
+    (KeyName::Insert, KeyCode::Insert),
+    (KeyName::Home, KeyCode::Home),
+    (KeyName::End, KeyCode::End),
+    (KeyName::PageUp, KeyCode::PageUp),
+    (KeyName::PageDown, KeyCode::PageDown),
+    (KeyName::Up, KeyCode::Up),
+    (KeyName::Down, KeyCode::Down),
+    (KeyName::Left, KeyCode::Left),
+    (KeyName::Right, KeyCode::Right),
+    (KeyName::F1, KeyCode::F1),
+    (KeyName::F2, KeyCode::F2),
+    (KeyName::F3, KeyCode::F3),
+    (KeyName::F4, KeyCode::F4),
+    (KeyName::F5, KeyCode::F5),
+    (KeyName::F6, KeyCode::F6),
+    (KeyName::F7, KeyCode::F7),
+    (KeyName::F8, KeyCode::F8),
+    (KeyName::F9, KeyCode::F9),
+    (KeyName::F10, KeyCode::F10),
+    (KeyName::F11, KeyCode::F11),
+    (KeyName::F12, KeyCode::F12),
+    (KeyName::CapsLock, KeyCode::CapsLock),
+    (KeyName::LeftShift, KeyCode::LeftShift),
+    (KeyName::RightShift, KeyCode::RightShift),
+    (KeyName::LeftControl, KeyCode::LeftControl),
+    (KeyName::RightControl, KeyCode::RightControl),
+    (KeyName::LeftAlt, KeyCode::LeftAlt),
+    (KeyName::RightAlt, KeyCode::RightAlt),
+    (KeyName::LeftSuper, KeyCode::LeftSuper),
+    (KeyName::RightSuper, KeyCode::RightSuper),
+];
+
+/// Modifier keys, which change the modifiers of other keys
+const MODIFIER_KEYS: &[KeyCode] = &[
+    KeyCode::LeftShift,
+    KeyCode::RightShift,
+    KeyCode::LeftControl,
+    KeyCode::RightControl,
+    KeyCode::LeftAlt,
+    KeyCode::RightAlt,
+    KeyCode::LeftSuper,
+    KeyCode::RightSuper,
+];
+
+/// Modifiers of the modifier keys which are held, as given by
+/// [`is_key_down`]
+pub fn modifiers() -> Modifiers {
+    let held = |left, right| is_key_down(left) || is_key_down(right);
+    Modifiers {
+        shift: held(KeyCode::LeftShift, KeyCode::RightShift),
+        control: held(KeyCode::LeftControl, KeyCode::RightControl),
+        alt: held(KeyCode::LeftAlt, KeyCode::RightAlt),
+        super_key: held(KeyCode::LeftSuper, KeyCode::RightSuper),
+        primary: false,
+    }
+}
+
+/// Keys which were pressed this frame with the modifiers which are held,
+/// other than modifier keys, in no particular order
+///
+/// Keys are physical keys, so shift and `1` is `<S-1>`.
+pub fn key_presses() -> impl Iterator<Item = Key> {
+    let modifiers = modifiers();
+    get_keys_pressed()
+        .into_iter()
+        .filter(|code| !MODIFIER_KEYS.contains(code))
+        .filter_map(move |code| {
+            let name = KeyName::try_from(code).ok()?;
+            Some(Key { modifiers, name })
+        })
+}
+
+/// Key of a key code with the modifiers of a miniquad key event
+impl TryFrom<(KeyCode, KeyMods)> for Key {
+    type Error = ConversionError;
+    fn try_from((code, keymods): (KeyCode, KeyMods)) -> Result<Self, Self::Error> {
+        Ok(Key {
+            modifiers: keymods.into(),
+            name: code.try_into()?,
+        })
+    }
+}
+
+impl TryFrom<KeyCode> for KeyName {
+    type Error = ConversionError;
+    fn try_from(code: KeyCode) -> Result<Self, Self::Error> {
+        CODES
+            .iter()
+            .find(|(_, known)| *known == code)
+            .map(|(name, _)| *name)
+            .ok_or_else(|| ConversionError::UnsupportedKey(format!("{:?}", code)))
+    }
+}
+
+impl TryFrom<KeyName> for KeyCode {
+    type Error = ConversionError;
+    fn try_from(name: KeyName) -> Result<Self, Self::Error> {
+        CODES
+            .iter()
+            .find(|(known, _)| *known == name)
+            .map(|(_, code)| *code)
+            .ok_or_else(|| ConversionError::UnsupportedKey(name.to_string()))
+    }
+}
+
+impl From<KeyMods> for Modifiers {
+    fn from(keymods: KeyMods) -> Self {
+        Modifiers {
+            shift: keymods.shift,
+            control: keymods.ctrl,
+            alt: keymods.alt,
+            super_key: keymods.logo,
+            primary: false,
+        }
+    }
+}
+
+/// [`Modifiers::primary`] must be resolved first with
+/// [`Modifiers::resolve_platform`]
+impl TryFrom<Modifiers> for KeyMods {
+    type Error = ConversionError;
+    fn try_from(modifiers: Modifiers) -> Result<Self, Self::Error> {
+        let modifiers = modifiers.require_resolved()?;
+        Ok(KeyMods {
+            shift: modifiers.shift,
+            ctrl: modifiers.control,
+            alt: modifiers.alt,
+            logo: modifiers.super_key,
+        })
+    }
+}
@@ -0,0 +1,101 @@
+use crate::{Key, KeyName, Keys};
+
+/// How shifted digits and symbols are normalized, such as `<S-1>` and `!`
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum ShiftedSymbols {
+    /// `<S-1>` and `!` are different keys
+    #[default]
+    Keep,
+    /// `<S-1>` becomes `!`, as on a US keyboard
+    ToSymbol,
+    /// `!` becomes `<S-1>`, as on a US keyboard
+    ToShifted,
+}
+
+/// Unshifted and shifted keys on a US keyboard
+pub(crate) const US_SHIFTED: &[(KeyName, KeyName)] = &[
+    (KeyName::Number1, KeyName::Bang),
+    (KeyName::Number2, KeyName::At),
+    (KeyName::Number3, KeyName::Pound),
+    (KeyName::Number4, KeyName::Dollar),
+    (KeyName::Number5, KeyName::Percent),
+    (KeyName::Number6, KeyName::Carrot),
+    (KeyName::Number7, KeyName::Ampersand),
+    (KeyName::Number8, KeyName::Star),
+    (KeyName::Number9, KeyName::ParenLeft),
+    (KeyName::Number0, KeyName::ParenRight),
+    (KeyName::Dash, KeyName::Underscore),
+    (KeyName::Equals, KeyName::Plus),
+    (KeyName::BracketLeft, KeyName::BraceLeft),
+    (KeyName::BracketRight, KeyName::BraceRight),
+    (KeyName::Backslash, KeyName::Pipe),
+    (KeyName::Semicolon, KeyName::Colon),
+    (KeyName::SingleQuote, KeyName::DoubleQuote),
+    (KeyName::Comma, KeyName::LessThan),
+    (KeyName::Period, KeyName::GreaterThan),
+    (KeyName::ForwardSlash, KeyName::Question),
+    (KeyName::Backtick, KeyName::Tilde),
+];
+
+impl Key {
+    /// Rewrite the key in one canonical form, keeping shifted symbols
+    ///
+    /// Modifiers have no order, and `<S-a>` and `A` are already the same key,
+    /// so this only replaces [`KeyName::Char`] with a named key where one
+    /// exists.
+    pub fn normalize(self) -> Self {
+        self.normalize_with(ShiftedSymbols::default())
+    }
+
+    pub fn normalize_with(mut self, shifted: ShiftedSymbols) -> Self {
+        if let KeyName::Char(ch) = self.name {
+            let (name, shift) = KeyName::from_char(ch);
+            self.name = name;
+            self.modifiers.shift |= shift;
+        }
+
+        match shifted {
+            ShiftedSymbols::Keep => (),
+            ShiftedSymbols::ToSymbol if self.modifiers.shift => {
+                if let Some((_, symbol)) = US_SHIFTED.iter().find(|(key, _)| *key == self.name) {
+                    self.name = *symbol;
+                    self.modifiers.shift = false;
+                }
+            }
+            ShiftedSymbols::ToSymbol => (),
+            ShiftedSymbols::ToShifted => {
+                if let Some((key, _)) = US_SHIFTED.iter().find(|(_, symbol)| *symbol == self.name) {
+                    self.name = *key;
+                    self.modifiers.shift = true;
+                }
+            }
+        }
+        self
+    }
+
+    /// Whether the keys are the same once normalized
+    pub fn eq_normalized(self, other: Self) -> bool {
+        self.normalize() == other.normalize()
+    }
+}
+
+impl Keys {
+    /// Rewrite every key in one canonical form, as with [`Key::normalize`]
+    pub fn normalize(&self) -> Self {
+        self.normalize_with(ShiftedSymbols::default())
+    }
+
+    pub fn normalize_with(&self, shifted: ShiftedSymbols) -> Self {
+        Keys(
+            self.0
+                .iter()
+                .map(|key| key.normalize_with(shifted))
+                .collect(),
+        )
+    }
+
+    /// Whether the keys are the same once normalized
+    pub fn eq_normalized(&self, other: &Self) -> bool {
+        self.normalize() == other.normalize()
+    }
+}
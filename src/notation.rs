@@ -0,0 +1,78 @@
+//! Key notation dialects
+//!
+//! A [`Notation`] parses and formats [`Keys`] in one dialect, so keys can be
+//! converted between dialects with [`Keys::parse_with`] and [`Keys::format`].
+
+mod autohotkey;
+mod emacs;
+mod gui;
+mod i3;
+mod kanata;
+mod qmk;
+mod readline;
+mod tmux;
+mod vscode;
+
+pub use autohotkey::AutoHotkey;
+pub use emacs::Emacs;
+pub use gui::Gui;
+pub use i3::I3;
+pub use kanata::Kanata;
+pub use qmk::Qmk;
+pub use readline::Readline;
+pub use tmux::Tmux;
+pub use vscode::VsCode;
+
+use crate::{parse_keys, unescape, ConversionError, Error, KeyName, Keys};
+
+pub trait Notation {
+    fn parse(&self, input: &str) -> Result<Keys, Error>;
+    /// Format keys, or fail if the notation cannot write one of them
+    fn format(&self, keys: &Keys) -> Result<String, ConversionError>;
+}
+
+impl<N: Notation + ?Sized> Notation for &N {
+    fn parse(&self, input: &str) -> Result<Keys, Error> {
+        (**self).parse(input)
+    }
+    fn format(&self, keys: &Keys) -> Result<String, ConversionError> {
+        (**self).format(keys)
+    }
+}
+
+/// Character of a key which is written literally, such as `a` or `-`
+pub(crate) fn literal_char(name: KeyName) -> Option<char> {
+    match name {
+        KeyName::Char(ch) => Some(ch),
+        _ => unescape(name.lower_str()?).ok()?.chars().next(),
+    }
+}
+
+/// Function key or unknown key in any case, such as `f5` or `Unknown:42`
+fn function_or_unknown_key(name: &str) -> Option<KeyName> {
+    match KeyName::from_name(name)? {
+        key @ KeyName::Unknown(_) => Some(key),
+        key if name.starts_with(['F', 'f']) && name[1..].parse::<u8>().is_ok() => Some(key),
+        _ => None,
+    }
+}
+
+/// Name of a function key, such as `F5`
+fn function_key_str(name: KeyName) -> Option<&'static str> {
+    name.named_str()
+        .filter(|named| function_or_unknown_key(named).is_some())
+}
+
+/// Vim-style notation, such as `<C-x><C-s>`, as used by [`parse_keys`] and
+/// `Display`
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Vim;
+
+impl Notation for Vim {
+    fn parse(&self, input: &str) -> Result<Keys, Error> {
+        parse_keys(input)
+    }
+    fn format(&self, keys: &Keys) -> Result<String, ConversionError> {
+        Ok(keys.to_string())
+    }
+}
@@ -0,0 +1,131 @@
+use super::{function_or_unknown_key, literal_char, Notation};
+use crate::{ConversionError, Error, Key, KeyName, Keys, Modifiers};
+
+/// AutoHotkey hotkey notation, such as `^+a`, `!F4` and `#d`
+///
+/// Keys in a sequence are separated by whitespace. Hotkey options such as
+/// `~` and `$`, and left and right modifiers such as `<^`, are accepted and
+/// ignored. Letters are the same key in either case, so shift must be written
+/// as `+`
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct AutoHotkey;
+
+/// Keys written as a name, such as `Enter`, where the first name is used for
+/// formatting
+const NAMES: &[(KeyName, &str)] = &[
+    (KeyName::Space, "Space"),
+    (KeyName::Enter, "Enter"),
+    (KeyName::Tab, "Tab"),
+    (KeyName::Escape, "Escape"),
+    (KeyName::Escape, "Esc"),
+    (KeyName::Backspace, "Backspace"),
+    (KeyName::Backspace, "BS"),
+    (KeyName::Delete, "Delete"),
+    (KeyName::Delete, "Del"),
+    (KeyName::Insert, "Insert"),
+    (KeyName::Insert, "Ins"),
+    (KeyName::Home, "Home"),
+    (KeyName::End, "End"),
+    (KeyName::PageUp, "PgUp"),
+    (KeyName::PageDown, "PgDn"),
+    (KeyName::Up, "Up"),
+    (KeyName::Down, "Down"),
+    (KeyName::Left, "Left"),
+    (KeyName::Right, "Right"),
+    (KeyName::CapsLock, "CapsLock"),
+    (KeyName::LeftShift, "LShift"),
+    (KeyName::RightShift, "RShift"),
+    (KeyName::LeftControl, "LCtrl"),
+    (KeyName::LeftControl, "LControl"),
+    (KeyName::RightControl, "RCtrl"),
+    (KeyName::RightControl, "RControl"),
+    (KeyName::LeftAlt, "LAlt"),
+    (KeyName::RightAlt, "RAlt"),
+    (KeyName::LeftSuper, "LWin"),
+    (KeyName::RightSuper, "RWin"),
+];
+
+impl Notation for AutoHotkey {
+    fn parse(&self, input: &str) -> Result<Keys, Error> {
+        let keys: Result<_, _> = input.split_whitespace().map(parse_key).collect();
+        Ok(Keys(keys?))
+    }
+
+    fn format(&self, keys: &Keys) -> Result<String, ConversionError> {
+        let keys: Result<Vec<_>, _> = keys.0.iter().map(|key| format_key(*key)).collect();
+        Ok(keys?.join(" "))
+    }
+}
+
+fn parse_key(input: &str) -> Result<Key, Error> {
+    let mut modifiers = Modifiers::default();
+    let mut rest = input;
+
+    // The last character is always the key, as in `^!`
+    while rest.chars().nth(1).is_some() {
+        let mut chars = rest.chars();
+        match chars.next() {
+            Some('^') => modifiers.control = true,
+            Some('!') => modifiers.alt = true,
+            Some('+') => modifiers.shift = true,
+            Some('#') => modifiers.super_key = true,
+            Some('~' | '$' | '*') => (),
+            // Left or right modifier, such as `<^`
+            Some('<' | '>') if chars.as_str().starts_with(['^', '!', '+', '#']) => (),
+            _ => break,
+        }
+        rest = chars.as_str();
+    }
+
+    let mut chars = rest.chars();
+    let name = match (chars.next(), chars.next()) {
+        (Some(ch), None) => {
+            let (name, shift) = KeyName::from_char(ch.to_ascii_lowercase());
+            modifiers.shift |= shift;
+            name
+        }
+        _ => NAMES
+            .iter()
+            .find(|(_, named)| named.eq_ignore_ascii_case(rest))
+            .map(|(key, _)| *key)
+            .or_else(|| function_or_unknown_key(rest))
+            .ok_or_else(|| Error::InvalidKeyName(rest.to_string()))?,
+    };
+
+    Ok(Key { modifiers, name })
+}
+
+fn format_key(key: Key) -> Result<String, ConversionError> {
+    let Modifiers {
+        shift,
+        control,
+        alt,
+        super_key,
+        primary,
+    } = key.modifiers;
+    if primary {
+        return Err(ConversionError::UnsupportedKey(key.to_string()));
+    }
+
+    let mut output = String::new();
+    for (is_held, modifier) in [(control, '^'), (alt, '!'), (shift, '+'), (super_key, '#')] {
+        if is_held {
+            output.push(modifier);
+        }
+    }
+
+    if let Some((_, name)) = NAMES.iter().find(|(name, _)| *name == key.name) {
+        output.push_str(name);
+    } else {
+        match (key.name, literal_char(key.name)) {
+            (_, Some(ch)) => output.push(ch),
+            (KeyName::Unknown(code), _) => output.push_str(&format!("Unknown:{}", code)),
+            // `F4`
+            (name, _) => output.push_str(
+                name.named_str()
+                    .ok_or_else(|| ConversionError::UnsupportedKey(key.to_string()))?,
+            ),
+        }
+    }
+    Ok(output)
+}
@@ -0,0 +1,181 @@
+use super::{literal_char, Notation};
+use crate::{ConversionError, Error, Key, KeyName, Keys, Modifiers};
+
+/// Emacs `kbd` notation, such as `C-x C-s`, `M-<f5>` and `s-a`
+///
+/// Keys are separated by whitespace, and a word without modifiers, such as
+/// `abc`, is a key for each character. `M-` is [`Modifiers::alt`] and `s-`
+/// is [`Modifiers::super_key`]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Emacs;
+
+/// Keys written as a word, such as `RET`
+const WORDS: &[(KeyName, &str)] = &[
+    (KeyName::Enter, "RET"),
+    (KeyName::Space, "SPC"),
+    (KeyName::Tab, "TAB"),
+    (KeyName::Escape, "ESC"),
+    (KeyName::Backspace, "DEL"),
+];
+
+/// Keys written in angle brackets, such as `<f5>`
+const FUNCTION_KEYS: &[(KeyName, &str)] = &[
+    (KeyName::Enter, "return"),
+    (KeyName::Tab, "tab"),
+    (KeyName::Escape, "escape"),
+    (KeyName::Backspace, "backspace"),
+    (KeyName::Delete, "delete"),
+    (KeyName::Insert, "insert"),
+    (KeyName::Home, "home"),
+    (KeyName::End, "end"),
+    (KeyName::PageUp, "prior"),
+    (KeyName::PageDown, "next"),
+    (KeyName::Up, "up"),
+    (KeyName::Down, "down"),
+    (KeyName::Left, "left"),
+    (KeyName::Right, "right"),
+    (KeyName::F1, "f1"),
+    (KeyName::F2, "f2"),
+    (KeyName::F3, "f3"),
+    (KeyName::F4, "f4"),
+    (KeyName::F5, "f5"),
+    (KeyName::F6, "f6"),
+    (KeyName::F7, "f7"),
+    (KeyName::F8, "f8"),
+    (KeyName::F9, "f9"),
+    (KeyName::F10, "f10"),
+    (KeyName::F11, "f11"),
+    (KeyName::F12, "f12"),
+];
+
+impl Notation for Emacs {
+    fn parse(&self, input: &str) -> Result<Keys, Error> {
+        let mut keys = Vec::new();
+        for word in input.split_whitespace() {
+            parse_word(word, &mut keys)?;
+        }
+        Ok(Keys(keys))
+    }
+
+    fn format(&self, keys: &Keys) -> Result<String, ConversionError> {
+        let keys: Result<Vec<_>, _> = keys.0.iter().map(|key| format_key(*key)).collect();
+        Ok(keys?.join(" "))
+    }
+}
+
+fn parse_word(word: &str, keys: &mut Vec<Key>) -> Result<(), Error> {
+    // Modifiers can be inside the brackets, as in `<C-f5>`
+    let (mut rest, is_bracketed) = match word.strip_prefix('<').and_then(|w| w.strip_suffix('>')) {
+        Some(inner) if !inner.is_empty() => (inner, true),
+        _ => (word, false),
+    };
+
+    let mut modifiers = Modifiers::default();
+    loop {
+        let mut chars = rest.chars();
+        let (Some(modifier), Some('-')) = (chars.next(), chars.next()) else {
+            break;
+        };
+        if chars.as_str().is_empty() {
+            break;
+        }
+        match modifier {
+            'C' => modifiers.control = true,
+            'M' => modifiers.alt = true,
+            'S' => modifiers.shift = true,
+            's' => modifiers.super_key = true,
+            // Hyper and Alt, which have no matching modifier
+            'H' | 'A' => return Err(Error::InvalidKeyModifier(modifier.to_string())),
+            _ => break,
+        }
+        rest = chars.as_str();
+    }
+    let has_modifiers = modifiers != Modifiers::default();
+
+    let function_key = match rest.strip_prefix('<').and_then(|w| w.strip_suffix('>')) {
+        Some(name) if !is_bracketed && !name.is_empty() => Some(name),
+        _ if is_bracketed => Some(rest),
+        _ => None,
+    };
+    let name = if let Some(name) = function_key {
+        match FUNCTION_KEYS.iter().find(|(_, function)| *function == name) {
+            Some((key, _)) => *key,
+            None => match KeyName::from_name(name) {
+                Some(unknown @ KeyName::Unknown(_)) => unknown,
+                _ => return Err(Error::InvalidKeyName(name.to_string())),
+            },
+        }
+    } else if let Some((key, _)) = WORDS.iter().find(|(_, word)| *word == rest) {
+        *key
+    } else {
+        let mut chars = rest.chars();
+        match (chars.next(), chars.next()) {
+            (Some(ch), None) => {
+                let (name, shift) = KeyName::from_char(ch);
+                modifiers.shift |= shift;
+                name
+            }
+            // A word of characters, such as `abc`
+            _ if !has_modifiers => {
+                for ch in rest.chars() {
+                    let (name, shift) = KeyName::from_char(ch);
+                    let modifiers = Modifiers {
+                        shift,
+                        ..Default::default()
+                    };
+                    keys.push(Key { modifiers, name });
+                }
+                return Ok(());
+            }
+            _ => return Err(Error::InvalidKeyName(rest.to_string())),
+        }
+    };
+
+    keys.push(Key { modifiers, name });
+    Ok(())
+}
+
+fn format_key(key: Key) -> Result<String, ConversionError> {
+    let Modifiers {
+        shift,
+        control,
+        alt,
+        super_key,
+        primary,
+    } = key.modifiers;
+    if primary {
+        return Err(ConversionError::UnsupportedKey(key.to_string()));
+    }
+
+    // Shift is implied by the uppercase letter, if the key has one
+    let upper = if shift { key.name.upper_str() } else { None };
+    let shift = shift && upper.is_none();
+
+    let mut output = String::new();
+    for (is_held, modifier) in [
+        (control, "C-"),
+        (alt, "M-"),
+        (shift, "S-"),
+        (super_key, "s-"),
+    ] {
+        if is_held {
+            output.push_str(modifier);
+        }
+    }
+
+    if let Some(upper) = upper {
+        output.push_str(upper);
+    } else if let Some((_, word)) = WORDS.iter().find(|(name, _)| *name == key.name) {
+        output.push_str(word);
+    } else if let Some((_, name)) = FUNCTION_KEYS.iter().find(|(name, _)| *name == key.name) {
+        output.push_str(&format!("<{}>", name));
+    } else {
+        match (key.name, literal_char(key.name)) {
+            (_, Some(ch)) => output.push(ch),
+            (KeyName::Unknown(code), _) => output.push_str(&format!("<Unknown:{}>", code)),
+            // Modifier keys, which Emacs does not see as events
+            _ => return Err(ConversionError::UnsupportedKey(key.to_string())),
+        }
+    }
+    Ok(output)
+}
@@ -0,0 +1,157 @@
+use super::{function_key_str, function_or_unknown_key, literal_char, Notation};
+use crate::{ConversionError, Error, Key, KeyName, Keys, Modifiers};
+
+/// GUI accelerator notation, such as `Ctrl+Shift+P`
+///
+/// Keys in a sequence are separated by whitespace. Letters are written in
+/// uppercase, so shift must be written as a modifier. Names and modifiers are
+/// case-insensitive
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Gui {
+    /// Write [`Modifiers::super_key`] as `Cmd` rather than `Super`, as on
+    /// macOS
+    pub is_mac: bool,
+}
+
+// Not derivable on macOS
+#[allow(clippy::derivable_impls)]
+impl Default for Gui {
+    fn default() -> Self {
+        Self {
+            is_mac: cfg!(target_os = "macos"),
+        }
+    }
+}
+
+/// Keys written as a name, such as `Enter`, where the first name is used for
+/// formatting
+const NAMES: &[(KeyName, &str)] = &[
+    (KeyName::Plus, "Plus"),
+    (KeyName::Space, "Space"),
+    (KeyName::Enter, "Enter"),
+    (KeyName::Enter, "Return"),
+    (KeyName::Tab, "Tab"),
+    (KeyName::Escape, "Esc"),
+    (KeyName::Escape, "Escape"),
+    (KeyName::Backspace, "Backspace"),
+    (KeyName::Delete, "Delete"),
+    (KeyName::Delete, "Del"),
+    (KeyName::Insert, "Insert"),
+    (KeyName::Home, "Home"),
+    (KeyName::End, "End"),
+    (KeyName::PageUp, "PageUp"),
+    (KeyName::PageDown, "PageDown"),
+    (KeyName::Up, "Up"),
+    (KeyName::Down, "Down"),
+    (KeyName::Left, "Left"),
+    (KeyName::Right, "Right"),
+];
+
+impl Notation for Gui {
+    fn parse(&self, input: &str) -> Result<Keys, Error> {
+        let keys: Result<_, _> = input
+            .split_whitespace()
+            .map(|key| self.parse_key(key))
+            .collect();
+        Ok(Keys(keys?))
+    }
+
+    fn format(&self, keys: &Keys) -> Result<String, ConversionError> {
+        let keys: Result<Vec<_>, _> = keys.0.iter().map(|key| self.format_key(*key)).collect();
+        Ok(keys?.join(" "))
+    }
+}
+
+impl Gui {
+    fn parse_key(&self, input: &str) -> Result<Key, Error> {
+        // `Ctrl++` for the plus key
+        let (modifier_strings, name) = if input == "+" {
+            ("", "+")
+        } else if let Some(modifier_strings) = input.strip_suffix("++") {
+            (modifier_strings, "+")
+        } else {
+            input.rsplit_once('+').unwrap_or(("", input))
+        };
+        if name.is_empty() {
+            return Err(Error::NoKeyName);
+        }
+
+        let mut modifiers = Modifiers::default();
+        for modifier in modifier_strings.split('+').filter(|part| !part.is_empty()) {
+            let held = match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => &mut modifiers.control,
+                "alt" | "option" => &mut modifiers.alt,
+                "shift" => &mut modifiers.shift,
+                "cmd" | "command" | "super" | "meta" => &mut modifiers.super_key,
+                "cmdorctrl" | "commandorcontrol" => &mut modifiers.primary,
+                _ => return Err(Error::InvalidKeyModifier(modifier.to_string())),
+            };
+            *held = true;
+        }
+
+        let mut chars = name.chars();
+        let name = match (chars.next(), chars.next()) {
+            // Letters are the same key in either case
+            (Some(ch), None) if ch.is_ascii_alphabetic() => {
+                KeyName::from_char(ch.to_ascii_lowercase()).0
+            }
+            (Some(ch), None) => {
+                let (name, shift) = KeyName::from_char(ch);
+                modifiers.shift |= shift;
+                name
+            }
+            _ => parse_name(name).ok_or_else(|| Error::InvalidKeyName(name.to_string()))?,
+        };
+
+        Ok(Key { modifiers, name })
+    }
+
+    fn format_key(&self, key: Key) -> Result<String, ConversionError> {
+        let Modifiers {
+            shift,
+            control,
+            alt,
+            super_key,
+            primary,
+        } = key.modifiers;
+        let super_name = if self.is_mac { "Cmd" } else { "Super" };
+
+        let mut output = String::new();
+        for (is_held, modifier) in [
+            (primary, "CmdOrCtrl"),
+            (control, "Ctrl"),
+            (alt, "Alt"),
+            (shift, "Shift"),
+            (super_key, super_name),
+        ] {
+            if is_held {
+                output.push_str(modifier);
+                output.push('+');
+            }
+        }
+
+        if let Some((_, name)) = NAMES.iter().find(|(name, _)| *name == key.name) {
+            output.push_str(name);
+        } else if let Some(upper) = key.name.upper_str() {
+            output.push_str(upper);
+        } else {
+            match (key.name, literal_char(key.name)) {
+                (_, Some(ch)) => output.push(ch),
+                (KeyName::Unknown(code), _) => output.push_str(&format!("Unknown:{}", code)),
+                (name, _) => match function_key_str(name) {
+                    Some(function) => output.push_str(function),
+                    None => return Err(ConversionError::UnsupportedKey(key.to_string())),
+                },
+            }
+        }
+        Ok(output)
+    }
+}
+
+fn parse_name(name: &str) -> Option<KeyName> {
+    NAMES
+        .iter()
+        .find(|(_, named)| named.eq_ignore_ascii_case(name))
+        .map(|(key, _)| *key)
+        .or_else(|| function_or_unknown_key(name))
+}
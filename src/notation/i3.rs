@@ -0,0 +1,195 @@
+use super::{function_or_unknown_key, literal_char, Notation};
+use crate::{ConversionError, Error, Key, KeyName, Keys, Modifiers};
+
+/// i3 and sway `bindsym` notation, such as `$mod+Shift+Return`
+///
+/// Keys in a sequence are separated by whitespace. Key names are xkb keysyms,
+/// such as `space` and `bracketleft`, and letters are written in lowercase,
+/// so shift must be written as a modifier
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct I3 {
+    /// Modifiers that `$mod` stands for, which is [`Modifiers::super_key`]
+    /// (`Mod4`) by default. With no modifiers, `$mod` is never written
+    pub mod_key: Modifiers,
+}
+
+impl Default for I3 {
+    fn default() -> Self {
+        Self {
+            mod_key: Modifiers {
+                super_key: true,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// Keysyms of keys, where the first keysym is used for formatting
+const KEYSYMS: &[(KeyName, &str)] = &[
+    (KeyName::Space, "space"),
+    (KeyName::Enter, "Return"),
+    (KeyName::Tab, "Tab"),
+    (KeyName::Escape, "Escape"),
+    (KeyName::Backspace, "BackSpace"),
+    (KeyName::Delete, "Delete"),
+    (KeyName::Insert, "Insert"),
+    (KeyName::Home, "Home"),
+    (KeyName::End, "End"),
+    (KeyName::PageUp, "Prior"),
+    (KeyName::PageUp, "Page_Up"),
+    (KeyName::PageDown, "Next"),
+    (KeyName::PageDown, "Page_Down"),
+    (KeyName::Up, "Up"),
+    (KeyName::Down, "Down"),
+    (KeyName::Left, "Left"),
+    (KeyName::Right, "Right"),
+    (KeyName::CapsLock, "Caps_Lock"),
+    (KeyName::LeftShift, "Shift_L"),
+    (KeyName::RightShift, "Shift_R"),
+    (KeyName::LeftControl, "Control_L"),
+    (KeyName::RightControl, "Control_R"),
+    (KeyName::LeftAlt, "Alt_L"),
+    (KeyName::RightAlt, "Alt_R"),
+    (KeyName::LeftSuper, "Super_L"),
+    (KeyName::RightSuper, "Super_R"),
+    (KeyName::Bang, "exclam"),
+    (KeyName::At, "at"),
+    (KeyName::Pound, "numbersign"),
+    (KeyName::Dollar, "dollar"),
+    (KeyName::Percent, "percent"),
+    (KeyName::Carrot, "asciicircum"),
+    (KeyName::Ampersand, "ampersand"),
+    (KeyName::Star, "asterisk"),
+    (KeyName::ParenLeft, "parenleft"),
+    (KeyName::ParenRight, "parenright"),
+    (KeyName::BracketLeft, "bracketleft"),
+    (KeyName::BracketRight, "bracketright"),
+    (KeyName::BraceLeft, "braceleft"),
+    (KeyName::BraceRight, "braceright"),
+    (KeyName::Backtick, "grave"),
+    (KeyName::Tilde, "asciitilde"),
+    (KeyName::Equals, "equal"),
+    (KeyName::Underscore, "underscore"),
+    (KeyName::Plus, "plus"),
+    (KeyName::ForwardSlash, "slash"),
+    (KeyName::Backslash, "backslash"),
+    (KeyName::Question, "question"),
+    (KeyName::Pipe, "bar"),
+    (KeyName::SingleQuote, "apostrophe"),
+    (KeyName::DoubleQuote, "quotedbl"),
+    (KeyName::Comma, "comma"),
+    (KeyName::Period, "period"),
+    (KeyName::Colon, "colon"),
+    (KeyName::Semicolon, "semicolon"),
+    (KeyName::Dash, "minus"),
+    (KeyName::LessThan, "less"),
+    (KeyName::GreaterThan, "greater"),
+];
+
+impl Notation for I3 {
+    fn parse(&self, input: &str) -> Result<Keys, Error> {
+        let keys: Result<_, _> = input
+            .split_whitespace()
+            .map(|key| self.parse_key(key))
+            .collect();
+        Ok(Keys(keys?))
+    }
+
+    fn format(&self, keys: &Keys) -> Result<String, ConversionError> {
+        let keys: Result<Vec<_>, _> = keys.0.iter().map(|key| self.format_key(*key)).collect();
+        Ok(keys?.join(" "))
+    }
+}
+
+impl I3 {
+    fn parse_key(&self, input: &str) -> Result<Key, Error> {
+        let (modifier_strings, name) = input.rsplit_once('+').unwrap_or(("", input));
+        if name.is_empty() {
+            return Err(Error::NoKeyName);
+        }
+
+        let mut modifiers = Modifiers::default();
+        for modifier in modifier_strings.split('+').filter(|part| !part.is_empty()) {
+            match modifier {
+                "$mod" => {
+                    modifiers.shift |= self.mod_key.shift;
+                    modifiers.control |= self.mod_key.control;
+                    modifiers.alt |= self.mod_key.alt;
+                    modifiers.super_key |= self.mod_key.super_key;
+                }
+                "Shift" => modifiers.shift = true,
+                "Control" | "Ctrl" => modifiers.control = true,
+                "Mod1" | "Alt" => modifiers.alt = true,
+                "Mod4" | "Super" => modifiers.super_key = true,
+                _ => return Err(Error::InvalidKeyModifier(modifier.to_string())),
+            }
+        }
+
+        let mut chars = name.chars();
+        let name = match (chars.next(), chars.next()) {
+            (Some(ch), None) => {
+                let (name, shift) = KeyName::from_char(ch);
+                modifiers.shift |= shift;
+                name
+            }
+            _ => KEYSYMS
+                .iter()
+                .find(|(_, keysym)| *keysym == name)
+                .map(|(key, _)| *key)
+                .or_else(|| function_or_unknown_key(name))
+                .ok_or_else(|| Error::InvalidKeyName(name.to_string()))?,
+        };
+
+        Ok(Key { modifiers, name })
+    }
+
+    fn format_key(&self, key: Key) -> Result<String, ConversionError> {
+        let Modifiers {
+            shift,
+            control,
+            alt,
+            super_key,
+            primary,
+        } = key.modifiers;
+        if primary {
+            return Err(ConversionError::UnsupportedKey(key.to_string()));
+        }
+
+        // `$mod` is written when every modifier it stands for is held
+        let mod_key = self.mod_key;
+        let has_mod = mod_key != Modifiers::default()
+            && (shift || !mod_key.shift)
+            && (control || !mod_key.control)
+            && (alt || !mod_key.alt)
+            && (super_key || !mod_key.super_key);
+
+        let mut output = String::new();
+        for (is_held, modifier) in [
+            (has_mod, "$mod"),
+            (shift && !(has_mod && mod_key.shift), "Shift"),
+            (control && !(has_mod && mod_key.control), "Ctrl"),
+            (alt && !(has_mod && mod_key.alt), "Mod1"),
+            (super_key && !(has_mod && mod_key.super_key), "Mod4"),
+        ] {
+            if is_held {
+                output.push_str(modifier);
+                output.push('+');
+            }
+        }
+
+        if let Some((_, keysym)) = KEYSYMS.iter().find(|(name, _)| *name == key.name) {
+            output.push_str(keysym);
+        } else {
+            match (key.name, literal_char(key.name)) {
+                (_, Some(ch)) => output.push(ch),
+                (KeyName::Unknown(code), _) => output.push_str(&format!("Unknown:{}", code)),
+                // `F1`
+                (name, _) => output.push_str(
+                    name.named_str()
+                        .ok_or_else(|| ConversionError::UnsupportedKey(key.to_string()))?,
+                ),
+            }
+        }
+        Ok(output)
+    }
+}
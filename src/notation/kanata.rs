@@ -0,0 +1,143 @@
+use super::{function_key_str, function_or_unknown_key, literal_char, Notation};
+use crate::{ConversionError, Error, Key, KeyName, Keys, Modifiers, ShiftedSymbols};
+
+/// Key names of kanata and kmonad configs, such as `lsft`, `spc` and `C-S-a`
+///
+/// Keys are separated by whitespace, as in a layer. Keys are written as they
+/// are on a US keyboard, so `?` is written as `S-slsh`. `M-` is
+/// [`Modifiers::super_key`], and `RA-` is read as [`Modifiers::alt`]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Kanata;
+
+/// Keys written as a name, such as `spc`, where the first name is used for
+/// formatting
+const NAMES: &[(KeyName, &str)] = &[
+    (KeyName::Space, "spc"),
+    (KeyName::Enter, "ret"),
+    (KeyName::Enter, "ent"),
+    (KeyName::Tab, "tab"),
+    (KeyName::Escape, "esc"),
+    (KeyName::Backspace, "bspc"),
+    (KeyName::Backspace, "bks"),
+    (KeyName::Delete, "del"),
+    (KeyName::Insert, "ins"),
+    (KeyName::Home, "home"),
+    (KeyName::End, "end"),
+    (KeyName::PageUp, "pgup"),
+    (KeyName::PageDown, "pgdn"),
+    (KeyName::Up, "up"),
+    (KeyName::Down, "down"),
+    (KeyName::Left, "left"),
+    (KeyName::Right, "rght"),
+    (KeyName::Right, "right"),
+    (KeyName::CapsLock, "caps"),
+    (KeyName::LeftShift, "lsft"),
+    (KeyName::RightShift, "rsft"),
+    (KeyName::LeftControl, "lctl"),
+    (KeyName::RightControl, "rctl"),
+    (KeyName::LeftAlt, "lalt"),
+    (KeyName::RightAlt, "ralt"),
+    (KeyName::LeftSuper, "lmet"),
+    (KeyName::LeftSuper, "lwin"),
+    (KeyName::RightSuper, "rmet"),
+    (KeyName::RightSuper, "rwin"),
+    (KeyName::Backtick, "grv"),
+    (KeyName::Dash, "min"),
+    (KeyName::Equals, "eql"),
+    (KeyName::BracketLeft, "lbrc"),
+    (KeyName::BracketRight, "rbrc"),
+    (KeyName::Backslash, "bsls"),
+    (KeyName::Semicolon, "scln"),
+    (KeyName::SingleQuote, "apos"),
+    (KeyName::Comma, "comm"),
+    (KeyName::Period, "dot"),
+    (KeyName::ForwardSlash, "slsh"),
+];
+
+impl Notation for Kanata {
+    fn parse(&self, input: &str) -> Result<Keys, Error> {
+        let keys: Result<_, _> = input.split_whitespace().map(parse_key).collect();
+        Ok(Keys(keys?))
+    }
+
+    fn format(&self, keys: &Keys) -> Result<String, ConversionError> {
+        let keys: Result<Vec<_>, _> = keys.0.iter().map(|key| format_key(*key)).collect();
+        Ok(keys?.join(" "))
+    }
+}
+
+fn parse_key(input: &str) -> Result<Key, Error> {
+    let mut modifiers = Modifiers::default();
+    let mut rest = input;
+    while let Some((modifier, after)) = rest.split_once('-') {
+        if after.is_empty() {
+            break;
+        }
+        let held = match modifier {
+            "C" => &mut modifiers.control,
+            "S" => &mut modifiers.shift,
+            "A" | "RA" | "AG" => &mut modifiers.alt,
+            "M" => &mut modifiers.super_key,
+            _ => return Err(Error::InvalidKeyModifier(modifier.to_string())),
+        };
+        *held = true;
+        rest = after;
+    }
+
+    let mut chars = rest.chars();
+    let name = match (chars.next(), chars.next()) {
+        (Some(ch), None) => {
+            let (name, shift) = KeyName::from_char(ch);
+            modifiers.shift |= shift;
+            name
+        }
+        _ => NAMES
+            .iter()
+            .find(|(_, named)| *named == rest)
+            .map(|(key, _)| *key)
+            .or_else(|| function_or_unknown_key(rest))
+            .ok_or_else(|| Error::InvalidKeyName(rest.to_string()))?,
+    };
+
+    Ok(Key { modifiers, name })
+}
+
+fn format_key(key: Key) -> Result<String, ConversionError> {
+    let key = key.normalize_with(ShiftedSymbols::ToShifted);
+    let Modifiers {
+        shift,
+        control,
+        alt,
+        super_key,
+        primary,
+    } = key.modifiers;
+    if primary {
+        return Err(ConversionError::UnsupportedKey(key.to_string()));
+    }
+
+    let mut output = String::new();
+    for (is_held, modifier) in [
+        (control, "C-"),
+        (alt, "A-"),
+        (shift, "S-"),
+        (super_key, "M-"),
+    ] {
+        if is_held {
+            output.push_str(modifier);
+        }
+    }
+
+    if let Some((_, name)) = NAMES.iter().find(|(name, _)| *name == key.name) {
+        output.push_str(name);
+    } else {
+        match (key.name, literal_char(key.name)) {
+            (_, Some(ch)) => output.push(ch),
+            (KeyName::Unknown(code), _) => output.push_str(&format!("Unknown:{}", code)),
+            (name, _) => match function_key_str(name) {
+                Some(function) => output.push_str(&function.to_ascii_lowercase()),
+                None => return Err(ConversionError::UnsupportedKey(key.to_string())),
+            },
+        }
+    }
+    Ok(output)
+}
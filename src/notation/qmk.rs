@@ -0,0 +1,275 @@
+use super::{function_key_str, function_or_unknown_key, literal_char, Notation};
+use crate::{ConversionError, Error, Key, KeyName, Keys, Modifiers, ShiftedSymbols};
+
+/// QMK firmware keycodes, such as `KC_A`, `LCTL(KC_C)` and `S(KC_1)`
+///
+/// Keys are separated by commas or whitespace, as in a `keymap.c` layer, and
+/// formatted with `, ` between them. Keys are written as they are on a US
+/// keyboard, so `?` is written as `LSFT(KC_SLSH)`, but aliases such as
+/// `KC_QUES` are read. Left and right modifier functions, such as `LCTL` and
+/// `RCTL`, are the same modifier
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Qmk;
+
+/// Keycodes written as a name, such as `KC_ENT`, where the first name is
+/// used for formatting
+const NAMES: &[(KeyName, &str)] = &[
+    (KeyName::Enter, "KC_ENT"),
+    (KeyName::Enter, "KC_ENTER"),
+    (KeyName::Escape, "KC_ESC"),
+    (KeyName::Escape, "KC_ESCAPE"),
+    (KeyName::Backspace, "KC_BSPC"),
+    (KeyName::Backspace, "KC_BACKSPACE"),
+    (KeyName::Tab, "KC_TAB"),
+    (KeyName::Space, "KC_SPC"),
+    (KeyName::Space, "KC_SPACE"),
+    (KeyName::Dash, "KC_MINS"),
+    (KeyName::Dash, "KC_MINUS"),
+    (KeyName::Equals, "KC_EQL"),
+    (KeyName::Equals, "KC_EQUAL"),
+    (KeyName::BracketLeft, "KC_LBRC"),
+    (KeyName::BracketLeft, "KC_LEFT_BRACKET"),
+    (KeyName::BracketRight, "KC_RBRC"),
+    (KeyName::BracketRight, "KC_RIGHT_BRACKET"),
+    (KeyName::Backslash, "KC_BSLS"),
+    (KeyName::Backslash, "KC_BACKSLASH"),
+    (KeyName::Semicolon, "KC_SCLN"),
+    (KeyName::Semicolon, "KC_SEMICOLON"),
+    (KeyName::SingleQuote, "KC_QUOT"),
+    (KeyName::SingleQuote, "KC_QUOTE"),
+    (KeyName::Backtick, "KC_GRV"),
+    (KeyName::Backtick, "KC_GRAVE"),
+    (KeyName::Comma, "KC_COMM"),
+    (KeyName::Comma, "KC_COMMA"),
+    (KeyName::Period, "KC_DOT"),
+    (KeyName::ForwardSlash, "KC_SLSH"),
+    (KeyName::ForwardSlash, "KC_SLASH"),
+    (KeyName::CapsLock, "KC_CAPS"),
+    (KeyName::CapsLock, "KC_CAPS_LOCK"),
+    (KeyName::Insert, "KC_INS"),
+    (KeyName::Insert, "KC_INSERT"),
+    (KeyName::Delete, "KC_DEL"),
+    (KeyName::Delete, "KC_DELETE"),
+    (KeyName::Home, "KC_HOME"),
+    (KeyName::End, "KC_END"),
+    (KeyName::PageUp, "KC_PGUP"),
+    (KeyName::PageUp, "KC_PAGE_UP"),
+    (KeyName::PageDown, "KC_PGDN"),
+    (KeyName::PageDown, "KC_PAGE_DOWN"),
+    (KeyName::Up, "KC_UP"),
+    (KeyName::Down, "KC_DOWN"),
+    (KeyName::Left, "KC_LEFT"),
+    (KeyName::Right, "KC_RGHT"),
+    (KeyName::Right, "KC_RIGHT"),
+    (KeyName::LeftControl, "KC_LCTL"),
+    (KeyName::LeftControl, "KC_LEFT_CTRL"),
+    (KeyName::LeftShift, "KC_LSFT"),
+    (KeyName::LeftShift, "KC_LEFT_SHIFT"),
+    (KeyName::LeftAlt, "KC_LALT"),
+    (KeyName::LeftAlt, "KC_LEFT_ALT"),
+    (KeyName::LeftAlt, "KC_LOPT"),
+    (KeyName::LeftSuper, "KC_LGUI"),
+    (KeyName::LeftSuper, "KC_LEFT_GUI"),
+    (KeyName::LeftSuper, "KC_LCMD"),
+    (KeyName::LeftSuper, "KC_LWIN"),
+    (KeyName::RightControl, "KC_RCTL"),
+    (KeyName::RightControl, "KC_RIGHT_CTRL"),
+    (KeyName::RightShift, "KC_RSFT"),
+    (KeyName::RightShift, "KC_RIGHT_SHIFT"),
+    (KeyName::RightAlt, "KC_RALT"),
+    (KeyName::RightAlt, "KC_RIGHT_ALT"),
+    (KeyName::RightAlt, "KC_ROPT"),
+    (KeyName::RightAlt, "KC_ALGR"),
+    (KeyName::RightSuper, "KC_RGUI"),
+    (KeyName::RightSuper, "KC_RIGHT_GUI"),
+    (KeyName::RightSuper, "KC_RCMD"),
+    (KeyName::RightSuper, "KC_RWIN"),
+    // Shifted aliases, which are only read
+    (KeyName::Tilde, "KC_TILD"),
+    (KeyName::Tilde, "KC_TILDE"),
+    (KeyName::Bang, "KC_EXLM"),
+    (KeyName::Bang, "KC_EXCLAIM"),
+    (KeyName::At, "KC_AT"),
+    (KeyName::Pound, "KC_HASH"),
+    (KeyName::Dollar, "KC_DLR"),
+    (KeyName::Dollar, "KC_DOLLAR"),
+    (KeyName::Percent, "KC_PERC"),
+    (KeyName::Percent, "KC_PERCENT"),
+    (KeyName::Carrot, "KC_CIRC"),
+    (KeyName::Carrot, "KC_CIRCUMFLEX"),
+    (KeyName::Ampersand, "KC_AMPR"),
+    (KeyName::Ampersand, "KC_AMPERSAND"),
+    (KeyName::Star, "KC_ASTR"),
+    (KeyName::Star, "KC_ASTERISK"),
+    (KeyName::ParenLeft, "KC_LPRN"),
+    (KeyName::ParenLeft, "KC_LEFT_PAREN"),
+    (KeyName::ParenRight, "KC_RPRN"),
+    (KeyName::ParenRight, "KC_RIGHT_PAREN"),
+    (KeyName::Underscore, "KC_UNDS"),
+    (KeyName::Underscore, "KC_UNDERSCORE"),
+    (KeyName::Plus, "KC_PLUS"),
+    (KeyName::BraceLeft, "KC_LCBR"),
+    (KeyName::BraceLeft, "KC_LEFT_CURLY_BRACE"),
+    (KeyName::BraceRight, "KC_RCBR"),
+    (KeyName::BraceRight, "KC_RIGHT_CURLY_BRACE"),
+    (KeyName::Pipe, "KC_PIPE"),
+    (KeyName::Colon, "KC_COLN"),
+    (KeyName::Colon, "KC_COLON"),
+    (KeyName::DoubleQuote, "KC_DQUO"),
+    (KeyName::DoubleQuote, "KC_DQT"),
+    (KeyName::DoubleQuote, "KC_DOUBLE_QUOTE"),
+    (KeyName::LessThan, "KC_LABK"),
+    (KeyName::LessThan, "KC_LT"),
+    (KeyName::LessThan, "KC_LEFT_ANGLE_BRACKET"),
+    (KeyName::GreaterThan, "KC_RABK"),
+    (KeyName::GreaterThan, "KC_GT"),
+    (KeyName::GreaterThan, "KC_RIGHT_ANGLE_BRACKET"),
+    (KeyName::Question, "KC_QUES"),
+    (KeyName::Question, "KC_QUESTION"),
+];
+
+const fn modifiers(control: bool, alt: bool, shift: bool, super_key: bool) -> Modifiers {
+    Modifiers {
+        shift,
+        control,
+        alt,
+        super_key,
+        primary: false,
+    }
+}
+
+/// Modifier functions, such as `LCTL(kc)` and `MEH(kc)`
+const FUNCTIONS: &[(&str, Modifiers)] = &[
+    ("LCTL", modifiers(true, false, false, false)),
+    ("LALT", modifiers(false, true, false, false)),
+    ("LSFT", modifiers(false, false, true, false)),
+    ("LGUI", modifiers(false, false, false, true)),
+    ("C", modifiers(true, false, false, false)),
+    ("A", modifiers(false, true, false, false)),
+    ("S", modifiers(false, false, true, false)),
+    ("G", modifiers(false, false, false, true)),
+    ("LOPT", modifiers(false, true, false, false)),
+    ("LCMD", modifiers(false, false, false, true)),
+    ("LWIN", modifiers(false, false, false, true)),
+    ("RCTL", modifiers(true, false, false, false)),
+    ("RALT", modifiers(false, true, false, false)),
+    ("ROPT", modifiers(false, true, false, false)),
+    ("ALGR", modifiers(false, true, false, false)),
+    ("RSFT", modifiers(false, false, true, false)),
+    ("RGUI", modifiers(false, false, false, true)),
+    ("RCMD", modifiers(false, false, false, true)),
+    ("RWIN", modifiers(false, false, false, true)),
+    ("LCS", modifiers(true, false, true, false)),
+    ("C_S", modifiers(true, false, true, false)),
+    ("LCA", modifiers(true, true, false, false)),
+    ("LSA", modifiers(false, true, true, false)),
+    ("LAG", modifiers(false, true, false, true)),
+    ("LSG", modifiers(false, false, true, true)),
+    ("SGUI", modifiers(false, false, true, true)),
+    ("LCAG", modifiers(true, true, false, true)),
+    ("MEH", modifiers(true, true, true, false)),
+    ("HYPR", modifiers(true, true, true, true)),
+];
+
+impl Notation for Qmk {
+    fn parse(&self, input: &str) -> Result<Keys, Error> {
+        let keys: Result<_, _> = input
+            .split(|ch: char| ch == ',' || ch.is_whitespace())
+            .filter(|keycode| !keycode.is_empty())
+            .map(parse_key)
+            .collect();
+        Ok(Keys(keys?))
+    }
+
+    fn format(&self, keys: &Keys) -> Result<String, ConversionError> {
+        let keys: Result<Vec<_>, _> = keys.0.iter().map(|key| format_key(*key)).collect();
+        Ok(keys?.join(", "))
+    }
+}
+
+fn parse_key(input: &str) -> Result<Key, Error> {
+    let mut modifiers = Modifiers::default();
+    let mut rest = input;
+
+    // Modifier functions wrap the keycode, as in `LCTL(LSFT(KC_A))`
+    while let Some((function, inner)) = rest.split_once('(') {
+        let inner = inner
+            .strip_suffix(')')
+            .ok_or_else(|| Error::InvalidKeyName(rest.to_string()))?;
+        let (_, held) = FUNCTIONS
+            .iter()
+            .find(|(name, _)| *name == function)
+            .ok_or_else(|| Error::InvalidKeyModifier(function.to_string()))?;
+        modifiers.control |= held.control;
+        modifiers.alt |= held.alt;
+        modifiers.shift |= held.shift;
+        modifiers.super_key |= held.super_key;
+        rest = inner;
+    }
+
+    let invalid = || Error::InvalidKeyName(rest.to_string());
+    if let Some((name, _)) = NAMES.iter().find(|(_, named)| *named == rest) {
+        return Ok(Key {
+            modifiers,
+            name: *name,
+        });
+    }
+    let code = rest.strip_prefix("KC_").ok_or_else(invalid)?;
+    let mut chars = code.chars();
+    let name = match (chars.next(), chars.next()) {
+        // `KC_A` and `KC_1`
+        (Some(ch), None) if ch.is_ascii_uppercase() || ch.is_ascii_digit() => {
+            KeyName::from_char(ch.to_ascii_lowercase()).0
+        }
+        // `KC_F5`
+        (Some('F'), Some(_)) => function_or_unknown_key(code).ok_or_else(invalid)?,
+        _ => return Err(invalid()),
+    };
+
+    Ok(Key { modifiers, name })
+}
+
+fn format_key(key: Key) -> Result<String, ConversionError> {
+    let key = key.normalize_with(ShiftedSymbols::ToShifted);
+    let Modifiers {
+        shift,
+        control,
+        alt,
+        super_key,
+        primary,
+    } = key.modifiers;
+    if primary {
+        return Err(ConversionError::UnsupportedKey(key.to_string()));
+    }
+
+    let code = if let Some((_, name)) = NAMES.iter().find(|(name, _)| *name == key.name) {
+        name.to_string()
+    } else if let Some(upper) = key.name.upper_str() {
+        format!("KC_{}", upper)
+    } else {
+        match (key.name, literal_char(key.name)) {
+            (_, Some(ch)) if ch.is_ascii_digit() => format!("KC_{}", ch),
+            (name, _) => match function_key_str(name) {
+                Some(function) => format!("KC_{}", function),
+                None => return Err(ConversionError::UnsupportedKey(key.to_string())),
+            },
+        }
+    };
+
+    let mut output = String::new();
+    let mut depth = 0;
+    for (is_held, function) in [
+        (control, "LCTL("),
+        (alt, "LALT("),
+        (shift, "LSFT("),
+        (super_key, "LGUI("),
+    ] {
+        if is_held {
+            output.push_str(function);
+            depth += 1;
+        }
+    }
+    output.push_str(&code);
+    output.push_str(&")".repeat(depth));
+    Ok(output)
+}
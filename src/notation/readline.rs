@@ -0,0 +1,227 @@
+use super::{literal_char, Notation};
+use crate::{ConversionError, Error, Key, KeyName, Keys, Modifiers};
+
+/// Readline `.inputrc` notation, such as `"\C-x\C-r"` and `"\M-\C-e"`
+///
+/// A quoted sequence is a key for each character, with escapes such as `\e`,
+/// `\d` and `\C-`. Without quotes, the input is a single key name, such as
+/// `Control-u` or `Meta-Rubout`. Control characters are keys with control,
+/// as in a terminal, so tab is `\t` rather than `\C-i`. Keys are always
+/// formatted as a quoted sequence
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Readline;
+
+/// Key names of the unquoted form, which are case-insensitive
+const NAMES: &[(char, &str)] = &[
+    ('\x7f', "Rubout"),
+    ('\x7f', "DEL"),
+    ('\x1b', "Escape"),
+    ('\x1b', "ESC"),
+    ('\n', "Newline"),
+    ('\n', "LFD"),
+    ('\r', "Return"),
+    ('\r', "RET"),
+    (' ', "Space"),
+    (' ', "SPC"),
+    ('\t', "Tab"),
+];
+
+impl Notation for Readline {
+    fn parse(&self, input: &str) -> Result<Keys, Error> {
+        let input = input.trim();
+        match input.strip_prefix('"').and_then(|i| i.strip_suffix('"')) {
+            Some(sequence) => parse_sequence(sequence),
+            None => parse_key_name(input).map(|key| Keys(vec![key])),
+        }
+    }
+
+    fn format(&self, keys: &Keys) -> Result<String, ConversionError> {
+        let keys: Result<Vec<_>, _> = keys.0.iter().map(|key| format_key(*key)).collect();
+        Ok(format!("\"{}\"", keys?.join("")))
+    }
+}
+
+fn parse_sequence(input: &str) -> Result<Keys, Error> {
+    let mut keys = Vec::new();
+    let mut rest = input;
+    while !rest.is_empty() {
+        let mut modifiers = Modifiers::default();
+        loop {
+            if let Some(after) = rest.strip_prefix("\\C-") {
+                modifiers.control = true;
+                rest = after;
+            } else if let Some(after) = rest.strip_prefix("\\M-") {
+                modifiers.alt = true;
+                rest = after;
+            } else {
+                break;
+            }
+        }
+
+        let (ch, after) = parse_char(rest)?;
+        keys.push(with_modifiers(char_key(ch), modifiers));
+        rest = after;
+    }
+    Ok(Keys(keys))
+}
+
+/// Parse one character of a quoted sequence, which may be escaped
+fn parse_char(input: &str) -> Result<(char, &str), Error> {
+    let mut chars = input.chars();
+    let ch = match chars.next() {
+        Some('\\') => chars.next().ok_or(Error::TrailingEscape)?,
+        Some(ch) => return Ok((ch, chars.as_str())),
+        None => return Err(Error::NoKeyName),
+    };
+    let rest = chars.as_str();
+
+    let ch = match ch {
+        'e' => '\x1b',
+        'd' => '\x7f',
+        'a' => '\x07',
+        'b' => '\x08',
+        'f' => '\x0c',
+        'n' => '\n',
+        'r' => '\r',
+        't' => '\t',
+        'v' => '\x0b',
+        // Octal, such as `\033`
+        '0'..='7' => return parse_code(&input[1..], "", 3, 8),
+        // Hexadecimal, such as `\x1b`
+        'x' => return parse_code(rest, "x", 2, 16),
+        // `\\`, `\"` and `\'`
+        ch => ch,
+    };
+    Ok((ch, rest))
+}
+
+/// Parse a character code of up to `max_digits` digits after `prefix`, such
+/// as `033` or `x1b`
+fn parse_code<'a>(
+    input: &'a str,
+    prefix: &str,
+    max_digits: usize,
+    radix: u32,
+) -> Result<(char, &'a str), Error> {
+    let length = input
+        .chars()
+        .take(max_digits)
+        .take_while(|ch| ch.is_digit(radix))
+        .count();
+    let (digits, rest) = input.split_at(length);
+    let invalid = |text: &str| Error::InvalidCharCode(format!("\\{}{}", prefix, text));
+    if digits.is_empty() {
+        let text: String = input.chars().take(max_digits).collect();
+        return Err(invalid(&text));
+    }
+    // Octal codes above `\377` do not fit in a byte
+    let code = u32::from_str_radix(digits, radix)
+        .ok()
+        .and_then(|code| u8::try_from(code).ok())
+        .ok_or_else(|| invalid(digits))?;
+    Ok((char::from(code), rest))
+}
+
+/// Parse a key name of the unquoted form, such as `Control-u`
+fn parse_key_name(input: &str) -> Result<Key, Error> {
+    let mut modifiers = Modifiers::default();
+    let mut rest = input;
+    while let Some((modifier, after)) = rest.split_once('-') {
+        if after.is_empty() {
+            break;
+        }
+        match modifier.to_ascii_lowercase().as_str() {
+            "c" | "control" => modifiers.control = true,
+            "m" | "meta" => modifiers.alt = true,
+            _ => return Err(Error::InvalidKeyModifier(modifier.to_string())),
+        }
+        rest = after;
+    }
+
+    let mut chars = rest.chars();
+    let ch = match (chars.next(), chars.next()) {
+        (Some(ch), None) => ch,
+        (None, _) => return Err(Error::NoKeyName),
+        _ => NAMES
+            .iter()
+            .find(|(_, name)| name.eq_ignore_ascii_case(rest))
+            .map(|(ch, _)| *ch)
+            .ok_or_else(|| Error::InvalidKeyName(rest.to_string()))?,
+    };
+    Ok(with_modifiers(char_key(ch), modifiers))
+}
+
+/// Key of a character, where control characters are keys with control
+fn char_key(ch: char) -> Key {
+    let (name, control) = match ch {
+        '\x1b' => (KeyName::Escape, false),
+        '\t' => (KeyName::Tab, false),
+        '\r' => (KeyName::Enter, false),
+        '\x7f' => (KeyName::Backspace, false),
+        // `\x01` is `C-a`
+        '\0'..='\x1f' => {
+            let ch = char::from(ch as u8 + 0x40).to_ascii_lowercase();
+            (KeyName::from_char(ch).0, true)
+        }
+        _ => {
+            let (name, shift) = KeyName::from_char(ch);
+            let modifiers = Modifiers {
+                shift,
+                ..Default::default()
+            };
+            return Key { modifiers, name };
+        }
+    };
+    let modifiers = Modifiers {
+        control,
+        ..Default::default()
+    };
+    Key { modifiers, name }
+}
+
+fn with_modifiers(mut key: Key, modifiers: Modifiers) -> Key {
+    key.modifiers.control |= modifiers.control;
+    key.modifiers.alt |= modifiers.alt;
+    key
+}
+
+fn format_key(key: Key) -> Result<String, ConversionError> {
+    let unsupported = || ConversionError::UnsupportedKey(key.to_string());
+    let Modifiers {
+        shift,
+        control,
+        alt,
+        super_key,
+        primary,
+    } = key.modifiers;
+
+    // Shift can only be written as an uppercase letter
+    let upper = if shift { key.name.upper_str() } else { None };
+    if super_key || primary || shift && upper.is_none() {
+        return Err(unsupported());
+    }
+
+    let mut output = String::new();
+    if alt {
+        output.push_str("\\M-");
+    }
+    if control {
+        output.push_str("\\C-");
+    }
+
+    match (key.name, upper, literal_char(key.name)) {
+        (KeyName::Escape, _, _) => output.push_str("\\e"),
+        (KeyName::Tab, _, _) => output.push_str("\\t"),
+        (KeyName::Enter, _, _) => output.push_str("\\r"),
+        (KeyName::Backspace, _, _) => output.push_str("\\d"),
+        (KeyName::Space, _, _) => output.push(' '),
+        (_, Some(upper), _) => output.push_str(upper),
+        (_, _, Some(ch @ ('\\' | '"'))) => {
+            output.push('\\');
+            output.push(ch);
+        }
+        (_, _, Some(ch)) if !ch.is_control() => output.push(ch),
+        _ => return Err(unsupported()),
+    }
+    Ok(output)
+}
@@ -0,0 +1,137 @@
+use super::{function_key_str, function_or_unknown_key, literal_char, Notation};
+use crate::{ConversionError, Error, Key, KeyName, Keys, Modifiers};
+
+/// tmux `bind-key` notation, such as `C-b`, `M-Left` and `S-F5`
+///
+/// Keys in a sequence are separated by whitespace. `^` is accepted for `C-`,
+/// and `BTab` is shift and tab. tmux has no super modifier, so keys with
+/// [`Modifiers::super_key`] cannot be formatted
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Tmux;
+
+/// Keys written as a name, such as `Enter`, where the first name is used for
+/// formatting. Names are case-insensitive
+const NAMES: &[(KeyName, &str)] = &[
+    (KeyName::Space, "Space"),
+    (KeyName::Enter, "Enter"),
+    (KeyName::Tab, "Tab"),
+    (KeyName::Escape, "Escape"),
+    (KeyName::Backspace, "BSpace"),
+    (KeyName::Delete, "DC"),
+    (KeyName::Insert, "IC"),
+    (KeyName::Home, "Home"),
+    (KeyName::End, "End"),
+    (KeyName::PageUp, "PPage"),
+    (KeyName::PageUp, "PageUp"),
+    (KeyName::PageUp, "PgUp"),
+    (KeyName::PageDown, "NPage"),
+    (KeyName::PageDown, "PageDown"),
+    (KeyName::PageDown, "PgDn"),
+    (KeyName::Up, "Up"),
+    (KeyName::Down, "Down"),
+    (KeyName::Left, "Left"),
+    (KeyName::Right, "Right"),
+];
+
+impl Notation for Tmux {
+    fn parse(&self, input: &str) -> Result<Keys, Error> {
+        let keys: Result<_, _> = input.split_whitespace().map(parse_key).collect();
+        Ok(Keys(keys?))
+    }
+
+    fn format(&self, keys: &Keys) -> Result<String, ConversionError> {
+        let keys: Result<Vec<_>, _> = keys.0.iter().map(|key| format_key(*key)).collect();
+        Ok(keys?.join(" "))
+    }
+}
+
+fn parse_key(input: &str) -> Result<Key, Error> {
+    let mut modifiers = Modifiers::default();
+    let mut rest = input;
+
+    loop {
+        let mut chars = rest.chars();
+        let held = match (chars.next(), chars.next()) {
+            (Some('^'), Some(_)) => {
+                modifiers.control = true;
+                rest = &rest[1..];
+                continue;
+            }
+            (Some(modifier), Some('-')) if !chars.as_str().is_empty() => {
+                match modifier.to_ascii_uppercase() {
+                    'C' => &mut modifiers.control,
+                    'M' => &mut modifiers.alt,
+                    'S' => &mut modifiers.shift,
+                    _ => return Err(Error::InvalidKeyModifier(modifier.to_string())),
+                }
+            }
+            _ => break,
+        };
+        *held = true;
+        rest = chars.as_str();
+    }
+
+    let mut chars = rest.chars();
+    let name = match (chars.next(), chars.next()) {
+        (Some(ch), None) => {
+            let (name, shift) = KeyName::from_char(ch);
+            modifiers.shift |= shift;
+            name
+        }
+        _ if rest.eq_ignore_ascii_case("BTab") => {
+            modifiers.shift = true;
+            KeyName::Tab
+        }
+        _ => NAMES
+            .iter()
+            .find(|(_, named)| named.eq_ignore_ascii_case(rest))
+            .map(|(key, _)| *key)
+            .or_else(|| function_or_unknown_key(rest))
+            .ok_or_else(|| Error::InvalidKeyName(rest.to_string()))?,
+    };
+
+    Ok(Key { modifiers, name })
+}
+
+fn format_key(key: Key) -> Result<String, ConversionError> {
+    let Modifiers {
+        shift,
+        control,
+        alt,
+        super_key,
+        primary,
+    } = key.modifiers;
+    if super_key || primary {
+        return Err(ConversionError::UnsupportedKey(key.to_string()));
+    }
+
+    // Shift is implied by the uppercase letter, or by `BTab`
+    let upper = if shift { key.name.upper_str() } else { None };
+    let is_back_tab = shift && key.name == KeyName::Tab;
+    let shift = shift && upper.is_none() && !is_back_tab;
+
+    let mut output = String::new();
+    for (is_held, modifier) in [(control, "C-"), (alt, "M-"), (shift, "S-")] {
+        if is_held {
+            output.push_str(modifier);
+        }
+    }
+
+    if let Some(upper) = upper {
+        output.push_str(upper);
+    } else if is_back_tab {
+        output.push_str("BTab");
+    } else if let Some((_, name)) = NAMES.iter().find(|(name, _)| *name == key.name) {
+        output.push_str(name);
+    } else {
+        match (key.name, literal_char(key.name)) {
+            (_, Some(ch)) => output.push(ch),
+            (KeyName::Unknown(code), _) => output.push_str(&format!("Unknown:{}", code)),
+            (name, _) => match function_key_str(name) {
+                Some(function) => output.push_str(function),
+                None => return Err(ConversionError::UnsupportedKey(key.to_string())),
+            },
+        }
+    }
+    Ok(output)
+}
@@ -0,0 +1,133 @@
+use super::{function_key_str, function_or_unknown_key, literal_char, Notation};
+use crate::{ConversionError, Error, Key, KeyName, Keys, Modifiers, ShiftedSymbols};
+
+/// VS Code `keybindings.json` notation, such as `ctrl+k ctrl+s`
+///
+/// Keys in a chord are separated by whitespace. Keys are written as they are
+/// on a US keyboard, so `?` is written as `shift+/`
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct VsCode {
+    /// Write [`Modifiers::super_key`] as `cmd` rather than `meta`, as on macOS
+    pub is_mac: bool,
+}
+
+// Not derivable on macOS
+#[allow(clippy::derivable_impls)]
+impl Default for VsCode {
+    fn default() -> Self {
+        Self {
+            is_mac: cfg!(target_os = "macos"),
+        }
+    }
+}
+
+const NAMES: &[(KeyName, &str)] = &[
+    (KeyName::Space, "space"),
+    (KeyName::Enter, "enter"),
+    (KeyName::Tab, "tab"),
+    (KeyName::Escape, "escape"),
+    (KeyName::Backspace, "backspace"),
+    (KeyName::Delete, "delete"),
+    (KeyName::Insert, "insert"),
+    (KeyName::Home, "home"),
+    (KeyName::End, "end"),
+    (KeyName::PageUp, "pageup"),
+    (KeyName::PageDown, "pagedown"),
+    (KeyName::Up, "up"),
+    (KeyName::Down, "down"),
+    (KeyName::Left, "left"),
+    (KeyName::Right, "right"),
+];
+
+impl Notation for VsCode {
+    fn parse(&self, input: &str) -> Result<Keys, Error> {
+        let keys: Result<_, _> = input.split_whitespace().map(parse_key).collect();
+        Ok(Keys(keys?))
+    }
+
+    fn format(&self, keys: &Keys) -> Result<String, ConversionError> {
+        let keys: Result<Vec<_>, _> = keys.0.iter().map(|key| self.format_key(*key)).collect();
+        Ok(keys?.join(" "))
+    }
+}
+
+fn parse_key(input: &str) -> Result<Key, Error> {
+    let (modifier_strings, name) = input.rsplit_once('+').unwrap_or(("", input));
+    if name.is_empty() {
+        return Err(Error::NoKeyName);
+    }
+
+    let mut modifiers = Modifiers::default();
+    for modifier in modifier_strings.split('+').filter(|part| !part.is_empty()) {
+        let held = match modifier.to_ascii_lowercase().as_str() {
+            "ctrl" => &mut modifiers.control,
+            "alt" => &mut modifiers.alt,
+            "shift" => &mut modifiers.shift,
+            "cmd" | "meta" | "win" => &mut modifiers.super_key,
+            _ => return Err(Error::InvalidKeyModifier(modifier.to_string())),
+        };
+        *held = true;
+    }
+
+    let lower = name.to_ascii_lowercase();
+    let mut chars = lower.chars();
+    let name = match (chars.next(), chars.next()) {
+        (Some(ch), None) => {
+            let (name, shift) = KeyName::from_char(ch);
+            modifiers.shift |= shift;
+            name
+        }
+        _ => NAMES
+            .iter()
+            .find(|(_, named)| *named == lower)
+            .map(|(key, _)| *key)
+            .or_else(|| function_or_unknown_key(name))
+            .ok_or_else(|| Error::InvalidKeyName(name.to_string()))?,
+    };
+
+    Ok(Key { modifiers, name })
+}
+
+impl VsCode {
+    fn format_key(&self, key: Key) -> Result<String, ConversionError> {
+        let key = key.normalize_with(ShiftedSymbols::ToShifted);
+        let Modifiers {
+            shift,
+            control,
+            alt,
+            super_key,
+            primary,
+        } = key.modifiers;
+        if primary {
+            return Err(ConversionError::UnsupportedKey(key.to_string()));
+        }
+        let super_name = if self.is_mac { "cmd" } else { "meta" };
+
+        let mut output = String::new();
+        for (is_held, modifier) in [
+            (control, "ctrl"),
+            (shift, "shift"),
+            (alt, "alt"),
+            (super_key, super_name),
+        ] {
+            if is_held {
+                output.push_str(modifier);
+                output.push('+');
+            }
+        }
+
+        if let Some((_, name)) = NAMES.iter().find(|(name, _)| *name == key.name) {
+            output.push_str(name);
+        } else {
+            match (key.name, literal_char(key.name)) {
+                (_, Some(ch)) => output.push(ch),
+                (KeyName::Unknown(code), _) => output.push_str(&format!("Unknown:{}", code)),
+                (name, _) => match function_key_str(name) {
+                    Some(function) => output.push_str(&function.to_ascii_lowercase()),
+                    None => return Err(ConversionError::UnsupportedKey(key.to_string())),
+                },
+            }
+        }
+        Ok(output)
+    }
+}
@@ -0,0 +1,127 @@
+//! Parsing input which may still be being typed, such as in a rebinding
+//! dialog
+
+use crate::{parse_key, parse_keys, parse_modifier, parse_name, suggest};
+use crate::{Error, KeyName, Keys, Modifiers, ParserOptions};
+
+/// Result of parsing input which may still be being typed
+#[derive(Clone, Debug, PartialEq)]
+pub enum PartialParse {
+    /// Valid and complete
+    Complete(Keys),
+    /// Valid so far, but the last key is unfinished, such as `a<C-`
+    ///
+    /// Contains the keys before the unfinished key
+    Incomplete(Keys),
+    /// Invalid, whatever is typed next
+    Invalid(Error),
+}
+
+/// Parse keys, allowing the last key to be unfinished
+pub fn parse_keys_partial(input: &str) -> PartialParse {
+    let error = match parse_keys(input) {
+        Ok(keys) => return PartialParse::Complete(keys),
+        Err(error) => error,
+    };
+    let Some(start) = unfinished_start(input) else {
+        return PartialParse::Invalid(error);
+    };
+    let keys = match parse_keys(&input[..start]) {
+        Ok(keys) => keys,
+        Err(error) => return PartialParse::Invalid(error),
+    };
+
+    let unfinished = &input[start..];
+    if unfinished == "\\" || unfinished.strip_prefix('<').is_some_and(is_group_prefix) {
+        return PartialParse::Incomplete(keys);
+    }
+    // Report the error as if the group was finished, such as `<Foo-` as
+    // `<Foo-a>`
+    let name = if unfinished.ends_with('-') { "a" } else { "" };
+    let finished = format!("{}{}>", unfinished, name);
+    PartialParse::Invalid(parse_key(&finished).err().unwrap_or(error))
+}
+
+/// Start of an unclosed group or trailing escape at the end of the input
+fn unfinished_start(input: &str) -> Option<usize> {
+    let mut group_start = None;
+    let mut escape_start = None;
+
+    for (i, ch) in input.char_indices() {
+        if escape_start.take().is_some() {
+            continue;
+        }
+        match ch {
+            '\\' => escape_start = Some(i),
+            '<' => group_start = Some(i),
+            '>' => group_start = None,
+            _ => (),
+        }
+    }
+
+    group_start.or(escape_start)
+}
+
+/// Whether the inside of an unclosed group could become a valid key
+fn is_group_prefix(input: &str) -> bool {
+    let options = ParserOptions::default();
+
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut is_escaped = false;
+    for (i, ch) in input.char_indices() {
+        if is_escaped {
+            is_escaped = false;
+        } else if ch == '\\' {
+            is_escaped = true;
+        } else if ch == '-' {
+            parts.push(&input[start..i]);
+            start = i + 1;
+        }
+    }
+    let last = &input[start..];
+    // Empty parts between dashes are ignored
+    let mut modifiers: Vec<&str> = parts.into_iter().filter(|part| !part.is_empty()).collect();
+
+    // `<C-Char-0x4`
+    if modifiers
+        .last()
+        .is_some_and(|modifier| modifier.eq_ignore_ascii_case("Char"))
+    {
+        modifiers.pop();
+        let digits = match last.strip_prefix("0x").or(last.strip_prefix("0X")) {
+            Some(hex) => hex.chars().all(|ch| ch.is_ascii_hexdigit()),
+            None => last.chars().all(|ch| ch.is_ascii_digit()),
+        };
+        return digits && is_valid_modifiers(&modifiers, &options);
+    }
+
+    if !is_valid_modifiers(&modifiers, &options) {
+        return false;
+    }
+    if last.is_empty() || last == "\\" || KeyName::from_name(last).is_some() {
+        return true;
+    }
+    // A single character needs a modifier before it, such as `<C-a`
+    if !modifiers.is_empty() && parse_name(last, &options).is_some() {
+        return true;
+    }
+    if parse_modifier(last, &mut Modifiers::default(), &options) {
+        return true;
+    }
+
+    let last = last.to_ascii_lowercase();
+    let mut candidates = KeyName::ALL
+        .iter()
+        .filter_map(|name| name.named_str())
+        .chain(suggest::MODIFIERS.iter().copied())
+        .chain(["Char", "Unknown:"]);
+    candidates.any(|candidate| candidate.to_ascii_lowercase().starts_with(&last))
+}
+
+fn is_valid_modifiers(modifiers: &[&str], options: &ParserOptions) -> bool {
+    let mut parsed = Modifiers::default();
+    modifiers
+        .iter()
+        .all(|modifier| parse_modifier(modifier, &mut parsed, options))
+}
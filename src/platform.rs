@@ -0,0 +1,77 @@
+use crate::{ConversionError, Key, Keys, Modifiers};
+
+/// Operating system that keys are shown or resolved for
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Platform {
+    MacOs,
+    Windows,
+    Linux,
+}
+
+impl Platform {
+    /// Platform this crate was compiled for, where any other Unix is
+    /// [`Platform::Linux`]
+    pub fn current() -> Self {
+        if cfg!(target_os = "macos") {
+            Platform::MacOs
+        } else if cfg!(windows) {
+            Platform::Windows
+        } else {
+            Platform::Linux
+        }
+    }
+}
+
+impl Default for Platform {
+    fn default() -> Self {
+        Self::current()
+    }
+}
+
+impl Keys {
+    /// Replace [`Modifiers::primary`] with super on macOS and control
+    /// elsewhere
+    pub fn resolve_platform(&self, platform: Platform) -> Keys {
+        Keys(
+            self.0
+                .iter()
+                .map(|key| key.resolve_platform(platform))
+                .collect(),
+        )
+    }
+}
+
+impl Key {
+    /// Replace [`Modifiers::primary`] with super on macOS and control
+    /// elsewhere
+    pub fn resolve_platform(&self, platform: Platform) -> Key {
+        Key {
+            modifiers: self.modifiers.resolve_platform(platform),
+            name: self.name,
+        }
+    }
+}
+
+impl Modifiers {
+    /// Replace [`Modifiers::primary`] with super on macOS and control
+    /// elsewhere
+    pub fn resolve_platform(self, platform: Platform) -> Modifiers {
+        let mut modifiers = self;
+        if modifiers.primary {
+            modifiers.primary = false;
+            match platform {
+                Platform::MacOs => modifiers.super_key = true,
+                Platform::Windows | Platform::Linux => modifiers.control = true,
+            }
+        }
+        modifiers
+    }
+    /// Modifiers as they are, if [`Modifiers::primary`] is not held, for
+    /// conversions which need it to be resolved first
+    pub(crate) fn require_resolved(self) -> Result<Modifiers, ConversionError> {
+        if self.primary {
+            return Err(ConversionError::UnresolvedPrimary);
+        }
+        Ok(self)
+    }
+}
@@ -0,0 +1,189 @@
+//! Conversion to and from [`sdl2`] keycodes and key modifiers
+
+use crate::normalize::US_SHIFTED;
+use crate::{ConversionError, Key, KeyName, Modifiers, ShiftedSymbols};
+use sdl2::keyboard::{Keycode, Mod};
+
+/// Keycodes of keys, including symbols which have a keycode of their own
+const KEYCODES: &[(KeyName, Keycode)] = &[
+    (KeyName::A, Keycode::A),
+    (KeyName::B, Keycode::B),
+    (KeyName::C, Keycode::C),
+    (KeyName::D, Keycode::D),
+    (KeyName::E, Keycode::E),
+    (KeyName::F, Keycode::F),
+    (KeyName::G, Keycode::G),
+    (KeyName::H, Keycode::H),
+    (KeyName::I, Keycode::I),
+    (KeyName::J, Keycode::J),
+    (KeyName::K, Keycode::K),
+    (KeyName::L, Keycode::L),
+    (KeyName::M, Keycode::M),
+    (KeyName::N, Keycode::N),
+    (KeyName::O, Keycode::O),
+    (KeyName::P, Keycode::P),
+    (KeyName::Q, Keycode::Q),
+    (KeyName::R, Keycode::R),
+    (KeyName::S, Keycode::S),
+    (KeyName::T, Keycode::T),
+    (KeyName::U, Keycode::U),
+    (KeyName::V, Keycode::V),
+    (KeyName::W, Keycode::W),
+    (KeyName::X, Keycode::X),
+    (KeyName::Y, Keycode::Y),
+    (KeyName::Z, Keycode::Z),
+    (KeyName::Number0, Keycode::NUM_0),
+    (KeyName::Number1, Keycode::NUM_1),
+    (KeyName::Number2, Keycode::NUM_2),
+    (KeyName::Number3, Keycode::NUM_3),
+    (KeyName::Number4, Keycode::NUM_4),
+    (KeyName::Number5, Keycode::NUM_5),
+    (KeyName::Number6, Keycode::NUM_6),
+    (KeyName::Number7, Keycode::NUM_7),
+    (KeyName::Number8, Keycode::NUM_8),
+    (KeyName::Number9, Keycode::NUM_9),
+    (KeyName::Bang, Keycode::EXCLAIM),
+    (KeyName::DoubleQuote, Keycode::QUOTEDBL),
+    (KeyName::Pound, Keycode::HASH),
+    (KeyName::Dollar, Keycode::DOLLAR),
+    (KeyName::Percent, Keycode::PERCENT),
+    (KeyName::Ampersand, Keycode::AMPERSAND),
+    (KeyName::SingleQuote, Keycode::QUOTE),
+    (KeyName::ParenLeft, Keycode::LEFTPAREN),
+    (KeyName::ParenRight, Keycode::RIGHTPAREN),
+    (KeyName::Star, Keycode::ASTERISK),
+    (KeyName::Plus, Keycode::PLUS),
+    (KeyName::Comma, Keycode::COMMA),
+    (KeyName::Dash, Keycode::MINUS),
+    (KeyName::Period, Keycode::PERIOD),
+    (KeyName::ForwardSlash, Keycode::SLASH),
+    (KeyName::Colon, Keycode::COLON),
+    (KeyName::Semicolon, Keycode::SEMICOLON),
+    (KeyName::LessThan, Keycode::LESS),
+    (KeyName::Equals, Keycode::EQUALS),
+    (KeyName::GreaterThan, Keycode::GREATER),
+    (KeyName::Question, Keycode::QUESTION),
+    (KeyName::At, Keycode::AT),
+    (KeyName::BracketLeft, Keycode::LEFTBRACKET),
+    (KeyName::Backslash, Keycode::BACKSLASH),
+    (KeyName::BracketRight, Keycode::RIGHTBRACKET),
+    (KeyName::Carrot, Keycode::CARET),
+    (KeyName::Underscore, Keycode::UNDERSCORE),
+    (KeyName::Backtick, Keycode::BACKQUOTE),
+    (KeyName::Space, Keycode::SPACE),
+    (KeyName::Enter, Keycode::RETURN),
+    (KeyName::Tab, Keycode::TAB),
+    (KeyName::Escape, Keycode::ESCAPE),
+    (KeyName::Backspace, Keycode::BACKSPACE),
+    (KeyName::Delete, Keycode::DELETE),
+    (KeyName::Insert, Keycode::INSERT),
+    (KeyName::Home, Keycode::HOME),
+    (KeyName::End, Keycode::END),
+    (KeyName::PageUp, Keycode::PAGEUP),
+    (KeyName::PageDown, Keycode::PAGEDOWN),
+    (KeyName::Up, Keycode::UP),
+    (KeyName::Down, Keycode::DOWN),
+    (KeyName::Left, Keycode::LEFT),
+    (KeyName::Right, Keycode::RIGHT),
+    (KeyName::F1, Keycode::F1),
+    (KeyName::F2, Keycode::F2),
+    (KeyName::F3, Keycode::F3),
+    (KeyName::F4, Keycode::F4),
+    (KeyName::F5, Keycode::F5),
+    (KeyName::F6, Keycode::F6),
+    (KeyName::F7, Keycode::F7),
+    (KeyName::F8, Keycode::F8),
+    (KeyName::F9, Keycode::F9),
+    (KeyName::F10, Keycode::F10),
+    (KeyName::F11, Keycode::F11),
+    (KeyName::F12, Keycode::F12),
+    (KeyName::CapsLock, Keycode::CAPSLOCK),
+    (KeyName::LeftShift, Keycode::LSHIFT),
+    (KeyName::RightShift, Keycode::RSHIFT),
+    (KeyName::LeftControl, Keycode::LCTRL),
+    (KeyName::RightControl, Keycode::RCTRL),
+    (KeyName::LeftAlt, Keycode::LALT),
+    (KeyName::RightAlt, Keycode::RALT),
+    (KeyName::LeftSuper, Keycode::LGUI),
+    (KeyName::RightSuper, Keycode::RGUI),
+];
+
+/// Key of a key press with the modifiers held, where caps lock, num lock
+/// and AltGr are ignored
+///
+/// SDL reports the unshifted key, so shift and `1` is `!`, as with
+/// [`ShiftedSymbols::ToSymbol`], and shift is not kept for symbols which are
+/// typed with shift on a US keyboard.
+impl TryFrom<(Keycode, Mod)> for Key {
+    type Error = ConversionError;
+    fn try_from((keycode, keymod): (Keycode, Mod)) -> Result<Self, Self::Error> {
+        let name = KeyName::try_from(keycode)?;
+        let mut modifiers = Modifiers::from(keymod);
+        if US_SHIFTED.iter().any(|(_, symbol)| *symbol == name) {
+            modifiers.shift = false;
+        }
+        Ok(Key { modifiers, name }.normalize_with(ShiftedSymbols::ToSymbol))
+    }
+}
+
+/// Shifted symbols such as `!` use their own keycode, and modifiers are the
+/// left modifiers, as with [`Mod::try_from`]
+impl TryFrom<Key> for (Keycode, Mod) {
+    type Error = ConversionError;
+    fn try_from(key: Key) -> Result<Self, Self::Error> {
+        let normalized = key.normalize_with(ShiftedSymbols::ToSymbol);
+        let keymod = normalized.modifiers.try_into()?;
+        let keycode = Keycode::try_from(normalized.name)
+            .map_err(|_| ConversionError::UnsupportedKey(key.to_string()))?;
+        Ok((keycode, keymod))
+    }
+}
+
+impl TryFrom<Keycode> for KeyName {
+    type Error = ConversionError;
+    fn try_from(keycode: Keycode) -> Result<Self, Self::Error> {
+        KEYCODES
+            .iter()
+            .find(|(_, known)| *known == keycode)
+            .map(|(name, _)| *name)
+            .ok_or_else(|| ConversionError::UnsupportedKey(keycode.name()))
+    }
+}
+
+impl TryFrom<KeyName> for Keycode {
+    type Error = ConversionError;
+    fn try_from(name: KeyName) -> Result<Self, Self::Error> {
+        KEYCODES
+            .iter()
+            .find(|(known, _)| *known == name)
+            .map(|(_, keycode)| *keycode)
+            .ok_or_else(|| ConversionError::UnsupportedKey(name.to_string()))
+    }
+}
+
+impl From<Mod> for Modifiers {
+    fn from(keymod: Mod) -> Self {
+        Modifiers {
+            shift: keymod.intersects(Mod::LSHIFTMOD | Mod::RSHIFTMOD),
+            control: keymod.intersects(Mod::LCTRLMOD | Mod::RCTRLMOD),
+            alt: keymod.intersects(Mod::LALTMOD | Mod::RALTMOD),
+            super_key: keymod.intersects(Mod::LGUIMOD | Mod::RGUIMOD),
+            primary: false,
+        }
+    }
+}
+
+/// Left modifiers, where [`Modifiers::primary`] must be resolved first with
+/// [`Modifiers::resolve_platform`]
+impl TryFrom<Modifiers> for Mod {
+    type Error = ConversionError;
+    fn try_from(modifiers: Modifiers) -> Result<Self, Self::Error> {
+        let modifiers = modifiers.require_resolved()?;
+        let mut keymod = Mod::empty();
+        keymod.set(Mod::LSHIFTMOD, modifiers.shift);
+        keymod.set(Mod::LCTRLMOD, modifiers.control);
+        keymod.set(Mod::LALTMOD, modifiers.alt);
+        keymod.set(Mod::LGUIMOD, modifiers.super_key);
+        Ok(keymod)
+    }
+}
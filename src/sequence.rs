@@ -0,0 +1,66 @@
+//! Matching of key sequences as they are typed, such as `gg` or `<C-w>j`
+
+use crate::{Key, Keys};
+
+/// Keys which were typed most recently, for matching sequences of keys
+///
+/// Only the last keys up to the limit are kept, so the limit should be at
+/// least the length of the longest sequence to match.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct KeySequence {
+    keys: Vec<Key>,
+    limit: usize,
+}
+
+impl Default for KeySequence {
+    fn default() -> Self {
+        Self::with_limit(16)
+    }
+}
+
+impl KeySequence {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_limit(limit: usize) -> Self {
+        Self {
+            keys: Vec::new(),
+            limit,
+        }
+    }
+
+    /// Track a key which was typed, forgetting the oldest key if over the
+    /// limit
+    pub fn push(&mut self, key: Key) {
+        self.keys.push(key);
+        if self.keys.len() > self.limit {
+            let extra = self.keys.len() - self.limit;
+            self.keys.drain(..extra);
+        }
+    }
+
+    /// Whether the keys were the last keys typed
+    pub fn ends_with(&self, keys: &Keys) -> bool {
+        !keys.as_slice().is_empty() && self.keys.ends_with(keys.as_slice())
+    }
+
+    /// Whether the keys were the last keys typed, forgetting every key if
+    /// they were, so the same keys do not match again
+    pub fn take(&mut self, keys: &Keys) -> bool {
+        let is_match = self.ends_with(keys);
+        if is_match {
+            self.clear();
+        }
+        is_match
+    }
+
+    /// Keys which are kept, from oldest to newest
+    pub fn as_slice(&self) -> &[Key] {
+        &self.keys
+    }
+
+    pub fn clear(&mut self) {
+        self.keys.clear();
+    }
+}
@@ -0,0 +1,57 @@
+use crate::KeyName;
+
+pub(crate) const MODIFIERS: &[&str] =
+    &["Ctrl", "Control", "Alt", "Shift", "Cmd", "Super", "Primary"];
+
+/// Closest named key, such as `Space` for `Spcae`
+pub(crate) fn key_name(input: &str) -> Option<&'static str> {
+    closest(
+        input,
+        KeyName::ALL.iter().filter_map(|name| name.named_str()),
+    )
+}
+
+/// Closest long modifier name, such as `Control` for `Contrl`
+pub(crate) fn modifier(input: &str) -> Option<&'static str> {
+    closest(input, MODIFIERS.iter().copied())
+}
+
+fn closest(input: &str, candidates: impl Iterator<Item = &'static str>) -> Option<&'static str> {
+    // Allow roughly one typo for every 3 characters
+    let max_distance = (input.chars().count() / 3).max(1);
+    candidates
+        .map(|candidate| (distance(input, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Case-insensitive edit distance, counting swapped adjacent characters as
+/// a single edit
+fn distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().map(|ch| ch.to_ascii_lowercase()).collect();
+    let b: Vec<char> = b.chars().map(|ch| ch.to_ascii_lowercase()).collect();
+
+    // `table[i][j]` is the distance between `a[..i]` and `b[..j]`
+    let mut table = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in table.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in table[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut best = (table[i - 1][j - 1] + cost)
+                .min(table[i - 1][j] + 1)
+                .min(table[i][j - 1] + 1);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(table[i - 2][j - 2] + 1);
+            }
+            table[i][j] = best;
+        }
+    }
+    table[a.len()][b.len()]
+}
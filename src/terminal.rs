@@ -0,0 +1,324 @@
+//! Key input of terminals, which is sent as escape sequences
+//!
+//! Decoders read one key from the start of the input, returning it with the
+//! number of bytes it used, or `None` if the input does not start with a
+//! complete sequence of that kind.
+
+mod csi_u;
+mod kitty;
+mod legacy;
+#[cfg(feature = "pty")]
+mod pty;
+#[cfg(feature = "stdin")]
+mod stdin;
+
+pub use csi_u::{decode_csi_u, encode_csi_u, encode_modify_other_keys};
+pub use kitty::{decode_kitty, encode_kitty};
+pub use legacy::{LegacyDecoder, PASTE_END, PASTE_START};
+#[cfg(feature = "pty")]
+pub use pty::{PtyError, PtySession};
+#[cfg(feature = "stdin")]
+pub use stdin::KeyReader;
+
+use crate::notation::literal_char;
+use crate::{ConversionError, Key, KeyAction, KeyName, Keys, Modifiers};
+use std::io::{self, Write};
+
+/// Key reported by a terminal, which may be released or repeated if the
+/// terminal reports those events
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct TerminalKey {
+    pub key: Key,
+    pub action: KeyAction,
+}
+
+/// Input of a terminal, where text pasted with bracketed paste mode is kept
+/// apart from typed keys
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum InputEvent {
+    Key(Key),
+    /// Pasted text, which should not be read as key bindings
+    Paste(String),
+}
+
+/// How a terminal sends keys which are not plain characters
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum TerminalFlavor {
+    /// Control characters, `ESC` before a key for alt, and xterm sequences
+    /// for keys such as arrows
+    #[default]
+    Legacy,
+    /// Kitty keyboard protocol, with all keys sent as escape sequences, as
+    /// with [`encode_kitty`]
+    Kitty,
+    /// `CSI u` sequences for keys with control, alt or super, as with
+    /// [`encode_csi_u`]
+    CsiU,
+    /// xterm modifyOtherKeys sequences for keys with control, alt or super,
+    /// as with [`encode_modify_other_keys`]
+    ModifyOtherKeys,
+}
+
+impl Keys {
+    /// Bytes which a terminal sends when the keys are typed, such as for
+    /// writing to a pseudo-terminal
+    pub fn to_terminal_bytes(&self, flavor: TerminalFlavor) -> Result<Vec<u8>, ConversionError> {
+        let mut output = Vec::new();
+        for key in &self.0 {
+            output.extend(key.to_terminal_bytes(flavor)?);
+        }
+        Ok(output)
+    }
+}
+
+/// Sending of keys to any writer of terminal input, such as an expectrl or
+/// rexpect session, or the master of a pseudo-terminal
+///
+/// Keys which cannot be sent give an error of [`io::ErrorKind::InvalidInput`],
+/// and nothing is written.
+pub trait WriteKeys: Write {
+    /// Send keys as a [`TerminalFlavor::Legacy`] terminal would send them
+    fn send_keys(&mut self, keys: &Keys) -> io::Result<()> {
+        self.send_keys_with(keys, TerminalFlavor::Legacy)
+    }
+
+    /// Send keys as a terminal of a flavor would send them
+    fn send_keys_with(&mut self, keys: &Keys, flavor: TerminalFlavor) -> io::Result<()> {
+        let bytes = keys
+            .to_terminal_bytes(flavor)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+        self.write_all(&bytes)?;
+        self.flush()
+    }
+}
+
+impl<W: Write + ?Sized> WriteKeys for W {}
+
+impl Key {
+    /// Bytes which a terminal sends when the key is typed
+    ///
+    /// [`Modifiers::primary`] must be resolved first with
+    /// [`Key::resolve_platform`].
+    pub fn to_terminal_bytes(&self, flavor: TerminalFlavor) -> Result<Vec<u8>, ConversionError> {
+        let Modifiers {
+            control,
+            alt,
+            super_key,
+            primary,
+            ..
+        } = self.modifiers;
+        let is_modified = control || alt || super_key || primary;
+        match flavor {
+            TerminalFlavor::Legacy => legacy::encode_legacy(*self),
+            TerminalFlavor::Kitty => Ok(encode_kitty(*self, KeyAction::Press)?.into_bytes()),
+            TerminalFlavor::CsiU if is_modified => Ok(encode_csi_u(*self)?.into_bytes()),
+            TerminalFlavor::ModifyOtherKeys if is_modified => {
+                Ok(encode_modify_other_keys(*self)?.into_bytes())
+            }
+            TerminalFlavor::CsiU | TerminalFlavor::ModifyOtherKeys => legacy::encode_legacy(*self),
+        }
+    }
+
+    /// Key typed by a control character, such as `<C-a>` for `0x01`, where
+    /// `0x00` is `<C-Space>`, `0x08` is `<C-h>`, and `0x7f` is
+    /// [`KeyName::Backspace`]
+    ///
+    /// Tab, carriage return and escape are [`KeyName::Tab`],
+    /// [`KeyName::Enter`] and [`KeyName::Escape`].
+    pub fn from_control_char(byte: u8) -> Option<Key> {
+        let (name, control) = match byte {
+            0x00 => (KeyName::Space, true),
+            b'\t' => (KeyName::Tab, false),
+            b'\r' => (KeyName::Enter, false),
+            0x1b => (KeyName::Escape, false),
+            0x7f => (KeyName::Backspace, false),
+            // `<C-a>` to `<C-z>`
+            0x01..=0x1a => (KeyName::from_char((byte + 0x60) as char).0, true),
+            // `<C-\>`, `<C-]>`, `<C-^>` and `<C-_>`
+            0x1c..=0x1f => (KeyName::from_char((byte + 0x40) as char).0, true),
+            _ => return None,
+        };
+        let modifiers = Modifiers {
+            control,
+            ..Default::default()
+        };
+        Some(Key { modifiers, name })
+    }
+
+    /// Control character typed by a key, as read by
+    /// [`Key::from_control_char`], where `<C-@>` is also `0x00`, `<C-[>` is
+    /// `0x1b`, and `<C-?>` is `0x7f`
+    ///
+    /// Control with a letter ignores shift, as `<C-A>` is the same as `<C-a>`,
+    /// and other modifiers give `None`.
+    pub fn to_control_char(&self) -> Option<u8> {
+        let Modifiers {
+            shift,
+            control,
+            alt,
+            super_key,
+            primary,
+        } = self.modifiers;
+        let shift = shift && self.name.upper_str().is_none();
+        if shift || alt || super_key || primary {
+            return None;
+        }
+        if !control {
+            return match self.name {
+                KeyName::Tab => Some(b'\t'),
+                KeyName::Enter => Some(b'\r'),
+                KeyName::Escape => Some(0x1b),
+                KeyName::Backspace => Some(0x7f),
+                _ => None,
+            };
+        }
+        match self.name {
+            KeyName::Space => Some(0x00),
+            name => match literal_char(name)? {
+                ch @ 'a'..='z' => Some(ch as u8 - 0x60),
+                ch @ ('@' | '[' | '\\' | ']' | '^' | '_') => Some(ch as u8 - 0x40),
+                '?' => Some(0x7f),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// Keys written as `CSI 1 letter`, such as `CSI A` for up
+const LETTERS: &[(KeyName, u8)] = &[
+    (KeyName::Up, b'A'),
+    (KeyName::Down, b'B'),
+    (KeyName::Right, b'C'),
+    (KeyName::Left, b'D'),
+    (KeyName::End, b'F'),
+    (KeyName::Home, b'H'),
+    (KeyName::F1, b'P'),
+    (KeyName::F2, b'Q'),
+    (KeyName::F4, b'S'),
+];
+
+/// Keys written as `CSI number ~`
+const TILDES: &[(KeyName, u32)] = &[
+    (KeyName::Insert, 2),
+    (KeyName::Delete, 3),
+    (KeyName::PageUp, 5),
+    (KeyName::PageDown, 6),
+    (KeyName::Home, 7),
+    (KeyName::End, 8),
+    (KeyName::F1, 11),
+    (KeyName::F2, 12),
+    (KeyName::F3, 13),
+    (KeyName::F4, 14),
+    (KeyName::F5, 15),
+    (KeyName::F6, 17),
+    (KeyName::F7, 18),
+    (KeyName::F8, 19),
+    (KeyName::F9, 20),
+    (KeyName::F10, 21),
+    (KeyName::F11, 23),
+    (KeyName::F12, 24),
+];
+
+/// Parameters and final byte of the `CSI` sequence at the start of the
+/// input, such as `1;5` and `A` for `ESC [ 1 ; 5 A`, and its length
+fn csi(input: &[u8]) -> Option<(&str, u8, usize)> {
+    let rest = input.strip_prefix(b"\x1b[")?;
+    let params_len = rest.iter().position(|byte| !(0x30..=0x3f).contains(byte))?;
+    let final_byte = rest[params_len];
+    if !(0x40..=0x7e).contains(&final_byte) {
+        return None;
+    }
+    let params = std::str::from_utf8(&rest[..params_len]).ok()?;
+    Some((params, final_byte, params_len + 3))
+}
+
+/// Fields of `CSI` parameters, such as `[[97, 65], [2]]` for `97:65;2`,
+/// where empty fields are `None`
+fn fields(params: &str) -> Option<Vec<Vec<Option<u32>>>> {
+    params
+        .split(';')
+        .map(|field| {
+            field
+                .split(':')
+                .map(|part| match part {
+                    "" => Ok(None),
+                    part => part.parse().map(Some),
+                })
+                .collect::<Result<_, _>>()
+                .ok()
+        })
+        .collect()
+}
+
+/// Modifiers of a parameter such as `5` for control, which is one more than
+/// the bits of the modifiers
+///
+/// Caps lock and num lock are ignored, and hyper and meta, which have no
+/// matching modifier, give `None`.
+fn decode_modifiers(param: u32) -> Option<Modifiers> {
+    let bits = param.checked_sub(1)?;
+    if bits & 0b11_0000 != 0 {
+        return None;
+    }
+    Some(Modifiers {
+        shift: bits & 1 != 0,
+        alt: bits & 2 != 0,
+        control: bits & 4 != 0,
+        super_key: bits & 8 != 0,
+        primary: false,
+    })
+}
+
+/// Parameter of modifiers, as read by [`decode_modifiers`]
+fn encode_modifiers(modifiers: Modifiers) -> u32 {
+    let mut bits = 0;
+    for (is_held, bit) in [
+        (modifiers.shift, 1),
+        (modifiers.alt, 2),
+        (modifiers.control, 4),
+        (modifiers.super_key, 8),
+    ] {
+        if is_held {
+            bits |= bit;
+        }
+    }
+    bits + 1
+}
+
+/// Key of a `CSI 1;modifiers letter` or `CSI number;modifiers ~` sequence,
+/// which xterm sends for keys such as arrows and function keys
+fn function_key(number: Option<u32>, final_byte: u8) -> Option<KeyName> {
+    match final_byte {
+        b'~' => {
+            let number = number?;
+            Some(TILDES.iter().find(|(_, known)| *known == number)?.0)
+        }
+        letter if number.unwrap_or(1) == 1 => {
+            Some(LETTERS.iter().find(|(_, known)| *known == letter)?.0)
+        }
+        _ => None,
+    }
+}
+
+/// Number and final byte of the sequence of a key, as read by
+/// [`function_key`]
+fn function_sequence(name: KeyName) -> Option<(u32, u8)> {
+    if let Some((_, letter)) = LETTERS.iter().find(|(known, _)| *known == name) {
+        return Some((1, *letter));
+    }
+    let (_, number) = TILDES.iter().find(|(known, _)| *known == name)?;
+    Some((*number, b'~'))
+}
+
+/// `CSI number;modifiers:event final`, where default parameters at the end
+/// are left out, and the number is left out of sequences such as `CSI A`
+fn encode_csi(number: u32, modifiers: Modifiers, event: Option<u32>, final_byte: u8) -> String {
+    let modifiers = encode_modifiers(modifiers);
+    let final_byte = final_byte as char;
+    match event {
+        Some(event) => format!("\x1b[{};{}:{}{}", number, modifiers, event, final_byte),
+        None if modifiers != 1 => format!("\x1b[{};{}{}", number, modifiers, final_byte),
+        None if final_byte.is_ascii_uppercase() => format!("\x1b[{}", final_byte),
+        None => format!("\x1b[{}{}", number, final_byte),
+    }
+}
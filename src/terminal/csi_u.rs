@@ -0,0 +1,119 @@
+//! fixterms `CSI u` sequences, such as `CSI 97;5u` for `<C-a>`, and xterm
+//! modifyOtherKeys sequences, such as `CSI 27;5;97~`
+//!
+//! See <http://www.leonerd.org.uk/hacks/fixterms/> and
+//! <https://invisible-island.net/xterm/modified-keys.html>
+
+use super::{
+    csi, decode_modifiers, encode_csi, encode_modifiers, fields, function_key, function_sequence,
+};
+use crate::normalize::US_SHIFTED;
+use crate::notation::literal_char;
+use crate::{ConversionError, Key, KeyName, Modifiers, ShiftedSymbols};
+
+/// Keys written as a control character code, rather than a character
+const CODES: &[(KeyName, u32)] = &[
+    (KeyName::Enter, 13),
+    (KeyName::Tab, 9),
+    (KeyName::Escape, 27),
+    (KeyName::Backspace, 127),
+    (KeyName::Backspace, 8),
+];
+
+/// Key of a `CSI u` or modifyOtherKeys sequence at the start of the input,
+/// or of a sequence which xterm sends for keys such as arrows, and the
+/// length of the sequence
+///
+/// Shift is implied by uppercase letters and shifted symbols, so
+/// `CSI 65;5u` and `CSI 27;6;65~` are both `<C-A>`.
+pub fn decode_csi_u(input: &[u8]) -> Option<(Key, usize)> {
+    let (params, final_byte, len) = csi(input)?;
+    let fields = fields(params)?;
+    // Subparameters, such as kitty event types, are not part of either
+    if fields.iter().any(|field| field.len() != 1) {
+        return None;
+    }
+    let field = |index: usize| fields.get(index).and_then(|field| field[0]);
+
+    let key = match (final_byte, fields.len()) {
+        (b'u', 1 | 2) => code_key(field(0)?, field(1).unwrap_or(1))?,
+        (b'~', 3) if field(0) == Some(27) => code_key(field(2)?, field(1)?)?,
+        (final_byte, 1 | 2) => Key {
+            modifiers: decode_modifiers(field(1).unwrap_or(1))?,
+            name: function_key(field(0), final_byte)?,
+        },
+        _ => return None,
+    };
+    Some((key, len))
+}
+
+/// `CSI u` sequence of a key, where a shifted character is written as the
+/// character it types on a US keyboard, so `<C-S-a>` is `CSI 65;5u`
+///
+/// Keys such as arrows are written as xterm sends them, and
+/// [`Modifiers::primary`] must be resolved first with
+/// [`Key::resolve_platform`].
+pub fn encode_csi_u(key: Key) -> Result<String, ConversionError> {
+    key.modifiers.require_resolved()?;
+    if let Some((number, final_byte)) = function_sequence(key.name) {
+        return Ok(encode_csi(number, key.modifiers, None, final_byte));
+    }
+    let (code, mut modifiers) = typed_code(key)?;
+    if literal_char(key.name).is_some() {
+        modifiers.shift = false;
+    }
+    Ok(encode_csi(code, modifiers, None, b'u'))
+}
+
+/// modifyOtherKeys sequence of a key, as xterm sends it, such as
+/// `CSI 27;6;65~` for `<C-S-a>`
+///
+/// Keys such as arrows are written as xterm sends them, and
+/// [`Modifiers::primary`] must be resolved first with
+/// [`Key::resolve_platform`].
+pub fn encode_modify_other_keys(key: Key) -> Result<String, ConversionError> {
+    key.modifiers.require_resolved()?;
+    if let Some((number, final_byte)) = function_sequence(key.name) {
+        return Ok(encode_csi(number, key.modifiers, None, final_byte));
+    }
+    let (code, modifiers) = typed_code(key)?;
+    Ok(format!("\x1b[27;{};{}~", encode_modifiers(modifiers), code))
+}
+
+/// Code of the character or control character a key types, with its
+/// modifiers
+fn typed_code(key: Key) -> Result<(u32, Modifiers), ConversionError> {
+    let normalized = key.normalize_with(ShiftedSymbols::ToSymbol);
+    let ch = match (normalized.modifiers.shift, normalized.name.upper_str()) {
+        (true, Some(upper)) => upper.chars().next(),
+        _ if normalized.name == KeyName::Space => Some(' '),
+        _ => literal_char(normalized.name),
+    };
+    let code = match ch {
+        Some(ch) => ch as u32,
+        None => CODES
+            .iter()
+            .find(|(name, _)| *name == normalized.name)
+            .map(|(_, code)| *code)
+            .ok_or_else(|| ConversionError::UnsupportedKey(key.to_string()))?,
+    };
+    Ok((code, normalized.modifiers))
+}
+
+/// Key of a character or control character code, where shift is implied by
+/// uppercase letters and shifted symbols
+fn code_key(code: u32, modifiers: u32) -> Option<Key> {
+    let mut modifiers = decode_modifiers(modifiers)?;
+    if let Some((name, _)) = CODES.iter().find(|(_, known)| *known == code) {
+        return Some(Key {
+            modifiers,
+            name: *name,
+        });
+    }
+    let (name, shift) = KeyName::from_char(char::from_u32(code)?);
+    modifiers.shift |= shift;
+    if US_SHIFTED.iter().any(|(_, symbol)| *symbol == name) {
+        modifiers.shift = false;
+    }
+    Some(Key { modifiers, name }.normalize_with(ShiftedSymbols::ToSymbol))
+}
@@ -0,0 +1,103 @@
+//! Kitty keyboard protocol, such as `CSI 97;5u` for `<C-a>`
+//!
+//! See <https://sw.kovidgoyal.net/kitty/keyboard-protocol/>
+
+use super::{
+    csi, decode_modifiers, encode_csi, fields, function_key, function_sequence, TerminalKey,
+};
+use crate::notation::literal_char;
+use crate::{ConversionError, Key, KeyAction, KeyName, ShiftedSymbols};
+
+/// Keys written as `CSI code u`, other than characters
+const CODES: &[(KeyName, u32)] = &[
+    (KeyName::Escape, 27),
+    (KeyName::Enter, 13),
+    (KeyName::Tab, 9),
+    (KeyName::Backspace, 127),
+    (KeyName::CapsLock, 57358),
+    (KeyName::LeftShift, 57441),
+    (KeyName::LeftControl, 57442),
+    (KeyName::LeftAlt, 57443),
+    (KeyName::LeftSuper, 57444),
+    (KeyName::RightShift, 57447),
+    (KeyName::RightControl, 57448),
+    (KeyName::RightAlt, 57449),
+    (KeyName::RightSuper, 57450),
+];
+
+/// Key of a kitty sequence at the start of the input, and the length of the
+/// sequence
+///
+/// A shifted key such as `CSI 47:63;2u` is read as the shifted symbol `?`.
+/// Without the shifted key, shifted digits and symbols become the symbol
+/// they type on a US keyboard, as with [`ShiftedSymbols::ToSymbol`].
+pub fn decode_kitty(input: &[u8]) -> Option<(TerminalKey, usize)> {
+    let (params, final_byte, len) = csi(input)?;
+    let fields = fields(params)?;
+    let field = |index: usize, part: usize| -> Option<u32> {
+        fields.get(index)?.get(part).copied().flatten()
+    };
+
+    let code = field(0, 0);
+    let mut modifiers = decode_modifiers(field(1, 0).unwrap_or(1))?;
+    let action = match field(1, 1).unwrap_or(1) {
+        1 => KeyAction::Press,
+        2 => KeyAction::Repeat,
+        3 => KeyAction::Release,
+        _ => return None,
+    };
+
+    let name = match final_byte {
+        b'u' => {
+            let code = code?;
+            match CODES.iter().find(|(_, known)| *known == code) {
+                Some((name, _)) => *name,
+                None => {
+                    let shifted = field(0, 1).filter(|_| modifiers.shift);
+                    let ch = char::from_u32(shifted.unwrap_or(code))?;
+                    if shifted.is_some() {
+                        modifiers.shift = false;
+                    }
+                    let (name, shift) = KeyName::from_char(ch);
+                    modifiers.shift |= shift;
+                    name
+                }
+            }
+        }
+        final_byte => function_key(code, final_byte)?,
+    };
+
+    let key = Key { modifiers, name }.normalize_with(ShiftedSymbols::ToSymbol);
+    Some((TerminalKey { key, action }, len))
+}
+
+/// Kitty sequence of a key, as sent with all keys reported as escape codes
+///
+/// Shifted symbols are written as the unshifted key with shift, as on a US
+/// keyboard, and [`Modifiers::primary`](crate::Modifiers::primary) must be
+/// resolved first with [`Key::resolve_platform`].
+pub fn encode_kitty(key: Key, action: KeyAction) -> Result<String, ConversionError> {
+    key.modifiers.require_resolved()?;
+    let resolved = key.normalize_with(ShiftedSymbols::ToShifted);
+
+    let (code, final_byte) =
+        if let Some((_, code)) = CODES.iter().find(|(name, _)| *name == resolved.name) {
+            (*code, b'u')
+        } else if let Some(sequence) = function_sequence(resolved.name) {
+            sequence
+        } else {
+            let ch = match resolved.name {
+                KeyName::Space => ' ',
+                name => literal_char(name)
+                    .ok_or_else(|| ConversionError::UnsupportedKey(key.to_string()))?,
+            };
+            (ch as u32, b'u')
+        };
+
+    let event = match action {
+        KeyAction::Press => None,
+        KeyAction::Repeat => Some(2),
+        KeyAction::Release => Some(3),
+    };
+    Ok(encode_csi(code, resolved.modifiers, event, final_byte))
+}
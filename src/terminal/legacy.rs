@@ -0,0 +1,284 @@
+//! Classic terminal input, such as `ESC [ A` for up, `0x01` for `<C-a>` and
+//! `ESC a` for `<M-a>`
+
+use super::{
+    csi, decode_csi_u, decode_modifiers, encode_csi, fields, function_sequence, InputEvent,
+};
+use crate::notation::literal_char;
+use crate::{ConversionError, Key, KeyName, Modifiers, ShiftedSymbols};
+
+/// Sequences which are not read as `CSI` sequences, as sent by xterm and the
+/// Linux console
+const SEQUENCES: &[(&[u8], KeyName, bool)] = &[
+    (b"\x1bOA", KeyName::Up, false),
+    (b"\x1bOB", KeyName::Down, false),
+    (b"\x1bOC", KeyName::Right, false),
+    (b"\x1bOD", KeyName::Left, false),
+    (b"\x1bOH", KeyName::Home, false),
+    (b"\x1bOF", KeyName::End, false),
+    (b"\x1bOP", KeyName::F1, false),
+    (b"\x1bOQ", KeyName::F2, false),
+    (b"\x1bOR", KeyName::F3, false),
+    (b"\x1bOS", KeyName::F4, false),
+    (b"\x1b[Z", KeyName::Tab, true),
+    (b"\x1b[1~", KeyName::Home, false),
+    (b"\x1b[4~", KeyName::End, false),
+    (b"\x1b[[A", KeyName::F1, false),
+    (b"\x1b[[B", KeyName::F2, false),
+    (b"\x1b[[C", KeyName::F3, false),
+    (b"\x1b[[D", KeyName::F4, false),
+    (b"\x1b[[E", KeyName::F5, false),
+];
+
+/// Sequence before pasted text, when bracketed paste mode is enabled with
+/// `CSI ? 2004 h`
+pub const PASTE_START: &[u8] = b"\x1b[200~";
+/// Sequence after pasted text
+pub const PASTE_END: &[u8] = b"\x1b[201~";
+
+/// Decoder of classic terminal input, with a table of escape sequences
+///
+/// Sequences in the table are read first, so terminfo quirks can be added
+/// with [`LegacyDecoder::insert`]. Other input is read as a `CSI` sequence
+/// with [`decode_csi_u`], a control character, `ESC` and a key for alt, or a
+/// character.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyDecoder {
+    sequences: Vec<(Box<[u8]>, Key)>,
+}
+
+impl Default for LegacyDecoder {
+    fn default() -> Self {
+        let sequences = SEQUENCES
+            .iter()
+            .map(|(sequence, name, shift)| {
+                let modifiers = Modifiers {
+                    shift: *shift,
+                    ..Default::default()
+                };
+                (
+                    Box::from(*sequence),
+                    Key {
+                        modifiers,
+                        name: *name,
+                    },
+                )
+            })
+            .collect();
+        Self { sequences }
+    }
+}
+
+impl LegacyDecoder {
+    /// Decoder with the sequences of xterm and the Linux console
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decoder with no table, which only reads `CSI` sequences, control
+    /// characters and characters
+    pub fn empty() -> Self {
+        Self {
+            sequences: Vec::new(),
+        }
+    }
+
+    /// Read a sequence as a key, replacing the key of the sequence if it is
+    /// already in the table
+    pub fn insert(&mut self, sequence: &[u8], key: Key) {
+        match self
+            .sequences
+            .iter_mut()
+            .find(|(known, _)| **known == *sequence)
+        {
+            Some((_, known_key)) => *known_key = key,
+            None => self.sequences.push((sequence.into(), key)),
+        }
+    }
+
+    /// Key or pasted text at the start of the input, and the number of bytes
+    /// it used
+    ///
+    /// Pasted text is read between [`PASTE_START`] and [`PASTE_END`], so
+    /// `None` is given until the end of the paste is in the input. Invalid
+    /// UTF-8 in pasted text is replaced.
+    pub fn decode_event(&self, input: &[u8]) -> Option<(InputEvent, usize)> {
+        let Some(rest) = input.strip_prefix(PASTE_START) else {
+            let (key, len) = self.decode(input)?;
+            return Some((InputEvent::Key(key), len));
+        };
+        let end = rest
+            .windows(PASTE_END.len())
+            .position(|window| window == PASTE_END)?;
+        let text = String::from_utf8_lossy(&rest[..end]).into_owned();
+        let len = PASTE_START.len() + end + PASTE_END.len();
+        Some((InputEvent::Paste(text), len))
+    }
+
+    /// Whether the input could be the start of a longer key, such as `ESC [`
+    /// before the rest of `ESC [ A`, or the first bytes of a UTF-8 character,
+    /// so more input should be read before decoding it
+    ///
+    /// A lone `ESC` is incomplete, since it is also the start of a sequence.
+    pub fn is_incomplete(&self, input: &[u8]) -> bool {
+        if self
+            .sequences
+            .iter()
+            .any(|(sequence, _)| sequence.len() > input.len() && sequence.starts_with(input))
+        {
+            return true;
+        }
+        match input {
+            [] => false,
+            [0x1b] | [0x1b, b'O'] => true,
+            // Parameters of a `CSI` sequence, before the final byte
+            [0x1b, b'[', rest @ ..] => rest.iter().all(|byte| (0x20..=0x3f).contains(byte)),
+            [0x1b, rest @ ..] => self.is_incomplete(rest),
+            _ => {
+                let len = input.len().min(4);
+                match std::str::from_utf8(&input[..len]) {
+                    Ok(_) => false,
+                    Err(error) => error.valid_up_to() == 0 && error.error_len().is_none(),
+                }
+            }
+        }
+    }
+
+    /// Key at the start of the input, and the number of bytes it used
+    ///
+    /// The input is taken to be complete, so a lone `ESC` is
+    /// [`KeyName::Escape`]. Empty input and invalid UTF-8 give `None`.
+    pub fn decode(&self, input: &[u8]) -> Option<(Key, usize)> {
+        // The longest sequence, so quirks can extend shorter ones
+        let sequence = self
+            .sequences
+            .iter()
+            .filter(|(sequence, _)| input.starts_with(sequence))
+            .max_by_key(|(sequence, _)| sequence.len());
+        if let Some((sequence, key)) = sequence {
+            return Some((*key, sequence.len()));
+        }
+        if let Some(decoded) = decode_csi_u(input).or_else(|| decode_modified_f3(input)) {
+            return Some(decoded);
+        }
+
+        match input {
+            [] => None,
+            // Alt sends `ESC` before the key
+            [0x1b, rest @ ..] if !rest.is_empty() => {
+                let (mut key, len) = self.decode(rest)?;
+                if key.modifiers.alt {
+                    return Some((Key::from_control_char(0x1b)?, 1));
+                }
+                key.modifiers.alt = true;
+                Some((key, len + 1))
+            }
+            [byte, ..] if byte.is_ascii_control() => Some((Key::from_control_char(*byte)?, 1)),
+            _ => {
+                // Up to 4 bytes of a UTF-8 character
+                let len = input.len().min(4);
+                let valid = match std::str::from_utf8(&input[..len]) {
+                    Ok(valid) => valid,
+                    Err(error) => std::str::from_utf8(&input[..error.valid_up_to()]).ok()?,
+                };
+                let ch = valid.chars().next()?;
+                let (name, shift) = KeyName::from_char(ch);
+                let modifiers = Modifiers {
+                    shift,
+                    ..Default::default()
+                };
+                Some((Key { modifiers, name }, ch.len_utf8()))
+            }
+        }
+    }
+}
+
+/// F3 with modifiers, which xterm sends as `CSI 1;modifiers R`
+///
+/// `R` is not in the table of letters, as `CSI row;column R` is also a
+/// cursor position report, so only sequences with modifiers are read.
+fn decode_modified_f3(input: &[u8]) -> Option<(Key, usize)> {
+    let (params, final_byte, len) = csi(input)?;
+    let fields = fields(params)?;
+    let [number, modifiers] = fields.as_slice() else {
+        return None;
+    };
+    if final_byte != b'R' || *number != [Some(1)] {
+        return None;
+    }
+    let modifiers = decode_modifiers((*modifiers.first()?)?)?;
+    if modifiers == Modifiers::default() {
+        return None;
+    }
+    let name = KeyName::F3;
+    Some((Key { modifiers, name }, len))
+}
+
+/// Bytes which xterm sends for a key, with `ESC` before the key for alt
+///
+/// Keys such as `<C-1>`, which have no control character, and super, other
+/// than with keys such as arrows, cannot be sent, and
+/// [`Modifiers::primary`] must be resolved first.
+pub(super) fn encode_legacy(key: Key) -> Result<Vec<u8>, ConversionError> {
+    let unsupported = || ConversionError::UnsupportedKey(key.to_string());
+    key.modifiers.require_resolved()?;
+    let resolved = key.normalize_with(ShiftedSymbols::ToSymbol);
+    let Modifiers {
+        shift,
+        control,
+        alt,
+        super_key,
+        ..
+    } = resolved.modifiers;
+
+    if resolved.modifiers == Modifiers::default() {
+        // `ESC O P`, rather than `CSI P`
+        if let Some(letter) = [KeyName::F1, KeyName::F2, KeyName::F3, KeyName::F4]
+            .iter()
+            .position(|name| *name == resolved.name)
+        {
+            return Ok(vec![0x1b, b'O', b'P' + letter as u8]);
+        }
+    }
+    // `CSI 1;modifiers R`, as with F1, F2 and F4, rather than `CSI 13~`
+    if resolved.name == KeyName::F3 {
+        return Ok(encode_csi(1, resolved.modifiers, None, b'R').into_bytes());
+    }
+    if let Some((number, final_byte)) = function_sequence(resolved.name) {
+        return Ok(encode_csi(number, resolved.modifiers, None, final_byte).into_bytes());
+    }
+    if resolved.name == KeyName::Tab && shift && !(control || alt || super_key) {
+        return Ok(b"\x1b[Z".to_vec());
+    }
+    if super_key {
+        return Err(unsupported());
+    }
+
+    let mut output = Vec::new();
+    if alt {
+        output.push(0x1b);
+    }
+    let plain = Key {
+        modifiers: Modifiers {
+            alt: false,
+            ..resolved.modifiers
+        },
+        name: resolved.name,
+    };
+    if let Some(byte) = plain.to_control_char() {
+        output.push(byte);
+        return Ok(output);
+    }
+    let ch = match (control, shift, resolved.name) {
+        (true, _, _) => return Err(unsupported()),
+        (false, false, KeyName::Space) => ' ',
+        (false, true, name) => name
+            .upper_str()
+            .and_then(|upper| upper.chars().next())
+            .ok_or_else(unsupported)?,
+        (false, false, name) => literal_char(name).ok_or_else(unsupported)?,
+    };
+    let mut buffer = [0; 4];
+    output.extend_from_slice(ch.encode_utf8(&mut buffer).as_bytes());
+    Ok(output)
+}
@@ -0,0 +1,145 @@
+//! Commands run in a pseudo-terminal, which are sent keys as a terminal would
+//! send them, such as for end-to-end tests of terminal applications
+
+use super::TerminalFlavor;
+use crate::{parse_keys, ConversionError, Error, Keys};
+use portable_pty::{native_pty_system, Child, CommandBuilder, ExitStatus, MasterPty, PtySize};
+use std::io::{self, Read, Write};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Error of a command in a pseudo-terminal
+#[derive(Debug, thiserror::Error)]
+pub enum PtyError {
+    #[error("Pseudo-terminal failed: {0}")]
+    Pty(String),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    Parse(#[from] Error),
+    #[error(transparent)]
+    Conversion(#[from] ConversionError),
+    #[error("Timed out waiting for output `{0}`")]
+    Timeout(String),
+}
+
+/// Command running in a pseudo-terminal, with its output collected as it is
+/// written
+///
+/// Keys are sent as a [`TerminalFlavor::Legacy`] terminal would send them,
+/// unless another flavor is set with [`PtySession::with_flavor`]. The
+/// command is killed when the session is dropped.
+pub struct PtySession {
+    child: Box<dyn Child + Send + Sync>,
+    writer: Box<dyn Write + Send>,
+    output: Arc<Mutex<Vec<u8>>>,
+    flavor: TerminalFlavor,
+    // Closing the pseudo-terminal would hang up the command
+    _master: Box<dyn MasterPty + Send>,
+}
+
+impl PtySession {
+    /// Run a command in a pseudo-terminal of 24 rows and 80 columns
+    pub fn spawn(command: CommandBuilder) -> Result<Self, PtyError> {
+        Self::spawn_with_size(
+            command,
+            PtySize {
+                rows: 24,
+                cols: 80,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Run a command in a pseudo-terminal of a size
+    pub fn spawn_with_size(command: CommandBuilder, size: PtySize) -> Result<Self, PtyError> {
+        let pair = native_pty_system().openpty(size).map_err(pty_error)?;
+        let child = pair.slave.spawn_command(command).map_err(pty_error)?;
+        // The reader only ends once no process has the terminal open
+        drop(pair.slave);
+
+        let mut reader = pair.master.try_clone_reader().map_err(pty_error)?;
+        let writer = pair.master.take_writer().map_err(pty_error)?;
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let collected = Arc::clone(&output);
+        std::thread::spawn(move || {
+            let mut bytes = [0; 1024];
+            loop {
+                match reader.read(&mut bytes) {
+                    Ok(0) => break,
+                    Ok(len) => collected.lock().unwrap().extend_from_slice(&bytes[..len]),
+                    Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(_) => break,
+                }
+            }
+        });
+
+        Ok(Self {
+            child,
+            writer,
+            output,
+            flavor: TerminalFlavor::default(),
+            _master: pair.master,
+        })
+    }
+
+    /// Send keys as a terminal of a flavor would send them
+    pub fn with_flavor(mut self, flavor: TerminalFlavor) -> Self {
+        self.flavor = flavor;
+        self
+    }
+
+    /// Send keys to the command
+    pub fn send_keys(&mut self, keys: &Keys) -> Result<(), PtyError> {
+        self.writer
+            .write_all(&keys.to_terminal_bytes(self.flavor)?)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// Send keys written in notation, such as `:wq<CR>`
+    pub fn send(&mut self, input: &str) -> Result<(), PtyError> {
+        self.send_keys(&parse_keys(input)?)
+    }
+
+    /// Output of the command so far, including escape sequences, where
+    /// invalid UTF-8 is replaced
+    pub fn output(&self) -> String {
+        String::from_utf8_lossy(&self.output.lock().unwrap()).into_owned()
+    }
+
+    /// Wait until the output contains some text
+    pub fn wait_for(&self, text: &str, timeout: Duration) -> Result<(), PtyError> {
+        let start = Instant::now();
+        while !self.output().contains(text) {
+            if start.elapsed() > timeout {
+                return Err(PtyError::Timeout(text.to_string()));
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        Ok(())
+    }
+
+    /// Wait until the command exits
+    pub fn wait(&mut self) -> Result<ExitStatus, PtyError> {
+        Ok(self.child.wait()?)
+    }
+
+    /// Stop the command
+    pub fn kill(&mut self) -> Result<(), PtyError> {
+        Ok(self.child.kill()?)
+    }
+}
+
+impl Drop for PtySession {
+    fn drop(&mut self) {
+        if let Ok(None) = self.child.try_wait() {
+            let _ = self.child.kill();
+        }
+    }
+}
+
+/// Error of `portable_pty`, which is an `anyhow::Error`
+fn pty_error(error: impl std::fmt::Display) -> PtyError {
+    PtyError::Pty(error.to_string())
+}
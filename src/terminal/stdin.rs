@@ -0,0 +1,260 @@
+//! Blocking key input from standard input, with the terminal in raw mode
+
+use super::{InputEvent, LegacyDecoder, PASTE_START};
+use crate::Key;
+use std::io::{self, Read, Stdin, Write};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+/// How long to wait for the rest of a sequence after `ESC`, before reading
+/// it as [`KeyName::Escape`](crate::KeyName::Escape)
+const ESCAPE_TIMEOUT: Duration = Duration::from_millis(50);
+
+/// Reader of keys from standard input, or any other input of terminal bytes
+///
+/// Keys are read with a [`LegacyDecoder`], so kitty sequences are not read.
+/// Bytes which could be the start of a longer key, such as `ESC [` or part
+/// of a UTF-8 character, are kept until the rest is read. Bytes which are
+/// not a key, such as invalid UTF-8, are skipped.
+///
+/// A lone `ESC` is [`KeyName::Escape`] once nothing more is read within
+/// the escape timeout, for standard input, or at the end of the input.
+///
+/// Text pasted in bracketed paste mode is read by [`KeyReader::read_event`],
+/// and skipped when reading keys.
+///
+/// [`KeyName::Escape`]: crate::KeyName::Escape
+#[derive(Debug)]
+pub struct KeyReader<R = Stdin> {
+    source: Source<R>,
+    decoder: LegacyDecoder,
+    buffer: Vec<u8>,
+    /// Whether the buffer is decoded as it is, after a timeout or the end of
+    /// the input
+    is_flushing: bool,
+    is_end: bool,
+    is_raw_mode: bool,
+    is_bracketed_paste: bool,
+}
+
+#[derive(Debug)]
+enum Source<R> {
+    Reader(R),
+    /// Standard input, which is read on a thread so reads can time out
+    Stdin {
+        escape_timeout: Duration,
+    },
+}
+
+impl KeyReader {
+    /// Reader of standard input, which puts the terminal in raw mode until
+    /// it is dropped
+    ///
+    /// Standard input is read on a thread from then on, so it should only
+    /// be read by key readers.
+    pub fn new() -> io::Result<Self> {
+        Self::with_decoder(LegacyDecoder::new())
+    }
+
+    /// Reader of standard input with a decoder, such as one with terminfo
+    /// quirks
+    pub fn with_decoder(decoder: LegacyDecoder) -> io::Result<Self> {
+        crossterm::terminal::enable_raw_mode()?;
+        let mut reader = Self::from_source(
+            Source::Stdin {
+                escape_timeout: ESCAPE_TIMEOUT,
+            },
+            decoder,
+        );
+        reader.is_raw_mode = true;
+        Ok(reader)
+    }
+
+    /// Wait for the rest of a sequence after `ESC` for a time other than the
+    /// default of 50 milliseconds
+    pub fn with_escape_timeout(mut self, timeout: Duration) -> Self {
+        if let Source::Stdin { escape_timeout } = &mut self.source {
+            *escape_timeout = timeout;
+        }
+        self
+    }
+
+    /// Ask the terminal to send pasted text between [`PASTE_START`] and
+    /// [`PASTE_END`](super::PASTE_END), until the reader is dropped
+    pub fn enable_bracketed_paste(&mut self) -> io::Result<()> {
+        let mut stdout = io::stdout();
+        stdout.write_all(b"\x1b[?2004h")?;
+        stdout.flush()?;
+        self.is_bracketed_paste = true;
+        Ok(())
+    }
+}
+
+impl<R: Read> KeyReader<R> {
+    /// Reader of bytes which a terminal would send, which does not change
+    /// the mode of the terminal
+    ///
+    /// Reads cannot time out, so a lone `ESC` is only read as a key once
+    /// more input is read or the input ends.
+    pub fn from_reader(reader: R, decoder: LegacyDecoder) -> Self {
+        Self::from_source(Source::Reader(reader), decoder)
+    }
+
+    fn from_source(source: Source<R>, decoder: LegacyDecoder) -> Self {
+        Self {
+            source,
+            decoder,
+            buffer: Vec::new(),
+            is_flushing: false,
+            is_end: false,
+            is_raw_mode: false,
+            is_bracketed_paste: false,
+        }
+    }
+
+    /// Next key, waiting for input if none is ready, or `None` at the end of
+    /// the input
+    pub fn read_key(&mut self) -> io::Result<Option<Key>> {
+        loop {
+            match self.read_event()? {
+                Some(InputEvent::Key(key)) => return Ok(Some(key)),
+                Some(InputEvent::Paste(_)) => continue,
+                None => return Ok(None),
+            }
+        }
+    }
+
+    /// Next key or pasted text, waiting for input if none is ready, or `None`
+    /// at the end of the input
+    ///
+    /// Pasted text with no end at the end of the input is given as it is.
+    pub fn read_event(&mut self) -> io::Result<Option<InputEvent>> {
+        loop {
+            if let Some(event) = self.decode_buffer() {
+                return Ok(Some(event));
+            }
+            if self.is_end {
+                if self.buffer.is_empty() {
+                    return Ok(None);
+                }
+                let text = String::from_utf8_lossy(&self.buffer[PASTE_START.len()..]);
+                let event = InputEvent::Paste(text.into_owned());
+                self.buffer.clear();
+                return Ok(Some(event));
+            }
+
+            let can_time_out = self.decoder.is_incomplete(&self.buffer);
+            match self.source.read(can_time_out)? {
+                // Timed out
+                None => self.is_flushing = true,
+                Some(bytes) if bytes.is_empty() => {
+                    self.is_end = true;
+                    self.is_flushing = true;
+                }
+                Some(bytes) => {
+                    self.buffer.extend_from_slice(&bytes);
+                    self.is_flushing = false;
+                }
+            }
+        }
+    }
+
+    /// Next event in the buffer, leaving bytes which could be the start of a
+    /// longer key or an unfinished paste
+    fn decode_buffer(&mut self) -> Option<InputEvent> {
+        while !self.buffer.is_empty() {
+            if !self.is_flushing && self.decoder.is_incomplete(&self.buffer) {
+                return None;
+            }
+            match self.decoder.decode_event(&self.buffer) {
+                Some((event, len)) => {
+                    self.buffer.drain(..len);
+                    return Some(event);
+                }
+                // Wait for the end of the paste
+                None if self.buffer.starts_with(PASTE_START) => return None,
+                None => {
+                    self.buffer.remove(0);
+                }
+            }
+        }
+        None
+    }
+}
+
+impl<R: Read> Source<R> {
+    /// Bytes which are ready, which are empty at the end of the input, or
+    /// `None` if standard input timed out
+    fn read(&mut self, can_time_out: bool) -> io::Result<Option<Vec<u8>>> {
+        match self {
+            Source::Reader(reader) => {
+                let mut bytes = [0; 64];
+                loop {
+                    match reader.read(&mut bytes) {
+                        Ok(len) => return Ok(Some(bytes[..len].to_vec())),
+                        Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                        Err(error) => return Err(error),
+                    }
+                }
+            }
+            Source::Stdin { escape_timeout } => {
+                let receiver = stdin_bytes().lock().unwrap();
+                let received = if can_time_out {
+                    receiver.recv_timeout(*escape_timeout)
+                } else {
+                    receiver.recv().map_err(|_| RecvTimeoutError::Disconnected)
+                };
+                match received {
+                    Ok(bytes) => bytes.map(Some),
+                    Err(RecvTimeoutError::Timeout) => Ok(None),
+                    Err(RecvTimeoutError::Disconnected) => Ok(Some(Vec::new())),
+                }
+            }
+        }
+    }
+}
+
+/// Bytes of standard input, read on a thread which is started once and
+/// stops at the end of the input
+fn stdin_bytes() -> &'static Mutex<Receiver<io::Result<Vec<u8>>>> {
+    static BYTES: OnceLock<Mutex<Receiver<io::Result<Vec<u8>>>>> = OnceLock::new();
+    BYTES.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            let mut stdin = io::stdin();
+            let mut bytes = [0; 64];
+            loop {
+                let result = match stdin.read(&mut bytes) {
+                    Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                    result => result.map(|len| bytes[..len].to_vec()),
+                };
+                let is_end = !matches!(&result, Ok(bytes) if !bytes.is_empty());
+                if sender.send(result).is_err() || is_end {
+                    break;
+                }
+            }
+        });
+        Mutex::new(receiver)
+    })
+}
+
+impl<R: Read> Iterator for KeyReader<R> {
+    type Item = io::Result<Key>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_key().transpose()
+    }
+}
+
+impl<R> Drop for KeyReader<R> {
+    fn drop(&mut self) {
+        if self.is_bracketed_paste {
+            let mut stdout = io::stdout();
+            let _ = stdout.write_all(b"\x1b[?2004l");
+            let _ = stdout.flush();
+        }
+        if self.is_raw_mode {
+            let _ = crossterm::terminal::disable_raw_mode();
+        }
+    }
+}
@@ -2,6 +2,8 @@ use super::*;
 
 #[test]
 fn split_keys_works() {
+    let options = ParserOptions::default();
+    let split_keys = |input| split_keys(input, &options).collect::<Result<Vec<_>, _>>();
     assert_eq!(split_keys(""), Ok(vec![]));
     assert_eq!(split_keys("a"), Ok(vec!["a"]));
     assert_eq!(split_keys("ab"), Ok(vec!["a", "b"]));
@@ -13,16 +15,47 @@ fn split_keys_works() {
     assert_eq!(split_keys("\\<"), Ok(vec!["\\<"]));
     assert_eq!(split_keys("a\\<b"), Ok(vec!["a", "\\<", "b"]));
 
-    assert_eq!(split_keys("<a"), Err(Error::UnexpectedEnd));
-    assert_eq!(split_keys("a<C-a><"), Err(Error::UnexpectedEnd));
-    assert_eq!(split_keys("<C-<a>"), Err(Error::UnexpectedGroupOpen));
-    assert_eq!(split_keys("C-<<a>"), Err(Error::UnexpectedGroupOpen));
-    assert_eq!(split_keys("a>"), Err(Error::UnexpectedGroupClose));
-    assert_eq!(split_keys("<C-a>>"), Err(Error::UnexpectedGroupClose));
+    assert_eq!(
+        split_keys("<a"),
+        Err(BorrowedError::new(ErrorKind::UnexpectedEnd, "<a"))
+    );
+    assert_eq!(
+        split_keys("a<C-a><"),
+        Err(BorrowedError::new(ErrorKind::UnexpectedEnd, "<"))
+    );
+    assert_eq!(
+        split_keys("<C-<a>"),
+        Err(BorrowedError::new(ErrorKind::UnexpectedGroupOpen, "<"))
+    );
+    assert_eq!(
+        split_keys("C-<<a>"),
+        Err(BorrowedError::new(ErrorKind::UnexpectedGroupOpen, "<"))
+    );
+    assert_eq!(
+        split_keys("a>"),
+        Err(BorrowedError::new(ErrorKind::UnexpectedGroupClose, ">"))
+    );
+    assert_eq!(
+        split_keys("<C-a>>"),
+        Err(BorrowedError::new(ErrorKind::UnexpectedGroupClose, ">"))
+    );
+    assert_eq!(
+        split_keys("a\\"),
+        Err(BorrowedError::new(ErrorKind::TrailingEscape, "\\"))
+    );
+    assert_eq!(
+        split_keys("\\"),
+        Err(BorrowedError::new(ErrorKind::TrailingEscape, "\\"))
+    );
+    assert_eq!(
+        split_keys("<C-\\"),
+        Err(BorrowedError::new(ErrorKind::UnexpectedEnd, "<C-\\"))
+    );
 }
 
 #[test]
 fn split_modifiers_works() {
+    let split_modifiers = |input| split_modifiers(input).collect::<Result<Vec<_>, _>>();
     assert_eq!(split_modifiers("a"), Ok(vec!["a"]));
     assert_eq!(split_modifiers("ab"), Ok(vec!["ab"]));
     assert_eq!(split_modifiers("C-a"), Ok(vec!["C", "a"]));
@@ -35,7 +68,14 @@ fn split_modifiers_works() {
     assert_eq!(split_modifiers("--a"), Ok(vec!["a"]));
     assert_eq!(split_modifiers("C--"), Ok(vec!["C"]));
 
-    // assert_eq!(split_modifiers("C-\\"), Err(Error::));
+    assert_eq!(
+        split_modifiers("C-\\"),
+        Err(BorrowedError::new(ErrorKind::TrailingEscape, "\\"))
+    );
+    assert_eq!(
+        split_modifiers("\\"),
+        Err(BorrowedError::new(ErrorKind::TrailingEscape, "\\"))
+    );
 }
 
 #[test]
@@ -387,3 +427,3201 @@ fn parse_key_works() {
         Err(Error::IncompleteGroup("--a".to_string()))
     );
 }
+
+#[test]
+fn parse_key_modifier_spellings_works() {
+    assert_eq!(
+        parse_key("<Ctrl-a>"),
+        Ok(Key {
+            name: KeyName::A,
+            modifiers: Modifiers {
+                control: true,
+                ..Default::default()
+            },
+        })
+    );
+    assert_eq!(parse_key("<Control-a>"), parse_key("<C-a>"));
+    assert_eq!(parse_key("<ctrl-a>"), parse_key("<C-a>"));
+    assert_eq!(parse_key("<CTRL-a>"), parse_key("<C-a>"));
+    assert_eq!(parse_key("<Alt-a>"), parse_key("<M-a>"));
+    assert_eq!(parse_key("<Ctrl-Alt-a>"), parse_key("<C-M-a>"));
+    assert_eq!(
+        parse_key("<Shift-a>"),
+        Ok(Key {
+            name: KeyName::A,
+            modifiers: Modifiers {
+                shift: true,
+                ..Default::default()
+            },
+        })
+    );
+    assert_eq!(parse_key("<S-a>"), parse_key("<Shift-a>"));
+    assert_eq!(parse_key("<Shift-A>"), parse_key("<Shift-a>"));
+    assert_eq!(
+        parse_key("<Cmd-a>"),
+        Ok(Key {
+            name: KeyName::A,
+            modifiers: Modifiers {
+                super_key: true,
+                ..Default::default()
+            },
+        })
+    );
+    assert_eq!(parse_key("<Super-a>"), parse_key("<Cmd-a>"));
+    assert_eq!(parse_key("<D-a>"), parse_key("<Cmd-a>"));
+
+    assert_eq!(parse_key("<c-a>"), parse_key("<C-a>"));
+    assert_eq!(parse_key("<m-x>"), parse_key("<M-x>"));
+    assert_eq!(parse_key("<c-m-A>"), parse_key("<C-M-A>"));
+    assert_eq!(parse_key("<s-a>"), parse_key("<S-a>"));
+    assert_eq!(parse_key("<d-a>"), parse_key("<D-a>"));
+
+    assert_eq!(
+        parse_key("<x-a>"),
+        Err(Error::InvalidKeyModifier("x".to_string()))
+    );
+    assert_eq!(
+        parse_key("<Ctl-a>"),
+        Err(Error::InvalidKeyModifier("Ctl".to_string()))
+    );
+}
+
+#[test]
+fn parse_key_char_works() {
+    assert_eq!(
+        parse_key("é"),
+        Ok(Key {
+            name: KeyName::Char('é'),
+            modifiers: Modifiers::default(),
+        })
+    );
+    assert_eq!(
+        parse_key("<C-ñ>"),
+        Ok(Key {
+            name: KeyName::Char('ñ'),
+            modifiers: Modifiers {
+                control: true,
+                ..Default::default()
+            },
+        })
+    );
+    assert_eq!(
+        parse_keys("aßb"),
+        Ok(Keys(vec![
+            Key {
+                name: KeyName::A,
+                modifiers: Modifiers::default(),
+            },
+            Key {
+                name: KeyName::Char('ß'),
+                modifiers: Modifiers::default(),
+            },
+            Key {
+                name: KeyName::B,
+                modifiers: Modifiers::default(),
+            },
+        ]))
+    );
+
+    // ASCII characters are never captured as `Char`
+    assert_eq!(
+        parse_key("\t"),
+        Err(Error::InvalidKeyName("\t".to_string()))
+    );
+    assert_eq!(
+        parse_key("éé"),
+        Err(Error::InvalidKeyName("éé".to_string()))
+    );
+}
+
+#[test]
+fn display_round_trip_works() {
+    for input in [
+        "",
+        "a",
+        "A",
+        "abc",
+        "<C-a>",
+        "<C-A>",
+        "<C-M-a>",
+        "<M-D-B>",
+        "<S-!>",
+        "<C-S-1>",
+        "\\<\\>\\-",
+        "<C-\\->",
+        "é",
+        "<C-ñ>",
+        "a<C-a>ß",
+    ] {
+        let keys = parse_keys(input).unwrap();
+        assert_eq!(keys.to_string(), input);
+        assert_eq!(parse_keys(&keys.to_string()), Ok(keys));
+    }
+
+    assert_eq!(parse_keys("<S-a>").unwrap().to_string(), "A");
+    assert_eq!(parse_keys("<M-C-a>").unwrap().to_string(), "<C-M-a>");
+    assert_eq!(parse_keys("<Ctrl-Shift-a>").unwrap().to_string(), "<C-A>");
+}
+
+#[test]
+fn parse_key_char_code_works() {
+    assert_eq!(parse_key("<Char-0x41>"), parse_key("A"));
+    assert_eq!(parse_key("<Char-65>"), parse_key("A"));
+    assert_eq!(parse_key("<char-97>"), parse_key("a"));
+    assert_eq!(parse_key("<C-Char-0x61>"), parse_key("<C-a>"));
+    assert_eq!(parse_key("<Char-0x2d>"), parse_key("\\-"));
+    assert_eq!(parse_key("<Char-60>"), parse_key("\\<"));
+    assert_eq!(parse_key("<Char-0xe9>"), parse_key("é"));
+    assert_eq!(
+        parse_key("<Char-32>"),
+        Ok(Key {
+            name: KeyName::Space,
+            modifiers: Modifiers::default(),
+        })
+    );
+    assert_eq!(
+        parse_key("<Char-0x01>"),
+        Ok(Key {
+            name: KeyName::Char('\u{1}'),
+            modifiers: Modifiers::default(),
+        })
+    );
+
+    assert_eq!(
+        parse_key("<Char-0xzz>"),
+        Err(Error::InvalidCharCode("0xzz".to_string()))
+    );
+    assert_eq!(
+        parse_key("<Char-0xd800>"),
+        Err(Error::InvalidCharCode("0xd800".to_string()))
+    );
+    assert_eq!(
+        parse_key("<Char>"),
+        Err(Error::IncompleteGroup("Char".to_string()))
+    );
+
+    assert_eq!(KeyName::Space.to_string(), "Space");
+    assert_eq!(parse_keys("<Char-32>").unwrap().to_string(), "<Space>");
+    assert_eq!(parse_keys("<C-Char-32>").unwrap().to_string(), "<C-Space>");
+    assert_eq!(parse_keys("<Char-9>").unwrap().to_string(), "<Char-0x9>");
+    assert_eq!(parse_keys("<Char-0x41>").unwrap().to_string(), "A");
+    for input in ["<Space>", "<M-Space>", "<Char-0x1b>", "a<Char-0x3000>b"] {
+        assert_eq!(parse_keys(input).unwrap().to_string(), input);
+    }
+}
+
+#[test]
+fn parse_key_named_works() {
+    assert_eq!(parse_key("<lt>"), parse_key("\\<"));
+    assert_eq!(parse_key("<gt>"), parse_key("\\>"));
+    assert_eq!(parse_key("<Minus>"), parse_key("\\-"));
+    assert_eq!(parse_key("<Bslash>"), parse_key("\\\\"));
+    assert_eq!(parse_key("<Bar>"), parse_key("|"));
+    assert_eq!(parse_key("<LT>"), parse_key("\\<"));
+    assert_eq!(parse_key("<C-lt>"), parse_key("<C-\\<>"));
+    assert_eq!(parse_key("<C-M-Bar>"), parse_key("<C-M-|>"));
+    assert_eq!(parse_key("<esc>"), parse_key("<Esc>"));
+    assert_eq!(
+        parse_keys("<C-Tab><S-F12><CR>").unwrap().to_string(),
+        "<C-Tab><S-F12><CR>"
+    );
+    assert_eq!(
+        parse_key("<Space>"),
+        Ok(Key {
+            name: KeyName::Space,
+            modifiers: Modifiers::default(),
+        })
+    );
+    assert_eq!(
+        parse_keys("a<lt>b"),
+        Ok(Keys(vec![
+            Key {
+                name: KeyName::A,
+                modifiers: Modifiers::default(),
+            },
+            Key {
+                name: KeyName::LessThan,
+                modifiers: Modifiers::default(),
+            },
+            Key {
+                name: KeyName::B,
+                modifiers: Modifiers::default(),
+            },
+        ]))
+    );
+
+    assert_eq!(
+        parse_key("<Pipe>"),
+        Err(Error::IncompleteGroup("Pipe".to_string()))
+    );
+    assert_eq!(
+        parse_key("<C-Pipe>"),
+        Err(Error::InvalidKeyName("Pipe".to_string()))
+    );
+
+    let keys = parse_keys("a\\<|<C-\\-><Space>").unwrap();
+    assert_eq!(keys.to_string(), "a\\<|<C-\\-><Space>");
+    assert_eq!(format!("{:#}", keys), "a<lt><Bar><C-Minus><Space>");
+    assert_eq!(parse_keys(&format!("{:#}", keys)), Ok(keys));
+}
+
+#[test]
+fn parse_key_unknown_works() {
+    assert_eq!(
+        parse_key("<Unknown:123>"),
+        Ok(Key {
+            name: KeyName::Unknown(123),
+            modifiers: Modifiers::default(),
+        })
+    );
+    assert_eq!(
+        parse_key("<C-unknown:7>"),
+        Ok(Key {
+            name: KeyName::Unknown(7),
+            modifiers: Modifiers {
+                control: true,
+                ..Default::default()
+            },
+        })
+    );
+    assert_eq!(
+        parse_key("<Unknown:x>"),
+        Err(Error::IncompleteGroup("Unknown:x".to_string()))
+    );
+
+    for input in ["<Unknown:123>", "<C-S-Unknown:0>"] {
+        assert_eq!(parse_keys(input).unwrap().to_string(), input);
+    }
+}
+
+#[test]
+fn parse_keys_whitespace_separated_works() {
+    let options = ParserOptions {
+        whitespace_separated: true,
+        ..Default::default()
+    };
+    let split_keys = |input| split_keys(input, &options).collect::<Result<Vec<_>, _>>();
+
+    assert_eq!(split_keys(" <C-x> <C-s> "), Ok(vec!["<C-x>", "<C-s>"]));
+    assert_eq!(split_keys("a\tb\n c"), Ok(vec!["a", "b", "c"]));
+    assert_eq!(split_keys("ab c"), Ok(vec!["a", "b", "c"]));
+
+    assert_eq!(
+        parse_keys_with("<C-x> <C-s>", &options),
+        parse_keys("<C-x><C-s>")
+    );
+    assert_eq!(
+        parse_keys_with("<C-w> v <C-w> l", &options),
+        parse_keys("<C-w>v<C-w>l")
+    );
+    assert_eq!(
+        parse_keys_with("a <Space> b", &options),
+        parse_keys("a<Space>b")
+    );
+    assert_eq!(parse_keys_with("   ", &options), Ok(Keys(vec![])));
+
+    assert_eq!(
+        parse_keys_with("<C- x>", &options),
+        Err(Error::InvalidKeyName(" x".to_string()))
+    );
+    assert_eq!(
+        parse_keys("a b"),
+        Err(Error::InvalidKeyName(" ".to_string()))
+    );
+}
+
+#[test]
+fn parse_keys_strict_works() {
+    let options = ParserOptions::strict();
+
+    assert_eq!(parse_keys_with("a<C-a>", &options), parse_keys("a<C-a>"));
+    assert_eq!(parse_keys_with("<Char-0xe9>", &options), parse_keys("é"));
+
+    assert_eq!(
+        parse_key_with("<c-a>", &options),
+        Err(Error::InvalidKeyModifier("c".to_string()))
+    );
+    assert_eq!(
+        parse_key_with("<Ctrl-a>", &options),
+        Err(Error::InvalidKeyModifier("Ctrl".to_string()))
+    );
+    assert_eq!(
+        parse_key_with("é", &options),
+        Err(Error::InvalidKeyName("é".to_string()))
+    );
+
+    let options = ParserOptions {
+        case_insensitive_modifiers: false,
+        ..Default::default()
+    };
+    assert_eq!(parse_key_with("<Ctrl-a>", &options), parse_key("<C-a>"));
+    assert_eq!(
+        parse_key_with("<CTRL-a>", &options),
+        Err(Error::InvalidKeyModifier("CTRL".to_string()))
+    );
+
+    let options = ParserOptions {
+        long_modifiers: false,
+        ..Default::default()
+    };
+    assert_eq!(parse_key_with("<c-a>", &options), parse_key("<C-a>"));
+    assert_eq!(
+        parse_key_with("<Alt-a>", &options),
+        Err(Error::InvalidKeyModifier("Alt".to_string()))
+    );
+}
+
+#[test]
+fn parse_keys_lenient_works() {
+    assert_eq!(
+        parse_keys_lenient("a<C-a>"),
+        (parse_keys("a<C-a>").unwrap(), vec![])
+    );
+    assert_eq!(
+        parse_keys_lenient("a<X-a>b"),
+        (
+            parse_keys("ab").unwrap(),
+            vec![SpannedError::new(
+                Error::InvalidKeyModifier("X".to_string()),
+                2..3
+            )]
+        )
+    );
+    assert_eq!(
+        parse_keys_lenient("a>b"),
+        (
+            parse_keys("ab").unwrap(),
+            vec![SpannedError::new(Error::UnexpectedGroupClose, 1..2)]
+        )
+    );
+    assert_eq!(
+        parse_keys_lenient("<C-<M-a>b"),
+        (
+            parse_keys("<M-a>b").unwrap(),
+            vec![SpannedError::new(Error::UnexpectedGroupOpen, 3..4)]
+        )
+    );
+    assert_eq!(
+        parse_keys_lenient("a<C-b"),
+        (
+            parse_keys("a").unwrap(),
+            vec![SpannedError::new(Error::UnexpectedEnd, 1..5)]
+        )
+    );
+    assert_eq!(
+        parse_keys_lenient("<C>a\t<Foo>>"),
+        (
+            parse_keys("a").unwrap(),
+            vec![
+                SpannedError::new(Error::IncompleteGroup("C".to_string()), 1..2),
+                SpannedError::new(Error::InvalidKeyName("\t".to_string()), 4..5),
+                SpannedError::new(Error::IncompleteGroup("Foo".to_string()), 6..9),
+                SpannedError::new(Error::UnexpectedGroupClose, 10..11),
+            ]
+        )
+    );
+}
+
+#[test]
+fn parse_keys_all_errors_works() {
+    assert_eq!(
+        parse_keys_all_errors("a<C-a>"),
+        Ok(parse_keys("a<C-a>").unwrap())
+    );
+    assert_eq!(
+        parse_keys_all_errors("a<X-a>b>"),
+        Err(vec![
+            SpannedError::new(Error::InvalidKeyModifier("X".to_string()), 2..3),
+            SpannedError::new(Error::UnexpectedGroupClose, 7..8),
+        ])
+    );
+    assert_eq!(
+        parse_keys_all_errors("<C-"),
+        Err(vec![SpannedError::new(Error::UnexpectedEnd, 0..3)])
+    );
+}
+
+#[test]
+fn parse_keys_spanned_works() {
+    assert_eq!(
+        parse_keys_spanned("a<C-a>"),
+        Ok(parse_keys("a<C-a>").unwrap())
+    );
+    assert_eq!(
+        parse_keys_spanned("ab<C-Foo>"),
+        Err(SpannedError::new(
+            Error::InvalidKeyName("Foo".to_string()),
+            5..8
+        ))
+    );
+    assert_eq!(
+        parse_keys_spanned("é<C-Char-0xzz>"),
+        Err(SpannedError::new(
+            Error::InvalidCharCode("0xzz".to_string()),
+            10..14
+        ))
+    );
+    // The first error in the input is returned
+    assert_eq!(
+        parse_keys_spanned("<C-Foo>>"),
+        Err(SpannedError::new(
+            Error::InvalidKeyName("Foo".to_string()),
+            3..6
+        ))
+    );
+    assert_eq!(
+        parse_keys_spanned("<C-a>>"),
+        Err(SpannedError::new(Error::UnexpectedGroupClose, 5..6))
+    );
+    assert_eq!(
+        parse_keys_spanned("a<x-b>").unwrap_err().to_string(),
+        "Invalid key modifier `x` (at 2..3)"
+    );
+}
+
+#[cfg(feature = "conformance")]
+#[test]
+fn conformance_works() {
+    assert_eq!(conformance::check_notation(), vec![]);
+
+    let report = conformance::check_backend(|key| Some(key.to_string()), |s| parse_key(&s).ok());
+    assert_eq!(report, conformance::Report::default());
+
+    // Backend which cannot represent control, and only knows the letter `a`
+    let report = conformance::check_backend(
+        |key| (!key.modifiers.control).then_some(key),
+        |key| {
+            Some(Key {
+                name: KeyName::A,
+                ..key
+            })
+        },
+    );
+    let cases = conformance::cases();
+    assert_eq!(report.unsupported.len(), cases.len() / 3);
+    assert_eq!(report.failures.len(), cases.len() / 3 * 2 - 2,);
+}
+
+#[test]
+fn error_suggestion_works() {
+    let error = parse_key("<Contrl-a>").unwrap_err();
+    assert_eq!(error.suggestion(), Some("Control"));
+    assert_eq!(
+        error.to_string(),
+        "Invalid key modifier `Contrl` (did you mean `Control`?)"
+    );
+    assert_eq!(
+        parse_key("<sift-a>").unwrap_err().suggestion(),
+        Some("Shift")
+    );
+    assert_eq!(
+        parse_key("<C-Spcae>").unwrap_err().suggestion(),
+        Some("Space")
+    );
+    assert_eq!(
+        parse_key("<Minsu>").unwrap_err().suggestion(),
+        Some("Minus")
+    );
+    assert_eq!(
+        parse_key("<bslsh>").unwrap_err().to_string(),
+        "Modifier group must be include modifer and key name, not `bslsh` (did you mean `Bslash`?)"
+    );
+
+    assert_eq!(parse_key("<X-a>").unwrap_err().suggestion(), None);
+    assert_eq!(
+        parse_key("<C-Foo>").unwrap_err().to_string(),
+        "Invalid key name `Foo`"
+    );
+}
+
+#[test]
+fn hash_works() {
+    use std::collections::HashMap;
+
+    /// Records every value written, rather than hashing
+    #[derive(Default)]
+    struct Recorder(Vec<u64>);
+    impl Hasher for Recorder {
+        fn finish(&self) -> u64 {
+            0
+        }
+        fn write(&mut self, _: &[u8]) {
+            unimplemented!("only `write_u64` and `write_usize` are used")
+        }
+        fn write_u64(&mut self, value: u64) {
+            self.0.push(value);
+        }
+        fn write_usize(&mut self, value: usize) {
+            self.0.push(value as u64);
+        }
+    }
+
+    let written = |key: &Key| {
+        let mut recorder = Recorder::default();
+        key.hash(&mut recorder);
+        recorder.0
+    };
+
+    assert_eq!(written(&parse_key("a").unwrap()), vec![0]);
+    assert_eq!(written(&parse_key("b").unwrap()), vec![1 << 32]);
+    assert_eq!(written(&parse_key("A").unwrap()), vec![1 << 48]);
+    assert_eq!(
+        written(&parse_key("<C-M-D-A>").unwrap()),
+        vec![0b1111 << 48]
+    );
+    let char_index = u64::from(KeyName::Char(' ').index());
+    assert_eq!(
+        written(&parse_key("é").unwrap()),
+        vec![char_index << 32 | 'é' as u64]
+    );
+    assert_eq!(
+        written(&parse_key("<Unknown:7>").unwrap()),
+        vec![(char_index + 1) << 32 | 7]
+    );
+
+    let mut recorder = Recorder::default();
+    parse_keys("ab").unwrap().hash(&mut recorder);
+    assert_eq!(recorder.0, vec![2, 0, 1 << 32]);
+
+    let mut map = HashMap::new();
+    map.insert(parse_keys("<C-x><C-s>").unwrap(), "save");
+    map.insert(parse_keys("<C-x><C-c>").unwrap(), "quit");
+    assert_eq!(
+        map.get(&parse_keys("<Ctrl-x><c-s>").unwrap()),
+        Some(&"save")
+    );
+    assert_eq!(map.get(&parse_keys("<C-x>").unwrap()), None);
+}
+
+#[test]
+fn grammar_works() {
+    assert_eq!(grammar::ebnf().lines().next(), Some("keys = { key } ;"));
+
+    assert!(is_valid_syntax(""));
+    assert!(is_valid_syntax("a<C-a>\\<"));
+    assert!(is_valid_syntax("<Foo-Bar>"));
+    assert!(!is_valid_syntax("<C-a"));
+    assert!(!is_valid_syntax("a>"));
+    assert!(!is_valid_syntax("<>"));
+
+    // Every short string over the notation's special characters
+    let alphabet = ['a', 'C', '<', '>', '-', '\\'];
+    let mut inputs = vec![String::new()];
+    let mut previous = inputs.clone();
+    for _ in 0..5 {
+        previous = previous
+            .iter()
+            .flat_map(|input| alphabet.iter().map(move |ch| format!("{}{}", input, ch)))
+            .collect();
+        inputs.extend(previous.iter().cloned());
+    }
+
+    for input in inputs {
+        let is_valid = is_valid_syntax(&input);
+        match parse_keys(&input) {
+            Ok(_) => assert!(is_valid, "`{}` parsed, but is not valid syntax", input),
+            Err(
+                Error::UnexpectedGroupOpen
+                | Error::UnexpectedGroupClose
+                | Error::UnexpectedEnd
+                | Error::TrailingEscape,
+            ) => assert!(!is_valid, "`{}` is valid syntax, but did not split", input),
+            Err(_) => (),
+        }
+    }
+}
+
+#[test]
+fn parse_keys_borrowed_works() {
+    assert_eq!(
+        parse_keys_borrowed("a<C-a>"),
+        Ok(parse_keys("a<C-a>").unwrap())
+    );
+
+    let input = "a<C-Foo>";
+    let error = parse_keys_borrowed(input).unwrap_err();
+    assert_eq!(error, BorrowedError::new(ErrorKind::InvalidKeyName, "Foo"));
+    assert_eq!(error.part.as_ptr(), input[4..].as_ptr());
+    assert_eq!(error.to_error(), Error::InvalidKeyName("Foo".to_string()));
+    assert_eq!(error.to_error().kind(), ErrorKind::InvalidKeyName);
+    assert_eq!(error.to_string(), "Invalid key name `Foo`");
+
+    assert_eq!(
+        parse_keys_borrowed("<C-a"),
+        Err(BorrowedError::new(ErrorKind::UnexpectedEnd, "<C-a"))
+    );
+    assert_eq!(
+        parse_keys_borrowed("<C-Char-x>"),
+        Err(BorrowedError::new(ErrorKind::InvalidCharCode, "x"))
+    );
+}
+
+#[cfg(feature = "bench-api")]
+#[test]
+fn bench_works() {
+    use std::time::Duration;
+
+    let report = bench::measure(&["<C-x><C-s>", "gg", "<M-A>"], 10).unwrap();
+    assert_eq!(report.parse.iterations, 10);
+    assert_eq!(report.matching.iterations, 10);
+    assert!(report.check_budget(Duration::from_secs(1)).is_ok());
+
+    let measurement = bench::Measurement {
+        iterations: 4,
+        total: Duration::from_millis(8),
+    };
+    assert_eq!(measurement.per_iteration(), Duration::from_millis(2));
+    let report = bench::Report {
+        parse: measurement,
+        format: measurement,
+        lookup: measurement,
+        convert: measurement,
+        matching: bench::Measurement {
+            iterations: 1,
+            total: Duration::from_millis(5),
+        },
+    };
+    assert_eq!(
+        report.check_budget(Duration::from_millis(3)),
+        Err(bench::BudgetExceeded {
+            stage: "match",
+            per_iteration: Duration::from_millis(5),
+            budget: Duration::from_millis(3),
+        })
+    );
+
+    assert_eq!(bench::measure(&["<C-"], 1), Err(Error::UnexpectedEnd));
+}
+
+#[cfg(feature = "diagnostics")]
+#[test]
+fn diagnostics_works() {
+    use miette::Diagnostic;
+
+    let error = parse_keys_spanned("a<Contrl-a>").unwrap_err();
+    assert_eq!(
+        error.code().map(|code| code.to_string()),
+        Some("keys::invalid_key_modifier".to_string())
+    );
+    assert_eq!(
+        error.help().map(|help| help.to_string()),
+        Some("did you mean `Control`?".to_string())
+    );
+    let labels: Vec<_> = error.labels().unwrap().collect();
+    assert_eq!(labels.len(), 1);
+    assert_eq!(labels[0].offset(), 2);
+    assert_eq!(labels[0].len(), 6);
+    assert_eq!(labels[0].label(), Some("unknown modifier"));
+
+    let error = parse_keys_spanned("<C-a").unwrap_err();
+    assert_eq!(error.help().map(|help| help.to_string()), None);
+}
+
+#[test]
+fn trailing_escape_works() {
+    assert_eq!(parse_keys("a\\"), Err(Error::TrailingEscape));
+    assert_eq!(parse_key("<C-\\>"), Err(Error::TrailingEscape));
+    assert_eq!(
+        parse_keys_spanned("ab\\"),
+        Err(SpannedError::new(Error::TrailingEscape, 2..3))
+    );
+    assert_eq!(
+        parse_keys_lenient("a\\"),
+        (
+            parse_keys("a").unwrap(),
+            vec![SpannedError::new(Error::TrailingEscape, 1..2)]
+        )
+    );
+}
+
+#[test]
+fn escape_notation_works() {
+    assert_eq!(escape_notation('a'), "a");
+    assert_eq!(escape_notation('é'), "é");
+    assert_eq!(escape_notation('-'), "\\-");
+    assert_eq!(escape_notation('<'), "\\<");
+    assert_eq!(escape_notation('>'), "\\>");
+    assert_eq!(escape_notation('\\'), "\\\\");
+    for ch in ['a', 'é', '-', '<', '>', '\\', '|'] {
+        assert_eq!(
+            parse_key(&escape_notation(ch)).map(|key| key.name),
+            Ok(KeyName::from_char(ch).0)
+        );
+        assert_eq!(unescape(&escape_notation(ch)).unwrap(), ch.to_string());
+    }
+
+    assert!(matches!(unescape("abc"), Ok(Cow::Borrowed("abc"))));
+    assert_eq!(unescape("a\\-b\\\\").unwrap(), "a-b\\");
+    assert_eq!(unescape("a\\"), Err(Error::TrailingEscape));
+
+    assert_eq!(
+        parse_keys("a\\\\b"),
+        Ok(Keys(vec![
+            Key {
+                name: KeyName::A,
+                modifiers: Modifiers::default(),
+            },
+            Key {
+                name: KeyName::Backslash,
+                modifiers: Modifiers::default(),
+            },
+            Key {
+                name: KeyName::B,
+                modifiers: Modifiers::default(),
+            },
+        ]))
+    );
+    assert_eq!(parse_keys("<C-\\\\>").unwrap().to_string(), "<C-\\\\>");
+    assert_eq!(parse_keys("\\\\").unwrap().to_string(), "\\\\");
+    assert_eq!(format!("{:#}", parse_keys("\\\\").unwrap()), "<Bslash>");
+}
+
+#[test]
+fn tokenize_works() {
+    use token::{Token, TokenKind::*};
+
+    fn tokens(input: &str) -> Vec<(token::TokenKind, &str)> {
+        tokenize(input)
+            .map(|Token { kind, span }| (kind, &input[span]))
+            .collect()
+    }
+
+    assert_eq!(tokens(""), vec![]);
+    assert_eq!(tokens("aé"), vec![(KeyName, "a"), (KeyName, "é")]);
+    assert_eq!(
+        tokens("a\\<\\\\"),
+        vec![(KeyName, "a"), (Escape, "\\<"), (Escape, "\\\\")]
+    );
+    assert_eq!(
+        tokens("<C-M-a>b"),
+        vec![
+            (GroupOpen, "<"),
+            (Modifier, "C"),
+            (Modifier, "M"),
+            (KeyName, "a"),
+            (GroupClose, ">"),
+            (KeyName, "b"),
+        ]
+    );
+    assert_eq!(
+        tokens("<lt><C--\\->"),
+        vec![
+            (GroupOpen, "<"),
+            (KeyName, "lt"),
+            (GroupClose, ">"),
+            (GroupOpen, "<"),
+            (Modifier, "C"),
+            (Escape, "\\-"),
+            (GroupClose, ">"),
+        ]
+    );
+    assert_eq!(
+        tokenize("<C-Char-65>").collect::<Vec<_>>(),
+        vec![
+            Token {
+                kind: GroupOpen,
+                span: 0..1
+            },
+            Token {
+                kind: Modifier,
+                span: 1..2
+            },
+            Token {
+                kind: KeyName,
+                span: 3..10
+            },
+            Token {
+                kind: GroupClose,
+                span: 10..11
+            },
+        ]
+    );
+
+    // Invalid input still has tokens
+    assert_eq!(
+        tokens("<C-a<Foo>>"),
+        vec![
+            (GroupOpen, "<"),
+            (Modifier, "C"),
+            (KeyName, "a"),
+            (GroupOpen, "<"),
+            (KeyName, "Foo"),
+            (GroupClose, ">"),
+            (GroupClose, ">"),
+        ]
+    );
+    assert_eq!(
+        tokens("a<S-b"),
+        vec![
+            (KeyName, "a"),
+            (GroupOpen, "<"),
+            (Modifier, "S"),
+            (KeyName, "b"),
+        ]
+    );
+    assert_eq!(tokens("a\\"), vec![(KeyName, "a"), (Escape, "\\")]);
+}
+
+#[test]
+fn parse_keys_partial_works() {
+    use PartialParse::*;
+
+    assert_eq!(parse_keys_partial(""), Complete(parse_keys("").unwrap()));
+    assert_eq!(
+        parse_keys_partial("a<C-b>"),
+        Complete(parse_keys("a<C-b>").unwrap())
+    );
+
+    for (input, before) in [
+        ("a<C-", "a"),
+        ("<", ""),
+        ("<C", ""),
+        ("<C--", ""),
+        ("<C-M-\\", ""),
+        ("<C-a", ""),
+        ("<a", ""),
+        ("<Sp", ""),
+        ("<space", ""),
+        ("<cont", ""),
+        ("<C-Ch", ""),
+        ("<C-Char-0x4", ""),
+        ("<Char-", ""),
+        ("<Unknown:", ""),
+        ("<Unknown:12", ""),
+        ("ab\\", "ab"),
+        ("<lt>\\", "<lt>"),
+    ] {
+        assert_eq!(
+            parse_keys_partial(input),
+            Incomplete(parse_keys(before).unwrap()),
+            "{input}"
+        );
+    }
+
+    assert_eq!(
+        parse_keys_partial("<Foo-"),
+        Invalid(Error::InvalidKeyModifier("Foo".to_string()))
+    );
+    assert_eq!(
+        parse_keys_partial("<C-ab"),
+        Invalid(Error::InvalidKeyName("ab".to_string()))
+    );
+    assert_eq!(
+        parse_keys_partial("<Char-0xz"),
+        Invalid(Error::InvalidCharCode("0xz".to_string()))
+    );
+    assert_eq!(
+        parse_keys_partial("<Foo>a<C-"),
+        Invalid(Error::IncompleteGroup("Foo".to_string()))
+    );
+    assert_eq!(
+        parse_keys_partial("a>"),
+        Invalid(Error::UnexpectedGroupClose)
+    );
+    assert!(matches!(parse_keys_partial("<ab"), Invalid(_)));
+}
+
+#[test]
+fn parse_keys_iter_works() {
+    for input in ["", "a", "a<C-b>\\<c", "<lt><Char-65><C-\\\\>é"] {
+        let keys: Result<Vec<_>, _> = parse_keys_iter(input).collect();
+        assert_eq!(keys.map(Keys), parse_keys(input));
+    }
+
+    let mut keys = parse_keys_iter("ab<Foo>c");
+    assert_eq!(keys.next(), Some(parse_key("a")));
+    assert_eq!(keys.next(), Some(parse_key("b")));
+    assert_eq!(
+        keys.next(),
+        Some(Err(Error::IncompleteGroup("Foo".to_string())))
+    );
+    assert_eq!(keys.next(), None);
+
+    // Only the keys which are needed are parsed
+    let first: Vec<_> = parse_keys_iter("a<C-b>c<").take(2).collect();
+    assert_eq!(first, vec![parse_key("a"), parse_key("<C-b>")]);
+
+    for (input, error) in [
+        ("a<C-<b>", Error::UnexpectedGroupOpen),
+        ("a>", Error::UnexpectedGroupClose),
+        ("a<C-b", Error::UnexpectedEnd),
+        ("a<C-\\", Error::UnexpectedEnd),
+        ("a\\", Error::TrailingEscape),
+    ] {
+        assert_eq!(parse_keys_iter(input).last(), Some(Err(error.clone())));
+        assert_eq!(parse_keys(input), Err(error));
+    }
+}
+
+#[test]
+fn parser_limits_works() {
+    let options = ParserOptions {
+        max_keys: Some(3),
+        max_group_len: Some(8),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        parse_keys_with("ab<C-M-c>", &options),
+        parse_keys("ab<C-M-c>")
+    );
+    assert_eq!(parse_keys_with("abcd", &options), Err(Error::LimitExceeded));
+    assert_eq!(
+        parse_keys_with("<C-M-S-a>", &options),
+        Err(Error::LimitExceeded)
+    );
+    // The group is not scanned past the limit
+    let long_group = format!("<{}", "C-".repeat(1000));
+    assert_eq!(
+        parse_keys_with(&long_group, &options),
+        Err(Error::LimitExceeded)
+    );
+    assert_eq!(
+        parse_keys_borrowed_with("abcd<", &options),
+        Err(BorrowedError::new(ErrorKind::LimitExceeded, "d<"))
+    );
+    assert_eq!(
+        parse_keys_spanned_with("a<C-M-S-a>", &options),
+        Err(SpannedError::new(Error::LimitExceeded, 1..9))
+    );
+    assert_eq!(Error::LimitExceeded.kind(), ErrorKind::LimitExceeded);
+}
+
+#[test]
+fn parse_keys_at_works() {
+    assert_eq!(
+        parse_keys_at("a<C-b>", Position::default()),
+        Ok(parse_keys("a<C-b>").unwrap())
+    );
+
+    let base = Position {
+        offset: 20,
+        line: 3,
+        column: 9,
+    };
+    let error = parse_keys_at("aé<x-b>", base).unwrap_err();
+    assert_eq!(
+        error,
+        LocatedError {
+            start: Position {
+                offset: 24,
+                line: 3,
+                column: 12,
+            },
+            end: Position {
+                offset: 25,
+                line: 3,
+                column: 13,
+            },
+            error: Error::InvalidKeyModifier("x".to_string()),
+        }
+    );
+    assert_eq!(error.to_string(), "Invalid key modifier `x` (at 3:12)");
+
+    // A newline is not a key, unless keys are whitespace separated
+    let error = parse_keys_at("a\n  <C-Foo>", base).unwrap_err();
+    assert_eq!(
+        (error.start, error.end),
+        (
+            Position {
+                offset: 21,
+                line: 3,
+                column: 10,
+            },
+            Position {
+                offset: 22,
+                line: 4,
+                column: 1,
+            },
+        )
+    );
+    assert_eq!(
+        parse_keys_lenient("a\n<").1[1].locate("a\n<", Position::default()),
+        LocatedError {
+            start: Position {
+                offset: 2,
+                line: 2,
+                column: 1,
+            },
+            end: Position {
+                offset: 3,
+                line: 2,
+                column: 2,
+            },
+            error: Error::UnexpectedEnd,
+        }
+    );
+}
+
+#[test]
+fn notation_works() {
+    use notation::{Notation, Vim};
+
+    /// Keys separated by spaces, as a custom dialect
+    struct Spaced;
+    impl Notation for Spaced {
+        fn parse(&self, input: &str) -> Result<Keys, Error> {
+            let keys: Result<Vec<_>, _> = input.split(' ').map(parse_key).collect();
+            Ok(Keys::from(keys?))
+        }
+        fn format(&self, keys: &Keys) -> Result<String, ConversionError> {
+            let keys: Vec<_> = keys.as_slice().iter().map(Key::to_string).collect();
+            Ok(keys.join(" "))
+        }
+    }
+
+    let keys = parse_keys("<C-x><C-s>").unwrap();
+    assert_eq!(Keys::parse_with(Vim, "<C-x><C-s>"), Ok(keys.clone()));
+    assert_eq!(keys.format(Vim).unwrap(), "<C-x><C-s>");
+
+    assert_eq!(Keys::parse_with(Spaced, "<C-x> <C-s>"), Ok(keys.clone()));
+    assert_eq!(keys.format(Spaced).unwrap(), "<C-x> <C-s>");
+    assert_eq!(
+        Keys::parse_with(Vim, "a\\<")
+            .unwrap()
+            .format(Spaced)
+            .unwrap(),
+        "a \\<"
+    );
+
+    let notations: [&dyn Notation; 2] = [&Vim, &Spaced];
+    for notation in notations {
+        assert_eq!(
+            Keys::parse_with(notation, &keys.format(notation).unwrap()),
+            Ok(keys.clone())
+        );
+    }
+}
+
+#[test]
+fn normalize_works() {
+    let key = |input| parse_key(input).unwrap();
+
+    assert_eq!(key("<S-a>").normalize(), key("A"));
+    assert_eq!(key("<M-C-a>").normalize(), key("<C-M-a>"));
+    assert!(key("<S-a>").eq_normalized(key("A")));
+    assert!(!key("<S-1>").eq_normalized(key("!")));
+
+    let char_key = Key {
+        name: KeyName::Char('a'),
+        modifiers: Modifiers {
+            control: true,
+            ..Default::default()
+        },
+    };
+    assert_eq!(char_key.normalize(), key("<C-a>"));
+    let char_key = Key {
+        name: KeyName::Char('<'),
+        modifiers: Modifiers::default(),
+    };
+    assert_eq!(char_key.normalize(), key("<lt>"));
+    assert_eq!(key("é").normalize(), key("é"));
+
+    assert_eq!(
+        key("<C-S-1>").normalize_with(ShiftedSymbols::ToSymbol),
+        key("<C-!>")
+    );
+    assert_eq!(
+        key("<S-a>").normalize_with(ShiftedSymbols::ToSymbol),
+        key("A")
+    );
+    assert_eq!(
+        key("<C-!>").normalize_with(ShiftedSymbols::ToShifted),
+        key("<C-S-1>")
+    );
+    assert_eq!(
+        key("<Bar>").normalize_with(ShiftedSymbols::ToShifted),
+        key("<S-\\\\>")
+    );
+    assert_eq!(key("1").normalize_with(ShiftedSymbols::ToShifted), key("1"));
+
+    let keys = parse_keys("<M-C-x><Char-0x61>").unwrap();
+    assert_eq!(keys.normalize(), parse_keys("<C-M-x>a").unwrap());
+    assert!(keys.eq_normalized(&parse_keys("<C-M-x>a").unwrap()));
+    assert_eq!(
+        parse_keys("a<S-2>")
+            .unwrap()
+            .normalize_with(ShiftedSymbols::ToSymbol),
+        parse_keys("a@").unwrap()
+    );
+}
+
+#[test]
+fn emacs_notation_works() {
+    use notation::Emacs;
+
+    let emacs = |input| Keys::parse_with(Emacs, input);
+    let vim = |input| parse_keys(input);
+
+    assert_eq!(emacs("C-x C-s"), vim("<C-x><C-s>"));
+    assert_eq!(emacs("  C-x\tC-s "), vim("<C-x><C-s>"));
+    assert_eq!(emacs("M-<f5>"), vim("<M-F5>"));
+    assert_eq!(emacs("<M-f5>"), vim("<M-F5>"));
+    assert_eq!(emacs("s-a"), vim("<D-a>"));
+    assert_eq!(emacs("C-M-S-x"), vim("<C-M-S-x>"));
+    assert_eq!(emacs("C-x 4 C-f"), vim("<C-x>4<C-f>"));
+    assert_eq!(emacs("abc"), vim("abc"));
+    assert_eq!(emacs("X"), vim("X"));
+    assert_eq!(emacs("C--"), vim("<C-\\->"));
+    assert_eq!(emacs("< > \\"), vim("\\<\\>\\\\"));
+    assert_eq!(
+        emacs("RET SPC TAB ESC DEL"),
+        vim("<CR><Space><Tab><Esc><BS>")
+    );
+    assert_eq!(
+        emacs("<return> <prior> C-<next>"),
+        vim("<CR><PageUp><C-PageDown>")
+    );
+    assert_eq!(emacs("<Unknown:42>"), vim("<Unknown:42>"));
+    assert_eq!(emacs(""), vim(""));
+
+    assert_eq!(
+        emacs("H-a"),
+        Err(Error::InvalidKeyModifier("H".to_string()))
+    );
+    assert_eq!(
+        emacs("C-abc"),
+        Err(Error::InvalidKeyName("abc".to_string()))
+    );
+    assert_eq!(
+        emacs("<f99>"),
+        Err(Error::InvalidKeyName("f99".to_string()))
+    );
+
+    for (vim_input, emacs_output) in [
+        ("<C-x><C-s>", "C-x C-s"),
+        ("<M-F5>", "M-<f5>"),
+        ("<D-a>", "s-a"),
+        ("<M-C-S-x>", "C-M-X"),
+        ("<S-Tab>", "S-TAB"),
+        ("<C-\\->\\<\\\\", "C-- < \\"),
+        ("<Space><CR><BS><Del>", "SPC RET DEL <delete>"),
+        ("é<Unknown:42>", "é <Unknown:42>"),
+    ] {
+        let keys = vim(vim_input).unwrap();
+        assert_eq!(keys.format(Emacs).unwrap(), emacs_output);
+        assert_eq!(emacs(emacs_output), Ok(keys));
+    }
+}
+
+#[test]
+fn gui_notation_works() {
+    use notation::Gui;
+
+    let linux = Gui { is_mac: false };
+    let mac = Gui { is_mac: true };
+    let gui = |input| Keys::parse_with(linux, input);
+    let vim = |input| parse_keys(input);
+
+    assert_eq!(gui("Ctrl+Shift+P"), vim("<C-S-p>"));
+    assert_eq!(gui("ctrl+shift+p"), vim("<C-P>"));
+    assert_eq!(gui("Control+P"), vim("<C-p>"));
+    assert_eq!(gui("Alt+F4"), vim("<M-F4>"));
+    assert_eq!(gui("Option+Enter"), vim("<M-CR>"));
+    assert_eq!(gui("Super+Space"), vim("<D-Space>"));
+    assert_eq!(gui("Ctrl+K Ctrl+S"), vim("<C-k><C-s>"));
+    assert_eq!(gui("Ctrl++"), vim("<C-+>"));
+    assert_eq!(gui("Ctrl+Plus"), vim("<C-+>"));
+    assert_eq!(gui("+"), vim("+"));
+    assert_eq!(gui("Ctrl+-"), vim("<C-\\->"));
+    assert_eq!(gui("Ctrl+!"), vim("<C-!>"));
+    assert_eq!(gui("f12"), vim("<F12>"));
+    assert_eq!(gui("CmdOrCtrl+P"), vim("<P-p>"));
+    assert_eq!(Keys::parse_with(mac, "CmdOrCtrl+P"), vim("<P-p>"));
+    assert_eq!(
+        Keys::parse_with(mac, "CommandOrControl+Shift+Z"),
+        vim("<P-S-z>")
+    );
+
+    assert_eq!(
+        gui("Hyper+P"),
+        Err(Error::InvalidKeyModifier("Hyper".to_string()))
+    );
+    assert_eq!(
+        gui("Ctrl+Foo"),
+        Err(Error::InvalidKeyName("Foo".to_string()))
+    );
+    assert_eq!(gui("Ctrl+"), Err(Error::NoKeyName));
+
+    for (vim_input, linux_output, mac_output) in [
+        ("<C-S-p>", "Ctrl+Shift+P", "Ctrl+Shift+P"),
+        ("<C-p>", "Ctrl+P", "Ctrl+P"),
+        ("<D-M-Left>", "Alt+Super+Left", "Alt+Cmd+Left"),
+        ("<C-+><Space>", "Ctrl+Plus Space", "Ctrl+Plus Space"),
+        ("<S-F5>\\-é", "Shift+F5 - é", "Shift+F5 - é"),
+        ("<C-!><Esc>", "Ctrl+! Esc", "Ctrl+! Esc"),
+    ] {
+        let keys = vim(vim_input).unwrap();
+        assert_eq!(keys.format(linux).unwrap(), linux_output);
+        assert_eq!(keys.format(mac).unwrap(), mac_output);
+        assert_eq!(gui(linux_output), Ok(keys.clone()));
+        assert_eq!(Keys::parse_with(mac, mac_output), Ok(keys));
+    }
+}
+
+#[test]
+fn fingerprint_works() {
+    let keys = |input| parse_keys(input).unwrap();
+    let salt = b"0123456789abcdef";
+    let pepper = b"fedcba9876543210";
+
+    assert_eq!(
+        keys("<C-x><C-s>").fingerprint(salt),
+        keys("<C-x><C-s>").fingerprint(salt)
+    );
+    assert_eq!(keys("<S-a>").fingerprint(salt), keys("A").fingerprint(salt));
+    assert_ne!(
+        keys("<C-x><C-s>").fingerprint(salt),
+        keys("<C-x><C-f>").fingerprint(salt)
+    );
+    assert_ne!(
+        keys("<C-x><C-s>").fingerprint(salt),
+        keys("<C-x><C-s>").fingerprint(pepper)
+    );
+}
+
+#[test]
+fn vscode_notation_works() {
+    use notation::VsCode;
+
+    let linux = VsCode { is_mac: false };
+    let mac = VsCode { is_mac: true };
+    let vscode = |input| Keys::parse_with(linux, input);
+    let vim = |input| parse_keys(input);
+
+    assert_eq!(vscode("ctrl+k ctrl+s"), vim("<C-k><C-s>"));
+    assert_eq!(vscode("ctrl+shift+p"), vim("<C-P>"));
+    assert_eq!(vscode("Ctrl+Shift+P"), vim("<C-P>"));
+    assert_eq!(vscode("cmd+k"), vim("<D-k>"));
+    assert_eq!(vscode("win+k"), vim("<D-k>"));
+    assert_eq!(vscode("alt+f4"), vim("<M-F4>"));
+    assert_eq!(vscode("shift+enter escape"), vim("<S-CR><Esc>"));
+    assert_eq!(vscode("ctrl+shift+/"), vim("<C-S-/>"));
+    assert_eq!(vscode("ctrl+-"), vim("<C-\\->"));
+    assert_eq!(vscode("ctrl+\\"), vim("<C-\\\\>"));
+
+    assert_eq!(
+        vscode("hyper+a"),
+        Err(Error::InvalidKeyModifier("hyper".to_string()))
+    );
+    assert_eq!(
+        vscode("ctrl+foo"),
+        Err(Error::InvalidKeyName("foo".to_string()))
+    );
+    assert_eq!(vscode("ctrl+"), Err(Error::NoKeyName));
+
+    for (vim_input, linux_output, mac_output) in [
+        ("<C-k><C-s>", "ctrl+k ctrl+s", "ctrl+k ctrl+s"),
+        ("<C-P>", "ctrl+shift+p", "ctrl+shift+p"),
+        ("<D-M-Left>", "alt+meta+left", "alt+cmd+left"),
+        ("<C-S-/><Space>", "ctrl+shift+/ space", "ctrl+shift+/ space"),
+        ("<F12><PageDown>", "f12 pagedown", "f12 pagedown"),
+    ] {
+        let keys = vim(vim_input).unwrap();
+        assert_eq!(keys.format(linux).unwrap(), linux_output);
+        assert_eq!(keys.format(mac).unwrap(), mac_output);
+        assert_eq!(vscode(linux_output), Ok(keys.clone()));
+        assert_eq!(Keys::parse_with(mac, mac_output), Ok(keys));
+    }
+    // Shifted symbols are written as on a US keyboard
+    assert_eq!(
+        vim("<C-?>!").unwrap().format(linux).unwrap(),
+        "ctrl+shift+/ shift+1"
+    );
+}
+
+#[test]
+fn autohotkey_notation_works() {
+    use notation::AutoHotkey;
+
+    let ahk = |input| Keys::parse_with(AutoHotkey, input);
+    let vim = |input| parse_keys(input);
+
+    assert_eq!(ahk("^+a"), vim("<C-A>"));
+    assert_eq!(ahk("^A"), vim("<C-a>"));
+    assert_eq!(ahk("!F4"), vim("<M-F4>"));
+    assert_eq!(ahk("#d"), vim("<D-d>"));
+    assert_eq!(ahk("^!"), vim("<C-!>"));
+    assert_eq!(ahk("!"), vim("!"));
+    assert_eq!(ahk("~$*<^>!Del"), vim("<C-M-Del>"));
+    assert_eq!(ahk("^k ^s"), vim("<C-k><C-s>"));
+    assert_eq!(ahk("+pgup esc"), vim("<S-PageUp><Esc>"));
+
+    assert_eq!(ahk("^Foo"), Err(Error::InvalidKeyName("Foo".to_string())));
+
+    for (vim_input, ahk_output) in [
+        ("<C-A>", "^+a"),
+        ("<M-F4>", "!F4"),
+        ("<D-d>", "#d"),
+        ("<C-M-S-D-Left>", "^!+#Left"),
+        ("<C-+><C-!>", "^+ ^!"),
+        ("<Space><CR><BS><PageDown>", "Space Enter Backspace PgDn"),
+        ("\\<é", "< é"),
+    ] {
+        let keys = vim(vim_input).unwrap();
+        assert_eq!(keys.format(AutoHotkey).unwrap(), ahk_output);
+        assert_eq!(ahk(ahk_output), Ok(keys));
+    }
+}
+
+#[test]
+fn tmux_notation_works() {
+    use notation::Tmux;
+
+    let tmux = |input| Keys::parse_with(Tmux, input);
+    let vim = |input| parse_keys(input);
+
+    assert_eq!(tmux("C-b"), vim("<C-b>"));
+    assert_eq!(tmux("^b"), vim("<C-b>"));
+    assert_eq!(tmux("c-m-left"), vim("<C-M-Left>"));
+    assert_eq!(tmux("S-F5"), vim("<S-F5>"));
+    assert_eq!(tmux("M-B"), vim("<M-B>"));
+    assert_eq!(tmux("C--"), vim("<C-\\->"));
+    assert_eq!(tmux("^"), vim("^"));
+    assert_eq!(tmux("BTab"), vim("<S-Tab>"));
+    assert_eq!(tmux("C-b pgdn"), vim("<C-b><PageDown>"));
+
+    assert_eq!(tmux("C-Foo"), Err(Error::InvalidKeyName("Foo".to_string())));
+    assert_eq!(tmux("D-a"), Err(Error::InvalidKeyModifier("D".to_string())));
+
+    for (vim_input, tmux_output) in [
+        ("<C-b>", "C-b"),
+        ("<M-Left>", "M-Left"),
+        ("<S-F5>", "S-F5"),
+        ("<C-M-S-Up>", "C-M-S-Up"),
+        ("<C-A>", "C-A"),
+        ("<S-Tab><Tab>", "BTab Tab"),
+        (
+            "<Space><CR><BS><Del><Insert><PageUp><PageDown>",
+            "Space Enter BSpace DC IC PPage NPage",
+        ),
+        ("\\<é\\-", "< é -"),
+    ] {
+        let keys = vim(vim_input).unwrap();
+        assert_eq!(keys.format(Tmux).unwrap(), tmux_output);
+        assert_eq!(tmux(tmux_output), Ok(keys));
+    }
+
+    let keys = vim("<C-D-a>").unwrap();
+    assert_eq!(
+        keys.format(Tmux),
+        Err(ConversionError::UnsupportedKey("<C-D-a>".to_string()))
+    );
+}
+
+#[test]
+fn readline_notation_works() {
+    use notation::Readline;
+
+    let readline = |input| Keys::parse_with(Readline, input);
+    let vim = |input| parse_keys(input);
+
+    assert_eq!(readline(r#""\C-x\C-r""#), vim("<C-x><C-r>"));
+    assert_eq!(readline(r#""\M-\C-e""#), vim("<C-M-e>"));
+    assert_eq!(readline(r#""\e[A""#), vim("<Esc>[A"));
+    assert_eq!(readline(r#""\033\x1b\t\d""#), vim("<Esc><Esc><Tab><BS>"));
+    assert_eq!(readline(r#""\x01\n""#), vim("<C-a><C-j>"));
+    assert_eq!(readline(r#""a\"\\ ""#), vim("a\"\\\\<Space>"));
+    assert_eq!(readline("Control-u"), vim("<C-u>"));
+    assert_eq!(readline("meta-rubout"), vim("<M-BS>"));
+    assert_eq!(readline("C-M-Space"), vim("<C-M-Space>"));
+
+    assert_eq!(readline(r#""\C-""#), Err(Error::NoKeyName));
+    assert_eq!(readline(r#""a\""#), Err(Error::TrailingEscape));
+    assert_eq!(readline(r#""\377""#), vim("<Char-0xff>"));
+    assert_eq!(
+        readline(r#""\400""#),
+        Err(Error::InvalidCharCode("\\400".to_string()))
+    );
+    assert_eq!(readline(r#""\1011""#), vim("A1"));
+    assert_eq!(
+        readline(r#""\x""#),
+        Err(Error::InvalidCharCode("\\x".to_string()))
+    );
+    assert_eq!(
+        readline(r#""\xg""#),
+        Err(Error::InvalidCharCode("\\xg".to_string()))
+    );
+    assert_eq!(
+        readline(r#""\xZZ""#),
+        Err(Error::InvalidCharCode("\\xZZ".to_string()))
+    );
+    assert_eq!(
+        readline("Super-a"),
+        Err(Error::InvalidKeyModifier("Super".to_string()))
+    );
+    assert_eq!(
+        readline("Foo"),
+        Err(Error::InvalidKeyName("Foo".to_string()))
+    );
+
+    for (vim_input, readline_output) in [
+        ("<C-x><C-r>", r#""\C-x\C-r""#),
+        ("<C-M-e>", r#""\M-\C-e""#),
+        ("<M-A>", r#""\M-A""#),
+        ("<Esc><Tab><CR><BS><Space>", r#""\e\t\r\d ""#),
+        ("\"\\\\é", r#""\"\\é""#),
+    ] {
+        let keys = vim(vim_input).unwrap();
+        assert_eq!(keys.format(Readline).unwrap(), readline_output);
+        assert_eq!(readline(readline_output), Ok(keys));
+    }
+
+    for vim_input in ["<D-a>", "<S-Tab>", "<Up>", "<F5>"] {
+        let keys = vim(vim_input).unwrap();
+        assert_eq!(
+            keys.format(Readline),
+            Err(ConversionError::UnsupportedKey(vim_input.to_string()))
+        );
+    }
+}
+
+#[test]
+fn i3_notation_works() {
+    use notation::I3;
+
+    let i3 = |input| Keys::parse_with(I3::default(), input);
+    let vim = |input| parse_keys(input);
+
+    assert_eq!(i3("$mod+Shift+Return"), vim("<D-S-CR>"));
+    assert_eq!(i3("$mod+A"), vim("<D-A>"));
+    assert_eq!(i3("Mod1+Ctrl+bracketleft"), vim("<C-M-[>"));
+    assert_eq!(i3("Control+minus"), vim("<C-\\->"));
+    assert_eq!(i3("$mod+1 $mod+F5"), vim("<D-1><D-F5>"));
+
+    let alt_mod = I3 {
+        mod_key: Modifiers {
+            alt: true,
+            ..Default::default()
+        },
+    };
+    assert_eq!(Keys::parse_with(alt_mod, "$mod+space"), vim("<M-Space>"));
+
+    assert_eq!(i3("$mod+"), Err(Error::NoKeyName));
+    assert_eq!(
+        i3("Mod2+a"),
+        Err(Error::InvalidKeyModifier("Mod2".to_string()))
+    );
+    assert_eq!(
+        i3("$mod+Foo"),
+        Err(Error::InvalidKeyName("Foo".to_string()))
+    );
+
+    for (vim_input, i3_output) in [
+        ("<D-S-CR>", "$mod+Shift+Return"),
+        ("<D-C-M-A>", "$mod+Shift+Ctrl+Mod1+a"),
+        ("<C-M-[>", "Ctrl+Mod1+bracketleft"),
+        ("<Space><BS><PageUp>", "space BackSpace Prior"),
+        ("\\<é", "less é"),
+    ] {
+        let keys = vim(vim_input).unwrap();
+        assert_eq!(keys.format(I3::default()).unwrap(), i3_output);
+        assert_eq!(i3(i3_output), Ok(keys));
+    }
+
+    let keys = vim("<D-M-x>").unwrap();
+    assert_eq!(keys.format(alt_mod).unwrap(), "$mod+Mod4+x");
+    let no_mod = I3 {
+        mod_key: Modifiers::default(),
+    };
+    assert_eq!(keys.format(no_mod).unwrap(), "Mod1+Mod4+x");
+}
+
+#[test]
+fn parse_map_command_works() {
+    let map = |mode, recursive, lhs, rhs| {
+        Some(Ok(MapCommand {
+            mode,
+            recursive,
+            lhs: parse_keys(lhs).unwrap(),
+            rhs,
+        }))
+    };
+
+    assert_eq!(
+        parse_map_command("nnoremap <C-w>v :vsplit<CR>"),
+        map(MapMode::Normal, false, "<C-w>v", ":vsplit<CR>")
+    );
+    assert_eq!(
+        parse_map_command("  :nmap <silent> <buffer> gd <Plug>(coc-definition)"),
+        map(MapMode::Normal, true, "gd", "<Plug>(coc-definition)")
+    );
+    assert_eq!(
+        parse_map_command("ino jk <Esc>"),
+        map(MapMode::Insert, false, "jk", "<Esc>")
+    );
+    assert_eq!(
+        parse_map_command("noremap! <c-a> <Home>"),
+        map(MapMode::InsertCommandLine, false, "<C-a>", "<Home>")
+    );
+    assert_eq!(
+        parse_map_command("xmap <Foo>-\\ x"),
+        map(MapMode::Visual, true, "\\<Foo\\>\\-\\\\", "x")
+    );
+    assert_eq!(
+        parse_map_command("map <lt>é<Space> x"),
+        map(MapMode::NormalVisualOperator, true, "\\<é<Space>", "x")
+    );
+
+    assert_eq!(parse_map_command("set number"), None);
+    assert_eq!(parse_map_command("nmap"), None);
+    assert_eq!(parse_map_command("nmap gd"), None);
+    assert_eq!(parse_map_command("nmap! gd x"), None);
+    assert_eq!(parse_map_command("n gd x"), None);
+    assert_eq!(
+        parse_map_command("nnoremap <Leader>w :w<CR>"),
+        Some(Err(Error::InvalidKeyName("Leader".to_string())))
+    );
+}
+
+#[test]
+fn kanata_notation_works() {
+    use notation::{AutoHotkey, Emacs, Gui, Kanata, I3};
+
+    let kanata = |input| Keys::parse_with(Kanata, input);
+    let vim = |input| parse_keys(input);
+
+    assert_eq!(
+        kanata("lsft spc ret caps"),
+        vim("<LShift><Space><CR><CapsLock>")
+    );
+    assert_eq!(kanata("C-S-a"), vim("<C-S-a>"));
+    assert_eq!(kanata("C-A"), vim("<C-A>"));
+    assert_eq!(kanata("RA-e M-tab"), vim("<M-e><D-Tab>"));
+    assert_eq!(kanata("grv min f5 - ,"), vim("`\\-<F5>\\-,"));
+
+    assert_eq!(
+        kanata("X-a"),
+        Err(Error::InvalidKeyModifier("X".to_string()))
+    );
+    assert_eq!(
+        kanata("LSFT"),
+        Err(Error::InvalidKeyName("LSFT".to_string()))
+    );
+
+    for (vim_input, kanata_output) in [
+        ("<C-S-a>", "C-S-a"),
+        ("<C-M-S-D-Left>", "C-A-S-M-left"),
+        ("?!", "S-slsh S-1"),
+        ("<LCtrl><RAlt><LSuper><F12>", "lctl ralt lmet f12"),
+        ("<Esc><BS><Del><PageDown><Right>", "esc bspc del pgdn rght"),
+        ("\\\\[;'é", "bsls lbrc scln apos é"),
+    ] {
+        let keys = vim(vim_input).unwrap();
+        assert_eq!(keys.format(Kanata).unwrap(), kanata_output);
+        assert_eq!(
+            kanata(kanata_output).unwrap(),
+            keys.normalize_with(ShiftedSymbols::ToShifted)
+        );
+    }
+
+    // Modifier keys in other notations
+    let keys = vim("<LShift><RSuper><CapsLock>").unwrap();
+    assert_eq!(keys.format(AutoHotkey).unwrap(), "LShift RWin CapsLock");
+    assert_eq!(
+        keys.format(I3::default()).unwrap(),
+        "Shift_L Super_R Caps_Lock"
+    );
+    assert_eq!(
+        keys.format(Emacs),
+        Err(ConversionError::UnsupportedKey("<LShift>".to_string()))
+    );
+    assert_eq!(
+        keys.format(Gui::default()),
+        Err(ConversionError::UnsupportedKey("<LShift>".to_string()))
+    );
+}
+
+#[test]
+fn keys_cache_works() {
+    use std::sync::Arc;
+
+    let mut cache = KeysCache::new();
+    assert!(cache.is_empty());
+
+    let first = cache.parse("<C-x><C-s>").unwrap();
+    let second = cache.parse("<C-x><C-s>").unwrap();
+    assert!(Arc::ptr_eq(&first, &second));
+    assert_eq!(*first, parse_keys("<C-x><C-s>").unwrap());
+    assert_eq!(cache.len(), 1);
+
+    assert_eq!(
+        cache.parse("<Foo>"),
+        Err(Error::IncompleteGroup("Foo".to_string()))
+    );
+    assert_eq!(cache.len(), 1);
+
+    cache.clear();
+    assert!(cache.is_empty());
+    assert!(!Arc::ptr_eq(&first, &cache.parse("<C-x><C-s>").unwrap()));
+
+    let mut cache = KeysCache::with_options(ParserOptions {
+        whitespace_separated: true,
+        ..Default::default()
+    });
+    assert_eq!(*cache.parse("<C-x> <C-s>").unwrap(), *first);
+}
+
+#[test]
+fn label_works() {
+    let label = |input, platform| parse_keys(input).unwrap().label(platform);
+
+    assert_eq!(label("<D-S-p>", Platform::MacOs), "⇧⌘P");
+    assert_eq!(label("<C-M-S-D-Left>", Platform::MacOs), "⌃⌥⇧⌘←");
+    assert_eq!(label("<D-CR><Esc><BS>", Platform::MacOs), "⌘↩ ⎋ ⌫");
+    assert_eq!(label("<C-P>", Platform::Linux), "Ctrl+Shift+P");
+    assert_eq!(label("<C-x><C-s>", Platform::Windows), "Ctrl+X Ctrl+S");
+    assert_eq!(label("<D-M-F4>", Platform::Windows), "Alt+Win+F4");
+    assert_eq!(label("<D-Space>", Platform::Linux), "Super+Space");
+    assert_eq!(
+        label("<C-\\<><PageDown>", Platform::Linux),
+        "Ctrl+< Page Down"
+    );
+    assert_eq!(label("<LSuper>", Platform::Windows), "Left Win");
+    assert_eq!(
+        label("<C-é><Unknown:42>", Platform::Linux),
+        "Ctrl+é Unknown:42"
+    );
+
+    assert_eq!(Platform::default(), Platform::current());
+}
+
+#[test]
+fn keys_builder_works() {
+    let keys = Keys::builder()
+        .ctrl('x')
+        .ctrl('S')
+        .key(KeyName::Tab)
+        .char('a')
+        .char('<')
+        .alt('.')
+        .super_key('é')
+        .named("<S-F5>")
+        .unwrap()
+        .modified(
+            'b',
+            Modifiers {
+                control: true,
+                alt: true,
+                ..Default::default()
+            },
+        )
+        .build();
+    assert_eq!(
+        Ok(keys),
+        parse_keys("<C-x><C-S><Tab>a\\<<M-.><D-é><S-F5><C-M-b>")
+    );
+
+    assert_eq!(
+        KeysBuilder::new().named("<C-Foo>"),
+        Err(Error::InvalidKeyName("Foo".to_string()))
+    );
+    assert_eq!(KeysBuilder::new().build(), Keys::from(Vec::new()));
+}
+
+#[test]
+fn resolve_platform_works() {
+    use notation::{Gui, Tmux};
+
+    let keys = parse_keys("<P-s><P-S-p>a").unwrap();
+    assert_eq!(keys.to_string(), "<P-s><P-P>a");
+    assert_eq!(
+        parse_keys_with(
+            "<Primary-s>",
+            &ParserOptions {
+                long_modifiers: true,
+                ..Default::default()
+            }
+        ),
+        parse_keys("<P-s>")
+    );
+
+    assert_eq!(
+        keys.resolve_platform(Platform::MacOs),
+        parse_keys("<D-s><D-P>a").unwrap()
+    );
+    assert_eq!(
+        keys.resolve_platform(Platform::Linux),
+        parse_keys("<C-s><C-P>a").unwrap()
+    );
+    assert_eq!(
+        keys.resolve_platform(Platform::Windows),
+        parse_keys("<C-s><C-P>a").unwrap()
+    );
+    let modifiers = parse_key("<P-S-s>").unwrap().modifiers;
+    assert_eq!(
+        modifiers.resolve_platform(Platform::MacOs),
+        parse_key("<D-S-s>").unwrap().modifiers
+    );
+    assert_eq!(
+        parse_keys("<C-P-s>")
+            .unwrap()
+            .resolve_platform(Platform::Linux),
+        parse_keys("<C-s>").unwrap()
+    );
+
+    assert_eq!(keys.label(Platform::MacOs), "⌘S ⇧⌘P A");
+    assert_eq!(keys.label(Platform::Linux), "Ctrl+S Ctrl+Shift+P A");
+    assert_eq!(
+        keys.format(Gui::default()).unwrap(),
+        "CmdOrCtrl+S CmdOrCtrl+Shift+P A"
+    );
+    assert_eq!(
+        keys.format(Tmux),
+        Err(ConversionError::UnsupportedKey("<P-s>".to_string()))
+    );
+
+    let mut hashes = std::collections::HashSet::new();
+    for input in ["<P-s>", "<D-s>", "<C-s>", "s"] {
+        assert!(hashes.insert(parse_key(input).unwrap()));
+    }
+}
+
+#[cfg(feature = "ggez")]
+#[test]
+fn ggez_works() {
+    use ::ggez::input::keyboard::{KeyCode, KeyInput, KeyMods};
+
+    let key = |keycode, mods| {
+        Key::try_from(KeyInput {
+            scancode: 0,
+            keycode: Some(keycode),
+            mods,
+        })
+        .unwrap()
+    };
+
+    assert_eq!(key(KeyCode::A, KeyMods::CTRL), parse_key("<C-a>").unwrap());
+    assert_eq!(key(KeyCode::A, KeyMods::SHIFT), parse_key("A").unwrap());
+    assert_eq!(key(KeyCode::Key1, KeyMods::SHIFT), parse_key("!").unwrap());
+    assert_eq!(
+        key(KeyCode::Slash, KeyMods::SHIFT | KeyMods::CTRL),
+        parse_key("<C-?>").unwrap()
+    );
+    assert_eq!(
+        key(KeyCode::LBracket, KeyMods::empty()),
+        parse_key("[").unwrap()
+    );
+    assert_eq!(
+        key(KeyCode::Minus, KeyMods::empty()),
+        parse_key("\\-").unwrap()
+    );
+    assert_eq!(
+        key(KeyCode::Asterisk, KeyMods::empty()),
+        parse_key("*").unwrap()
+    );
+    assert_eq!(
+        Key::try_from(KeyInput {
+            scancode: 0,
+            keycode: None,
+            mods: KeyMods::empty(),
+        }),
+        Err(ConversionError::NoKeycode)
+    );
+    assert_eq!(
+        Key::try_from(KeyInput {
+            scancode: 0,
+            keycode: Some(KeyCode::A),
+            mods: KeyMods::LOGO,
+        }),
+        Err(ConversionError::UnsupportedKey("LOGO".to_string()))
+    );
+    assert_eq!(
+        KeyName::try_from(KeyCode::Sleep),
+        Err(ConversionError::UnsupportedKey("Sleep".to_string()))
+    );
+}
+
+#[test]
+fn localized_label_works() {
+    struct Shouting;
+    impl KeyLabels for Shouting {
+        fn modifier(&self, modifier: Modifier) -> Option<&str> {
+            (modifier == Modifier::Alt).then_some("ALT")
+        }
+    }
+
+    let keys = parse_keys("<C-S-p><Space><M-Del><D-CR>").unwrap();
+    assert_eq!(
+        keys.label_with(Platform::Windows, German),
+        "Strg+Umschalt+P Leertaste Alt+Entf Win+Eingabe"
+    );
+    assert_eq!(
+        keys.label_with(Platform::Linux, French),
+        "Ctrl+Maj+P Espace Alt+Suppr Super+Entrée"
+    );
+    assert_eq!(
+        keys.label_with(Platform::MacOs, German),
+        "⌃⇧P Leertaste ⌥⌦ ⌘↩"
+    );
+    assert_eq!(
+        keys.label_with(Platform::Linux, English),
+        keys.label(Platform::Linux)
+    );
+    assert_eq!(
+        keys.label_with(Platform::Linux, &Shouting),
+        "Ctrl+Shift+P Space ALT+Delete Super+Enter"
+    );
+    assert_eq!(
+        parse_key("<P-F5>")
+            .unwrap()
+            .label_with(Platform::Windows, German),
+        "Strg+F5"
+    );
+}
+
+#[test]
+fn modifier_folding_works() {
+    let press = |name| KeyEvent {
+        name,
+        action: KeyAction::Press,
+    };
+    let release = |name| KeyEvent {
+        name,
+        action: KeyAction::Release,
+    };
+
+    let mut folder = ModifierFolder::new();
+    assert_eq!(folder.fold(press(KeyName::LeftShift)), None);
+    assert_eq!(folder.fold(press(KeyName::A)), parse_key("A").ok());
+    assert_eq!(folder.fold(release(KeyName::A)), None);
+    assert_eq!(folder.fold(press(KeyName::RightControl)), None);
+    assert_eq!(
+        folder.fold(KeyEvent {
+            name: KeyName::Tab,
+            action: KeyAction::Repeat,
+        }),
+        parse_key("<C-S-Tab>").ok()
+    );
+    // Right shift is still held
+    assert_eq!(folder.fold(press(KeyName::RightShift)), None);
+    assert_eq!(folder.fold(release(KeyName::LeftShift)), None);
+    assert_eq!(folder.fold(press(KeyName::B)), parse_key("<C-B>").ok());
+    folder.clear();
+    assert_eq!(folder.modifiers(), Modifiers::default());
+    assert_eq!(folder.fold(press(KeyName::B)), parse_key("b").ok());
+
+    assert_eq!(
+        parse_keys("<P-s>").unwrap().to_events(),
+        Err(ConversionError::UnresolvedPrimary)
+    );
+    let keys = parse_keys("<C-S-x>a").unwrap();
+    let events = keys.to_events().unwrap();
+    assert_eq!(
+        events,
+        [
+            press(KeyName::LeftControl),
+            press(KeyName::LeftShift),
+            press(KeyName::X),
+            release(KeyName::X),
+            release(KeyName::LeftShift),
+            release(KeyName::LeftControl),
+            press(KeyName::A),
+            release(KeyName::A),
+        ]
+    );
+
+    let mut folder = ModifierFolder::new();
+    let folded: Vec<_> = events
+        .into_iter()
+        .filter_map(|event| folder.fold(event))
+        .collect();
+    assert_eq!(Keys::from(folded), keys);
+    assert_eq!(folder, ModifierFolder::new());
+}
+
+#[test]
+fn windows_label_works() {
+    let label = |input| parse_keys(input).unwrap().label(Platform::Windows);
+
+    assert_eq!(label("<C-M-Del>"), "Ctrl+Alt+Del");
+    assert_eq!(label("<D-e>"), "Win+E");
+    assert_eq!(label("<S-Esc>"), "Shift+Esc");
+    assert_eq!(label("<C-PageDown><C-PageUp>"), "Ctrl+PgDn Ctrl+PgUp");
+    assert_eq!(label("<S-Insert><BS>"), "Shift+Ins Backspace");
+    assert_eq!(label("<C-S-Left><RSuper>"), "Ctrl+Shift+Left Right Win");
+    assert_eq!(
+        parse_keys("<C-M-Del>").unwrap().label(Platform::Linux),
+        "Ctrl+Alt+Delete"
+    );
+}
+
+#[test]
+fn format_options_works() {
+    let keys = parse_keys("<C-x>A<lt>|<S-Tab>").unwrap();
+
+    assert_eq!(
+        keys.format_with(&FormatOptions::default()),
+        keys.to_string()
+    );
+    assert_eq!(
+        keys.format_with(&FormatOptions {
+            named_symbols: true,
+            ..Default::default()
+        }),
+        format!("{:#}", keys)
+    );
+    assert_eq!(
+        keys.format_with(&FormatOptions {
+            uppercase: false,
+            named_symbols: true,
+            separator: " ",
+            ..Default::default()
+        }),
+        "<C-x> <S-a> <lt> <Bar> <S-Tab>"
+    );
+    assert_eq!(
+        keys.format_with(&FormatOptions {
+            separator: ", ",
+            ..Default::default()
+        }),
+        "<C-x>, A, \\<, |, <S-Tab>"
+    );
+    assert_eq!(
+        parse_key("<M-A>").unwrap().format_with(&FormatOptions {
+            uppercase: false,
+            ..Default::default()
+        }),
+        "<M-S-a>"
+    );
+
+    let spaced = FormatOptions {
+        uppercase: false,
+        separator: " ",
+        ..Default::default()
+    };
+    let output = keys.format(&spaced).unwrap();
+    assert_eq!(output, "<C-x> <S-a> \\< | <S-Tab>");
+    assert_eq!(Keys::parse_with(&spaced, &output), Ok(keys));
+}
+
+#[test]
+fn modifier_order_works() {
+    let keys = parse_keys("<C-M-D-P-S-Left>").unwrap();
+    let format = |modifier_order| {
+        keys.format_with(&FormatOptions {
+            modifier_order,
+            ..Default::default()
+        })
+    };
+
+    use Modifier::*;
+    assert_eq!(format([Control, Alt, Super, Shift]), "<C-M-D-P-S-Left>");
+    assert_eq!(format([Control, Alt, Shift, Super]), "<C-M-S-D-P-Left>");
+    assert_eq!(format([Shift, Super, Alt, Control]), "<S-D-P-M-C-Left>");
+    assert_eq!(format([Shift, Shift, Shift, Alt]), "<S-M-C-D-P-Left>");
+
+    let options = FormatOptions {
+        modifier_order: [Shift, Alt, Control, Super],
+        ..Default::default()
+    };
+    let keys = parse_keys("<C-S-Tab><C-M-x>").unwrap();
+    assert_eq!(keys.format_with(&options), "<S-C-Tab><M-C-x>");
+    assert_eq!(
+        Keys::parse_with(&options, &keys.format_with(&options)),
+        Ok(keys)
+    );
+}
+
+#[test]
+fn w3c_code_works() {
+    assert_eq!(KeyName::from_w3c_code("KeyA"), Some(KeyName::A));
+    assert_eq!(KeyName::from_w3c_code("Digit1"), Some(KeyName::Number1));
+    assert_eq!(
+        KeyName::from_w3c_code("BracketLeft"),
+        Some(KeyName::BracketLeft)
+    );
+    assert_eq!(KeyName::from_w3c_code("ArrowLeft"), Some(KeyName::Left));
+    assert_eq!(
+        KeyName::from_w3c_code("MetaRight"),
+        Some(KeyName::RightSuper)
+    );
+    assert_eq!(KeyName::from_w3c_code("keya"), None);
+    assert_eq!(KeyName::from_w3c_code("NumpadEnter"), None);
+
+    assert_eq!(KeyName::Z.to_w3c_code(), Some("KeyZ"));
+    assert_eq!(KeyName::SingleQuote.to_w3c_code(), Some("Quote"));
+    assert_eq!(KeyName::F12.to_w3c_code(), Some("F12"));
+    assert_eq!(KeyName::Bang.to_w3c_code(), None);
+    assert_eq!(KeyName::Char('é').to_w3c_code(), None);
+
+    let key = parse_key("?")
+        .unwrap()
+        .normalize_with(ShiftedSymbols::ToShifted);
+    assert!(key.modifiers.shift);
+    assert_eq!(key.name.to_w3c_code(), Some("Slash"));
+
+    for name in KeyName::ALL {
+        if let Some(code) = name.to_w3c_code() {
+            assert_eq!(KeyName::from_w3c_code(code), Some(*name));
+        }
+    }
+}
+
+#[test]
+fn w3c_key_works() {
+    let ctrl = Modifiers {
+        control: true,
+        ..Default::default()
+    };
+    let shift = Modifiers {
+        shift: true,
+        ..Default::default()
+    };
+    let key = |value, modifiers| Key::from_w3c_key(value, modifiers);
+    let vim = |input| parse_key(input).ok();
+
+    assert_eq!(key("a", ctrl), vim("<C-a>"));
+    assert_eq!(key("A", shift), vim("A"));
+    assert_eq!(key("!", shift), vim("!"));
+    assert_eq!(key(" ", Modifiers::default()), vim("<Space>"));
+    assert_eq!(key("Tab", shift), vim("<S-Tab>"));
+    assert_eq!(key("ArrowLeft", ctrl), vim("<C-Left>"));
+    assert_eq!(key("Meta", Modifiers::default()), vim("<LSuper>"));
+    assert_eq!(key("é", Modifiers::default()), vim("é"));
+    assert_eq!(key("Dead", Modifiers::default()), None);
+    assert_eq!(key("Unidentified", Modifiers::default()), None);
+
+    let value = |input| parse_key(input).unwrap().to_w3c_key();
+    assert_eq!(value("<C-a>").as_deref(), Some("a"));
+    assert_eq!(value("<C-A>").as_deref(), Some("A"));
+    assert_eq!(value("\\<").as_deref(), Some("<"));
+    assert_eq!(value("<Space>").as_deref(), Some(" "));
+    assert_eq!(value("<S-PageDown>").as_deref(), Some("PageDown"));
+    assert_eq!(value("<RCtrl>").as_deref(), Some("Control"));
+    assert_eq!(value("<Unknown:42>"), None);
+
+    for input in [
+        "a", "<C-A>", "|", "<S-Tab>", "<Up>", "<F5>", "<Esc>", "<LShift>",
+    ] {
+        let key = parse_key(input).unwrap();
+        let value = key.to_w3c_key().unwrap();
+        assert_eq!(Key::from_w3c_key(&value, key.modifiers), Some(key));
+    }
+}
+
+#[cfg(feature = "keyboard-types")]
+#[test]
+fn keyboard_types_works() {
+    use ::keyboard_types::{Code, KeyboardEvent, Modifiers as Mods, NamedKey};
+
+    let event = |key: ::keyboard_types::Key, modifiers| KeyboardEvent {
+        modifiers,
+        ..KeyboardEvent::key_down(key, Code::Unidentified)
+    };
+    let key = |event: KeyboardEvent| Key::try_from(&event).unwrap();
+
+    assert_eq!(
+        key(event("a".parse().unwrap(), Mods::CONTROL)),
+        parse_key("<C-a>").unwrap()
+    );
+    assert_eq!(
+        key(event("!".parse().unwrap(), Mods::SHIFT)),
+        parse_key("!").unwrap()
+    );
+    assert_eq!(
+        key(event(NamedKey::Enter.into(), Mods::META | Mods::ALT)),
+        parse_key("<M-D-CR>").unwrap()
+    );
+    assert!(Key::try_from(&event(NamedKey::AudioVolumeUp.into(), Mods::empty())).is_err());
+
+    assert_eq!(
+        ::keyboard_types::Key::try_from(parse_key("<C-A>").unwrap()).unwrap(),
+        ::keyboard_types::Key::Character("A".to_string())
+    );
+    assert_eq!(
+        ::keyboard_types::Key::try_from(parse_key("<PageUp>").unwrap()).unwrap(),
+        NamedKey::PageUp.into()
+    );
+
+    assert_eq!(
+        Key::try_from(::keyboard_types::Key::Character("A".to_string())).unwrap(),
+        parse_key("A").unwrap()
+    );
+    assert_eq!(
+        Key::try_from(::keyboard_types::Key::from(NamedKey::Dead)),
+        Err(ConversionError::UnsupportedKey("Dead".to_string()))
+    );
+
+    assert_eq!(KeyName::try_from(Code::KeyQ).unwrap(), KeyName::Q);
+    assert_eq!(Code::try_from(KeyName::LeftShift).unwrap(), Code::ShiftLeft);
+    assert!(Code::try_from(KeyName::Unknown(42)).is_err());
+
+    let modifiers = parse_key("<C-S-M-D-a>").unwrap().modifiers;
+    assert_eq!(
+        Modifiers::from(Mods::try_from(modifiers).unwrap()),
+        modifiers
+    );
+    let primary = parse_key("<P-a>").unwrap().modifiers;
+    assert_eq!(
+        Mods::try_from(primary),
+        Err(ConversionError::UnresolvedPrimary)
+    );
+    assert_eq!(
+        Mods::try_from(primary.resolve_platform(Platform::MacOs)),
+        Ok(Mods::META)
+    );
+}
+
+#[cfg(all(feature = "keyboard-types", feature = "conformance"))]
+#[test]
+fn keyboard_types_conformance_works() {
+    // Key values have no modifiers, other than shift within characters
+    let report = conformance::check_backend(
+        |key| {
+            let value = ::keyboard_types::Key::try_from(key).ok();
+            (key.modifiers == Modifiers::default())
+                .then_some(value)
+                .flatten()
+        },
+        |value| Key::try_from(value).ok(),
+    );
+    // Right modifier keys have the same value as left ones
+    let failures: Vec<_> = report
+        .failures
+        .iter()
+        .map(|failure| failure.case.notation.as_str())
+        .collect();
+    assert_eq!(failures, ["<RShift>", "<RCtrl>", "<RAlt>", "<RSuper>"]);
+    assert!(report
+        .unsupported
+        .iter()
+        .all(|case| case.key.modifiers != Modifiers::default()
+            || matches!(case.key.name, KeyName::Unknown(_))));
+}
+
+#[cfg(feature = "crokey")]
+#[test]
+fn crokey_works() {
+    use ::crokey::{key, KeyCombination};
+
+    let key = |combination: KeyCombination| Key::try_from(combination).unwrap();
+    assert_eq!(key(key!(ctrl - c)), parse_key("<C-c>").unwrap());
+    assert_eq!(key(key!(shift - a)), parse_key("A").unwrap());
+    assert_eq!(key(key!(alt - '?')), parse_key("<M-?>").unwrap());
+    assert_eq!(key(key!(shift - '?')), parse_key("?").unwrap());
+    assert_eq!(key(key!(backtab)), parse_key("<S-Tab>").unwrap());
+    assert_eq!(key(key!(cmd - f5)), parse_key("<D-F5>").unwrap());
+    assert_eq!(key(key!(space)), parse_key("<Space>").unwrap());
+    assert!(Key::try_from(key!(ctrl - a - b)).is_err());
+    assert!(Key::try_from(key!(f20)).is_err());
+
+    let combination = |input| KeyCombination::try_from(parse_key(input).unwrap()).unwrap();
+    assert_eq!(combination("<C-c>"), key!(ctrl - c));
+    assert_eq!(combination("<S-a>"), key!(shift - a));
+    assert_eq!(combination("<S-Tab>"), key!(backtab));
+    assert_eq!(combination("<M-CR>"), key!(alt - enter));
+    assert_eq!(combination("\\-"), key!(minus));
+    assert!(KeyCombination::try_from(parse_key("<Unknown:42>").unwrap()).is_err());
+    assert_eq!(
+        KeyCombination::try_from(parse_key("<P-c>").unwrap()),
+        Err(ConversionError::UnresolvedPrimary)
+    );
+
+    for input in ["<C-c>", "A", "<M-?>", "<S-Tab>", "<D-F5>", "<RCtrl>"] {
+        let key = parse_key(input).unwrap();
+        assert_eq!(
+            Key::try_from(KeyCombination::try_from(key).unwrap()),
+            Ok(key)
+        );
+    }
+}
+
+#[test]
+fn qmk_notation_works() {
+    use notation::Qmk;
+
+    let qmk = |input| Keys::parse_with(Qmk, input);
+    let vim = |input| parse_keys(input);
+
+    assert_eq!(qmk("KC_A, LCTL(KC_C), S(KC_1)"), vim("a<C-c><S-1>"));
+    assert_eq!(qmk("KC_ENTER KC_SPC,KC_LSFT"), vim("<CR><Space><LShift>"));
+    assert_eq!(qmk("LCTL(LSFT(KC_T))"), vim("<C-S-t>"));
+    assert_eq!(qmk("MEH(KC_F5) RGUI(KC_TAB)"), vim("<C-M-S-F5><D-Tab>"));
+    assert_eq!(qmk("KC_QUES KC_LPRN"), vim("?("));
+
+    assert_eq!(
+        qmk("LCTL(KC_A"),
+        Err(Error::InvalidKeyName("LCTL(KC_A".to_string()))
+    );
+    assert_eq!(
+        qmk("CTRL(KC_A)"),
+        Err(Error::InvalidKeyModifier("CTRL".to_string()))
+    );
+    assert_eq!(qmk("KC_a"), Err(Error::InvalidKeyName("KC_a".to_string())));
+    assert_eq!(
+        qmk("KC_TRNS"),
+        Err(Error::InvalidKeyName("KC_TRNS".to_string()))
+    );
+
+    for (vim_input, qmk_output) in [
+        ("a<C-c>", "KC_A, LCTL(KC_C)"),
+        ("!", "LSFT(KC_1)"),
+        ("<C-M-S-D-Left>", "LCTL(LALT(LSFT(LGUI(KC_LEFT))))"),
+        (
+            "<Esc><BS><PageDown><F12>",
+            "KC_ESC, KC_BSPC, KC_PGDN, KC_F12",
+        ),
+        ("<RAlt>\\-[", "KC_RALT, KC_MINS, KC_LBRC"),
+    ] {
+        let keys = vim(vim_input).unwrap();
+        assert_eq!(keys.format(Qmk).unwrap(), qmk_output);
+        assert_eq!(
+            qmk(qmk_output).unwrap(),
+            keys.normalize_with(ShiftedSymbols::ToShifted)
+        );
+    }
+
+    assert_eq!(
+        vim("é").unwrap().format(Qmk),
+        Err(ConversionError::UnsupportedKey("é".to_string()))
+    );
+}
+
+#[test]
+fn kitty_works() {
+    use terminal::{decode_kitty, encode_kitty, TerminalKey};
+
+    let decode = |input: &str| {
+        let (TerminalKey { key, action }, len) = decode_kitty(input.as_bytes()).unwrap();
+        assert_eq!(len, input.len());
+        (key.to_string(), action)
+    };
+    let press = |key| (parse_key(key).unwrap().to_string(), KeyAction::Press);
+
+    assert_eq!(decode("\x1b[97u"), press("a"));
+    assert_eq!(decode("\x1b[97;5u"), press("<C-a>"));
+    assert_eq!(decode("\x1b[97;2u"), press("A"));
+    assert_eq!(decode("\x1b[47:63;2u"), press("?"));
+    assert_eq!(decode("\x1b[49;2u"), press("!"));
+    assert_eq!(decode("\x1b[13;3u"), press("<M-CR>"));
+    assert_eq!(decode("\x1b[57441u"), press("<LShift>"));
+    assert_eq!(decode("\x1b[A"), press("<Up>"));
+    assert_eq!(decode("\x1b[1;9D"), press("<D-Left>"));
+    assert_eq!(decode("\x1b[3;5~"), press("<C-Del>"));
+    assert_eq!(decode("\x1b[13~"), press("<F3>"));
+    assert_eq!(decode("\x1b[97;1:2u"), ("a".to_string(), KeyAction::Repeat));
+    assert_eq!(
+        decode("\x1b[97;1:3u"),
+        ("a".to_string(), KeyAction::Release)
+    );
+    // Caps lock is ignored, and text is not needed
+    assert_eq!(decode("\x1b[97;65;65u"), press("a"));
+
+    assert_eq!(
+        decode_kitty(b"\x1b[97;5uabc"),
+        Some((
+            TerminalKey {
+                key: parse_key("<C-a>").unwrap(),
+                action: KeyAction::Press,
+            },
+            7
+        ))
+    );
+    assert_eq!(decode_kitty(b"\x1b[97;5"), None);
+    assert_eq!(decode_kitty(b"a"), None);
+    assert_eq!(decode_kitty(b"\x1b[97;17u"), None);
+    assert_eq!(decode_kitty(b"\x1b[99~"), None);
+
+    let encode = |input, action| encode_kitty(parse_key(input).unwrap(), action).unwrap();
+    assert_eq!(encode("a", KeyAction::Press), "\x1b[97u");
+    assert_eq!(encode("<C-a>", KeyAction::Press), "\x1b[97;5u");
+    assert_eq!(encode("?", KeyAction::Press), "\x1b[47;2u");
+    assert_eq!(encode("<Up>", KeyAction::Press), "\x1b[A");
+    assert_eq!(encode("<C-Up>", KeyAction::Press), "\x1b[1;5A");
+    assert_eq!(encode("<PageUp>", KeyAction::Release), "\x1b[5;1:3~");
+    assert_eq!(encode("<Space>", KeyAction::Repeat), "\x1b[32;1:2u");
+    assert_eq!(
+        encode_kitty(parse_key("<Unknown:42>").unwrap(), KeyAction::Press),
+        Err(ConversionError::UnsupportedKey("<Unknown:42>".to_string()))
+    );
+    let primary = parse_key("<P-s>").unwrap();
+    assert_eq!(
+        encode_kitty(primary, KeyAction::Press),
+        Err(ConversionError::UnresolvedPrimary)
+    );
+    assert_eq!(
+        encode_kitty(primary.resolve_platform(Platform::MacOs), KeyAction::Press),
+        Ok("\x1b[115;9u".to_string())
+    );
+
+    for input in ["<C-M-S-D-x>", "<CR>", "<Esc>", "<F12>", "<RAlt>", "é", "!"] {
+        let key = parse_key(input).unwrap();
+        for action in [KeyAction::Press, KeyAction::Repeat, KeyAction::Release] {
+            let encoded = encode_kitty(key, action).unwrap();
+            let (decoded, _) = decode_kitty(encoded.as_bytes()).unwrap();
+            assert_eq!(decoded, TerminalKey { key, action });
+        }
+    }
+}
+
+#[test]
+fn csi_u_works() {
+    use terminal::{decode_csi_u, encode_csi_u, encode_modify_other_keys};
+
+    let decode = |input: &str| {
+        let (key, len) = decode_csi_u(input.as_bytes()).unwrap();
+        assert_eq!(len, input.len());
+        key
+    };
+    let key = |input| parse_key(input).unwrap();
+
+    assert_eq!(decode("\x1b[97;5u"), key("<C-a>"));
+    assert_eq!(decode("\x1b[65;5u"), key("<C-A>"));
+    assert_eq!(decode("\x1b[97;6u"), key("<C-A>"));
+    assert_eq!(decode("\x1b[63;2u"), key("?"));
+    assert_eq!(decode("\x1b[49;2u"), key("!"));
+    assert_eq!(decode("\x1b[13;2u"), key("<S-CR>"));
+    assert_eq!(decode("\x1b[27u"), key("<Esc>"));
+    assert_eq!(decode("\x1b[27;5;97~"), key("<C-a>"));
+    assert_eq!(decode("\x1b[27;6;65~"), key("<C-A>"));
+    assert_eq!(decode("\x1b[27;5;13~"), key("<C-CR>"));
+    assert_eq!(decode("\x1b[27;3;32~"), key("<M-Space>"));
+    assert_eq!(decode("\x1b[1;5C"), key("<C-Right>"));
+    assert_eq!(decode("\x1b[15;2~"), key("<S-F5>"));
+
+    assert_eq!(decode_csi_u(b"\x1b[97;5"), None);
+    assert_eq!(decode_csi_u(b"\x1b[97;5;1;1u"), None);
+    assert_eq!(decode_csi_u(b"\x1b[28;5;97~"), None);
+    assert_eq!(decode_csi_u(b"\x1b[97;1:3u"), None);
+
+    let csi_u = |input| encode_csi_u(key(input)).unwrap();
+    assert_eq!(csi_u("<C-a>"), "\x1b[97;5u");
+    assert_eq!(csi_u("<C-S-a>"), "\x1b[65;5u");
+    assert_eq!(csi_u("<M-S-1>"), "\x1b[33;3u");
+    assert_eq!(csi_u("<S-CR>"), "\x1b[13;2u");
+    assert_eq!(csi_u("<C-Up>"), "\x1b[1;5A");
+    assert_eq!(csi_u("<Del>"), "\x1b[3~");
+
+    let modify_other_keys = |input| encode_modify_other_keys(key(input)).unwrap();
+    assert_eq!(modify_other_keys("<C-a>"), "\x1b[27;5;97~");
+    assert_eq!(modify_other_keys("<C-S-a>"), "\x1b[27;6;65~");
+    assert_eq!(modify_other_keys("<C-CR>"), "\x1b[27;5;13~");
+    assert_eq!(modify_other_keys("<S-F5>"), "\x1b[15;2~");
+    assert_eq!(
+        encode_csi_u(key("<Unknown:42>")),
+        Err(ConversionError::UnsupportedKey("<Unknown:42>".to_string()))
+    );
+    assert_eq!(
+        encode_csi_u(key("<P-s>")),
+        Err(ConversionError::UnresolvedPrimary)
+    );
+    assert_eq!(
+        encode_modify_other_keys(key("<P-Up>")),
+        Err(ConversionError::UnresolvedPrimary)
+    );
+
+    for input in [
+        "<C-M-S-D-x>",
+        "<C-?>",
+        "<Tab>",
+        "<BS>",
+        "<M-é>",
+        "<S-Space>",
+    ] {
+        let key = key(input);
+        assert_eq!(decode(&encode_csi_u(key).unwrap()), key);
+        assert_eq!(decode(&encode_modify_other_keys(key).unwrap()), key);
+    }
+}
+
+#[test]
+fn legacy_decoder_works() {
+    use terminal::LegacyDecoder;
+
+    let decoder = LegacyDecoder::new();
+    let decode = |input: &[u8]| {
+        let mut keys = Vec::new();
+        let mut rest = input;
+        while let Some((key, len)) = decoder.decode(rest) {
+            keys.push(key);
+            rest = &rest[len..];
+        }
+        assert!(rest.is_empty());
+        Keys(keys)
+    };
+    let vim = |input| parse_keys(input).unwrap();
+
+    assert_eq!(decode(b"aB1 ?"), vim("aB1<Space>?"));
+    assert_eq!(
+        decode(b"\x1b[A\x1bOB\x1b[1;5C\x1b[3~"),
+        vim("<Up><Down><C-Right><Del>")
+    );
+    assert_eq!(decode(b"\x1bOP\x1b[15~\x1b[Z"), vim("<F1><F5><S-Tab>"));
+    assert_eq!(
+        decode(b"\x01\x1a\x00\x1c\x1f\x1b"),
+        vim("<C-a><C-z><C-Space><C-\\\\><C-_><Esc>")
+    );
+    assert_eq!(decode(b"\t\r\x7f"), vim("<Tab><CR><BS>"));
+    assert_eq!(
+        decode(b"\x1ba\x1b\x01\x1b\x1b[A"),
+        vim("<M-a><C-M-a><M-Up>")
+    );
+    assert_eq!(decode(b"\x1b\x1b"), vim("<M-Esc>"));
+    assert_eq!(decode("é€".as_bytes()), vim("é€"));
+
+    assert_eq!(decoder.decode(b""), None);
+    assert_eq!(decoder.decode(b"\xff"), None);
+    assert_eq!(decoder.decode("é".as_bytes()[..1].as_ref()), None);
+
+    // Quirks, such as rxvt's shifted arrows
+    let mut decoder = LegacyDecoder::empty();
+    assert_eq!(
+        decoder.decode(b"\x1bOA"),
+        Some((parse_key("<M-O>").unwrap(), 2))
+    );
+    decoder.insert(b"\x1b[a", parse_key("<S-Up>").unwrap());
+    decoder.insert(b"\x1b[1~", parse_key("<Home>").unwrap());
+    decoder.insert(b"\x1b[1~", parse_key("<Insert>").unwrap());
+    assert_eq!(
+        decoder.decode(b"\x1b[a"),
+        Some((parse_key("<S-Up>").unwrap(), 3))
+    );
+    assert_eq!(
+        decoder.decode(b"\x1b[1~"),
+        Some((parse_key("<Insert>").unwrap(), 4))
+    );
+}
+
+#[test]
+fn terminal_bytes_works() {
+    use terminal::{LegacyDecoder, TerminalFlavor};
+
+    let bytes = |input, flavor| parse_keys(input).unwrap().to_terminal_bytes(flavor);
+    let legacy = |input| bytes(input, TerminalFlavor::Legacy).unwrap();
+
+    assert_eq!(legacy("ab<Space>C!é"), "ab C!é".as_bytes());
+    assert_eq!(
+        legacy("<C-a><C-S-z><C-Space><C-[><C-?>"),
+        b"\x01\x1a\x00\x1b\x7f"
+    );
+    assert_eq!(legacy("<CR><Tab><Esc><BS>"), b"\r\t\x1b\x7f");
+    assert_eq!(legacy("<M-x><C-M-a><M-S-1>"), b"\x1bx\x1b\x01\x1b!");
+    assert_eq!(legacy("<Up><F1><F5><S-Tab>"), b"\x1b[A\x1bOP\x1b[15~\x1b[Z");
+    assert_eq!(
+        legacy("<C-Up><M-F1><D-Del>"),
+        b"\x1b[1;5A\x1b[1;3P\x1b[3;9~"
+    );
+    for input in ["<C-1>", "<D-a>", "<S-CR>", "<Unknown:42>", "<LShift>"] {
+        assert!(bytes(input, TerminalFlavor::Legacy).is_err(), "{}", input);
+    }
+
+    assert_eq!(
+        bytes("a<C-a>", TerminalFlavor::Kitty).unwrap(),
+        b"\x1b[97u\x1b[97;5u"
+    );
+    assert_eq!(
+        bytes("a<C-1><CR>", TerminalFlavor::CsiU).unwrap(),
+        b"a\x1b[49;5u\r"
+    );
+    assert_eq!(
+        bytes("<P-c>", TerminalFlavor::Legacy),
+        Err(ConversionError::UnresolvedPrimary)
+    );
+    assert_eq!(
+        parse_keys("<P-c>")
+            .unwrap()
+            .resolve_platform(Platform::Linux)
+            .to_terminal_bytes(TerminalFlavor::Legacy)
+            .unwrap(),
+        b"\x03"
+    );
+    assert_eq!(
+        bytes("A<C-CR>", TerminalFlavor::ModifyOtherKeys).unwrap(),
+        b"A\x1b[27;5;13~"
+    );
+
+    assert_eq!(bytes("<F3>", TerminalFlavor::Legacy).unwrap(), b"\x1bOR");
+    assert_eq!(
+        bytes("<C-F3>", TerminalFlavor::Legacy).unwrap(),
+        b"\x1b[1;5R"
+    );
+    assert_eq!(
+        bytes("<S-F3>", TerminalFlavor::Legacy).unwrap(),
+        b"\x1b[1;2R"
+    );
+
+    // Bytes are read back as the same keys
+    let keys = parse_keys("aB?<C-a><M-x><C-M-S-Left><F3><C-F3><S-Tab><Space><BS>").unwrap();
+    let decoder = LegacyDecoder::new();
+    for flavor in [
+        TerminalFlavor::Legacy,
+        TerminalFlavor::CsiU,
+        TerminalFlavor::ModifyOtherKeys,
+    ] {
+        let bytes = keys.to_terminal_bytes(flavor).unwrap();
+        let mut decoded = Vec::new();
+        let mut rest = bytes.as_slice();
+        while let Some((key, len)) = decoder.decode(rest) {
+            decoded.push(key);
+            rest = &rest[len..];
+        }
+        assert_eq!(Keys(decoded), keys);
+    }
+}
+
+#[test]
+fn control_char_works() {
+    let key = |input| parse_key(input).unwrap();
+
+    assert_eq!(Key::from_control_char(0x01), Some(key("<C-a>")));
+    assert_eq!(Key::from_control_char(0x00), Some(key("<C-Space>")));
+    assert_eq!(Key::from_control_char(0x1b), Some(key("<Esc>")));
+    assert_eq!(Key::from_control_char(0x1d), Some(key("<C-]>")));
+    assert_eq!(Key::from_control_char(0x1f), Some(key("<C-_>")));
+    assert_eq!(Key::from_control_char(0x7f), Some(key("<BS>")));
+    assert_eq!(Key::from_control_char(b'a'), None);
+    assert_eq!(Key::from_control_char(0x80), None);
+
+    assert_eq!(key("<C-A>").to_control_char(), Some(0x01));
+    assert_eq!(key("<C-@>").to_control_char(), Some(0x00));
+    assert_eq!(key("<C-[>").to_control_char(), Some(0x1b));
+    assert_eq!(key("<C-^>").to_control_char(), Some(0x1e));
+    assert_eq!(key("<C-?>").to_control_char(), Some(0x7f));
+    assert_eq!(key("<CR>").to_control_char(), Some(b'\r'));
+    for input in ["a", "<M-a>", "<C-1>", "<C-M-a>", "<S-CR>", "<C-Up>"] {
+        assert_eq!(key(input).to_control_char(), None, "{}", input);
+    }
+
+    // Control characters are read back as the same byte
+    for byte in (0x00..=0x1f).chain([0x7f]) {
+        let key = Key::from_control_char(byte).unwrap();
+        assert_eq!(key.to_control_char(), Some(byte), "{}", key);
+    }
+}
+
+#[test]
+#[cfg(feature = "stdin")]
+fn key_reader_works() {
+    use terminal::{InputEvent, KeyReader, LegacyDecoder};
+
+    let input: &[u8] = b"a\x1b[A\x01\xff\x1bx\x1b";
+    let reader = KeyReader::from_reader(input, LegacyDecoder::new());
+    let keys = reader.collect::<std::io::Result<Vec<_>>>().unwrap();
+    assert_eq!(Keys(keys), parse_keys("a<Up><C-a><M-x><Esc>").unwrap());
+
+    let mut reader = KeyReader::from_reader(&b""[..], LegacyDecoder::new());
+    assert!(reader.read_key().unwrap().is_none());
+
+    // Keys split across reads are kept until the rest is read
+    struct ByteReader<'a>(&'a [u8]);
+    impl std::io::Read for ByteReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let Some((byte, rest)) = self.0.split_first() else {
+                return Ok(0);
+            };
+            buf[0] = *byte;
+            self.0 = rest;
+            Ok(1)
+        }
+    }
+    let input = "\x1b[A\x1b[1;5Cé\x1bx\x1bOP\x1b".as_bytes();
+    let reader = KeyReader::from_reader(ByteReader(input), LegacyDecoder::new());
+    let keys = reader.collect::<std::io::Result<Vec<_>>>().unwrap();
+    assert_eq!(
+        Keys(keys),
+        parse_keys("<Up><C-Right>é<M-x><F1><Esc>").unwrap()
+    );
+
+    let decoder = LegacyDecoder::new();
+    assert!(decoder.is_incomplete(b"\x1b"));
+    assert!(decoder.is_incomplete(b"\x1b[1;"));
+    assert!(decoder.is_incomplete(b"\x1b[["));
+    assert!(decoder.is_incomplete("é".as_bytes().split_at(1).0));
+    assert!(!decoder.is_incomplete(b"\x1b[A"));
+    assert!(!decoder.is_incomplete(b"a"));
+    assert!(!decoder.is_incomplete(b"\xff"));
+
+    // Pasted text is only read as an event
+    let input: &[u8] = b"\x1b[200~<CR>\x1b[201~a\x1b[200~b";
+    let mut reader = KeyReader::from_reader(input, LegacyDecoder::new());
+    assert_eq!(reader.next().unwrap().unwrap(), parse_key("a").unwrap());
+    assert!(reader.next().is_none());
+    let mut reader = KeyReader::from_reader(input, LegacyDecoder::new());
+    let paste = |text: &str| Some(InputEvent::Paste(text.to_string()));
+    assert_eq!(reader.read_event().unwrap(), paste("<CR>"));
+    assert_eq!(
+        reader.read_event().unwrap(),
+        Some(InputEvent::Key(parse_key("a").unwrap()))
+    );
+    assert_eq!(reader.read_event().unwrap(), paste("b"));
+    assert_eq!(reader.read_event().unwrap(), None);
+}
+
+#[test]
+#[cfg(feature = "pty")]
+fn pty_session_works() {
+    use portable_pty::CommandBuilder;
+    use std::time::Duration;
+    use terminal::PtySession;
+
+    let mut session = PtySession::spawn(CommandBuilder::new("cat")).unwrap();
+    session.send("hello<Space>pty<CR>").unwrap();
+    session
+        .wait_for("hello pty", Duration::from_secs(5))
+        .unwrap();
+    assert!(session
+        .wait_for("missing", Duration::from_millis(50))
+        .is_err());
+    assert!(session.send("<Unknown:42>").is_err());
+
+    // End of file for `cat`
+    session.send("<C-d>").unwrap();
+    assert!(session.wait().unwrap().success());
+}
+
+#[test]
+fn write_keys_works() {
+    use terminal::{TerminalFlavor, WriteKeys};
+
+    let mut output = Vec::new();
+    output
+        .send_keys(&parse_keys("<C-c>y<CR>").unwrap())
+        .unwrap();
+    output
+        .send_keys_with(&parse_keys("<C-a>").unwrap(), TerminalFlavor::Kitty)
+        .unwrap();
+    assert_eq!(output, b"\x03y\r\x1b[97;5u");
+
+    let error = output
+        .send_keys(&parse_keys("a<C-1>").unwrap())
+        .unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    assert_eq!(output.len(), 10);
+}
+
+#[test]
+fn input_event_works() {
+    use terminal::{InputEvent, LegacyDecoder};
+
+    let decoder = LegacyDecoder::new();
+    let input = b"\x1b[200~<C-a>:q\r\x1b[201~x";
+    assert_eq!(
+        decoder.decode_event(input),
+        Some((InputEvent::Paste("<C-a>:q\r".to_string()), 20))
+    );
+    assert_eq!(
+        decoder.decode_event(&input[20..]),
+        Some((InputEvent::Key(parse_key("x").unwrap()), 1))
+    );
+    assert_eq!(
+        decoder.decode_event(b"\x1b[200~\x1b[201~"),
+        Some((InputEvent::Paste(String::new()), 12))
+    );
+    // The end of the paste has not arrived
+    assert_eq!(decoder.decode_event(b"\x1b[200~abc"), None);
+}
+
+#[cfg(feature = "crossterm")]
+#[test]
+fn crossterm_works() {
+    use ::crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+    use terminal::TerminalKey;
+
+    let event = |code, modifiers| KeyEvent::new(code, modifiers);
+    let key = |event: KeyEvent| Key::try_from(event).unwrap();
+    assert_eq!(
+        key(event(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+        parse_key("<C-c>").unwrap()
+    );
+    assert_eq!(
+        key(event(KeyCode::Char('A'), KeyModifiers::SHIFT)),
+        parse_key("A").unwrap()
+    );
+    assert_eq!(
+        key(event(KeyCode::Char('?'), KeyModifiers::SHIFT)),
+        parse_key("?").unwrap()
+    );
+    assert_eq!(
+        key(event(KeyCode::BackTab, KeyModifiers::SHIFT)),
+        parse_key("<S-Tab>").unwrap()
+    );
+    assert_eq!(
+        key(event(KeyCode::F(5), KeyModifiers::ALT)),
+        parse_key("<M-F5>").unwrap()
+    );
+    assert!(Key::try_from(event(KeyCode::F(20), KeyModifiers::NONE)).is_err());
+    assert!(Key::try_from(event(KeyCode::Char('a'), KeyModifiers::HYPER)).is_err());
+
+    let release =
+        KeyEvent::new_with_kind(KeyCode::Enter, KeyModifiers::CONTROL, KeyEventKind::Release);
+    assert_eq!(
+        TerminalKey::try_from(release),
+        Ok(TerminalKey {
+            key: parse_key("<C-CR>").unwrap(),
+            action: KeyAction::Release,
+        })
+    );
+    assert_eq!(
+        KeyEvent::try_from(TerminalKey::try_from(release).unwrap()),
+        Ok(release)
+    );
+
+    let event = |input| KeyEvent::try_from(parse_key(input).unwrap()).unwrap();
+    assert_eq!(
+        event("<S-a>"),
+        KeyEvent::new(KeyCode::Char('A'), KeyModifiers::SHIFT)
+    );
+    assert_eq!(
+        event("<S-1>"),
+        KeyEvent::new(KeyCode::Char('!'), KeyModifiers::NONE)
+    );
+    assert_eq!(
+        event("<S-Tab>"),
+        KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT)
+    );
+    assert!(KeyEvent::try_from(parse_key("<Unknown:42>").unwrap()).is_err());
+    let primary = parse_key("<P-c>").unwrap();
+    assert_eq!(
+        KeyEvent::try_from(primary),
+        Err(ConversionError::UnresolvedPrimary)
+    );
+    assert_eq!(
+        KeyEvent::try_from(primary.resolve_platform(Platform::Windows)),
+        Ok(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL))
+    );
+
+    for input in [
+        "<C-c>", "A", "<M-?>", "<S-Tab>", "<D-F5>", "<RCtrl>", "<Space>",
+    ] {
+        let key = parse_key(input).unwrap();
+        assert_eq!(Key::try_from(KeyEvent::try_from(key).unwrap()), Ok(key));
+    }
+}
+
+#[cfg(feature = "winit")]
+#[test]
+fn winit_works() {
+    use ::winit::keyboard::{Key as LogicalKey, KeyCode, ModifiersState, NamedKey, PhysicalKey};
+
+    let key = |logical: LogicalKey, code, modifiers, policy| {
+        Key::from_winit_keys(&logical, PhysicalKey::Code(code), modifiers, policy).unwrap()
+    };
+    let character = |text: &str| LogicalKey::Character(text.into());
+
+    // `1` with shift, typed on a US keyboard
+    let bang = |policy| {
+        key(
+            character("!"),
+            KeyCode::Digit1,
+            ModifiersState::SHIFT,
+            policy,
+        )
+    };
+    assert_eq!(bang(KeyPolicy::Logical), parse_key("!").unwrap());
+    assert_eq!(bang(KeyPolicy::Physical), parse_key("<S-1>").unwrap());
+
+    // `q` with control, typed on an AZERTY keyboard
+    let azerty = |policy| {
+        key(
+            character("a"),
+            KeyCode::KeyQ,
+            ModifiersState::CONTROL,
+            policy,
+        )
+    };
+    assert_eq!(azerty(KeyPolicy::Logical), parse_key("<C-a>").unwrap());
+    assert_eq!(azerty(KeyPolicy::Physical), parse_key("<C-q>").unwrap());
+
+    assert_eq!(
+        key(
+            LogicalKey::Named(NamedKey::ArrowLeft),
+            KeyCode::ArrowLeft,
+            ModifiersState::ALT | ModifiersState::SUPER,
+            KeyPolicy::Logical,
+        ),
+        parse_key("<M-D-Left>").unwrap()
+    );
+    assert_eq!(
+        key(
+            LogicalKey::Named(NamedKey::Space),
+            KeyCode::Space,
+            ModifiersState::empty(),
+            KeyPolicy::Logical,
+        ),
+        parse_key("<Space>").unwrap()
+    );
+
+    // A dead key has no logical key, so the physical key is used
+    let dead = LogicalKey::Dead(Some('^'));
+    assert_eq!(
+        key(
+            dead.clone(),
+            KeyCode::BracketLeft,
+            ModifiersState::empty(),
+            KeyPolicy::Logical,
+        ),
+        parse_key("[").unwrap()
+    );
+    assert!(Key::from_winit_keys(
+        &dead,
+        PhysicalKey::Code(KeyCode::NumpadAdd),
+        ModifiersState::empty(),
+        KeyPolicy::Physical,
+    )
+    .is_err());
+}
+
+#[cfg(feature = "winit")]
+#[test]
+fn winit_modifiers_works() {
+    use ::winit::event::Modifiers as WinitModifiers;
+    use ::winit::keyboard::ModifiersState;
+
+    let modifiers = parse_key("<C-S-M-D-a>").unwrap().modifiers;
+    assert_eq!(
+        ModifiersState::try_from(modifiers).unwrap(),
+        ModifiersState::SHIFT
+            | ModifiersState::CONTROL
+            | ModifiersState::ALT
+            | ModifiersState::SUPER
+    );
+    assert_eq!(
+        Modifiers::from(ModifiersState::try_from(modifiers).unwrap()),
+        modifiers
+    );
+    assert_eq!(
+        Modifiers::from(ModifiersState::CONTROL | ModifiersState::SHIFT),
+        parse_key("<C-S-a>").unwrap().modifiers
+    );
+    assert_eq!(
+        Modifiers::from(WinitModifiers::from(ModifiersState::ALT)),
+        parse_key("<M-a>").unwrap().modifiers
+    );
+    assert_eq!(
+        ModifiersState::try_from(Modifiers::default()).unwrap(),
+        ModifiersState::empty()
+    );
+
+    let primary = parse_key("<P-a>").unwrap().modifiers;
+    assert_eq!(
+        ModifiersState::try_from(primary),
+        Err(ConversionError::UnresolvedPrimary)
+    );
+    assert_eq!(
+        ModifiersState::try_from(primary.resolve_platform(Platform::MacOs)),
+        Ok(ModifiersState::SUPER)
+    );
+}
+
+#[test]
+fn key_sequence_works() {
+    let keys = |input| parse_keys(input).unwrap();
+
+    let mut sequence = KeySequence::with_limit(3);
+    for key in keys("xgg").as_slice() {
+        sequence.push(*key);
+    }
+    assert!(sequence.ends_with(&keys("gg")));
+    assert!(sequence.ends_with(&keys("xgg")));
+    assert!(!sequence.ends_with(&keys("<C-g>g")));
+    assert!(!sequence.ends_with(&keys("")));
+
+    sequence.push(parse_key("<C-w>").unwrap());
+    sequence.push(parse_key("j").unwrap());
+    assert_eq!(sequence.as_slice(), keys("g<C-w>j").as_slice());
+    assert!(!sequence.ends_with(&keys("xgg<C-w>j")));
+
+    assert!(sequence.take(&keys("<C-w>j")));
+    assert!(sequence.as_slice().is_empty());
+    assert!(!sequence.take(&keys("<C-w>j")));
+}
+
+#[cfg(feature = "bevy")]
+#[test]
+fn bevy_works() {
+    use ::bevy_app::App;
+    use ::bevy_ecs::event::Events;
+    use ::bevy_input::keyboard::KeyCode;
+    use ::bevy_input::ButtonInput;
+    use bevy::{KeyPressed, KeysPlugin};
+
+    assert_eq!(KeyName::try_from(KeyCode::KeyA), Ok(KeyName::A));
+    assert_eq!(KeyCode::try_from(KeyName::SingleQuote), Ok(KeyCode::Quote));
+    assert!(KeyName::try_from(KeyCode::NumpadAdd).is_err());
+    assert!(KeyCode::try_from(KeyName::Bang).is_err());
+
+    let mut app = App::new();
+    app.add_plugins(KeysPlugin)
+        .init_resource::<ButtonInput<KeyCode>>();
+    let mut pressed = Vec::new();
+    let mut press = |codes: &[KeyCode]| {
+        let mut input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        input.clear();
+        for code in codes {
+            input.press(*code);
+        }
+        app.update();
+        let mut events = app.world_mut().resource_mut::<Events<KeyPressed>>();
+        pressed.extend(events.drain().map(|KeyPressed(key)| key));
+    };
+
+    press(&[KeyCode::ControlLeft, KeyCode::KeyW]);
+    press(&[KeyCode::KeyJ]);
+    // Control is still held, as it was not released
+    press(&[KeyCode::ShiftRight, KeyCode::Digit1]);
+
+    assert_eq!(Keys(pressed), parse_keys("<C-w><C-j><C-S-1>").unwrap());
+    assert!(app
+        .world()
+        .resource::<KeySequence>()
+        .ends_with(&parse_keys("<C-j><C-S-1>").unwrap()));
+}
+
+#[cfg(feature = "egui")]
+#[test]
+fn egui_works() {
+    use ::egui::{Event, InputState, KeyboardShortcut};
+
+    let event = |key, modifiers| Event::Key {
+        key,
+        physical_key: None,
+        pressed: true,
+        repeat: false,
+        modifiers,
+    };
+    let key = |key, modifiers| Key::try_from(&event(key, modifiers)).unwrap();
+    assert_eq!(
+        key(
+            ::egui::Key::P,
+            ::egui::Modifiers::CTRL | ::egui::Modifiers::SHIFT
+        ),
+        parse_key("<C-S-p>").unwrap()
+    );
+    assert_eq!(
+        key(::egui::Key::Questionmark, ::egui::Modifiers::SHIFT),
+        parse_key("?").unwrap()
+    );
+    assert_eq!(
+        key(::egui::Key::ArrowLeft, ::egui::Modifiers::ALT),
+        parse_key("<M-Left>").unwrap()
+    );
+    assert!(Key::try_from(&event(::egui::Key::F20, ::egui::Modifiers::NONE)).is_err());
+    assert!(Key::try_from(&Event::Text("a".to_string())).is_err());
+
+    let modifiers = parse_key("<C-S-M-D-a>").unwrap().modifiers;
+    assert_eq!(
+        Modifiers::from(::egui::Modifiers::from(modifiers)),
+        modifiers
+    );
+    assert_eq!(
+        ::egui::Modifiers::from(parse_key("<P-a>").unwrap().modifiers),
+        ::egui::Modifiers::COMMAND
+    );
+    assert_eq!(
+        KeyboardShortcut::try_from(parse_key("<P-s>").unwrap()),
+        Ok(KeyboardShortcut::new(
+            ::egui::Modifiers::COMMAND,
+            ::egui::Key::S
+        ))
+    );
+    assert!(KeyboardShortcut::try_from(parse_key("<Unknown:42>").unwrap()).is_err());
+
+    let mut input = InputState::default();
+    input.events = vec![
+        event(::egui::Key::P, ::egui::Modifiers::CTRL),
+        event(
+            ::egui::Key::P,
+            ::egui::Modifiers::CTRL | ::egui::Modifiers::SHIFT,
+        ),
+    ];
+    // Modifiers match exactly, so the press of `<C-p>` is left
+    assert!(input.consume(parse_key("<C-S-p>").unwrap()));
+    assert!(!input.consume(parse_key("<C-S-p>").unwrap()));
+    assert!(input.consume(parse_key("<C-p>").unwrap()));
+    assert!(input.events.is_empty());
+
+    // Primary matches command, which egui sets with the Mac command key
+    input.events = vec![event(
+        ::egui::Key::S,
+        ::egui::Modifiers::MAC_CMD | ::egui::Modifiers::COMMAND,
+    )];
+    assert!(!input.consume(parse_key("<C-s>").unwrap()));
+    assert!(input.consume(parse_key("<P-s>").unwrap()));
+    input.events = vec![event(::egui::Key::S, ::egui::Modifiers::CTRL)];
+    assert!(!input.consume(parse_key("<P-s>").unwrap()));
+    input.events = vec![event(
+        ::egui::Key::S,
+        ::egui::Modifiers::CTRL | ::egui::Modifiers::COMMAND,
+    )];
+    assert!(input.consume(parse_key("<P-s>").unwrap()));
+}
+
+#[cfg(feature = "iced")]
+#[test]
+fn iced_works() {
+    use ::iced_core::keyboard::{self, key, Location};
+    use ::iced_core::{event, window, Event};
+    use ::iced_futures::futures::{executor, stream, StreamExt};
+    use ::iced_futures::subscription;
+    use iced::key_bindings;
+
+    let character = |text: &str| keyboard::Key::Character(text.into());
+    let key = |key, modifiers| Key::from_iced(&key, modifiers).unwrap();
+    assert_eq!(
+        key(character("p"), keyboard::Modifiers::CTRL),
+        parse_key("<C-p>").unwrap()
+    );
+    assert_eq!(
+        key(character("1"), keyboard::Modifiers::SHIFT),
+        parse_key("!").unwrap()
+    );
+    assert_eq!(
+        key(character("a"), keyboard::Modifiers::SHIFT),
+        parse_key("A").unwrap()
+    );
+    assert_eq!(
+        key(
+            keyboard::Key::Named(key::Named::ArrowUp),
+            keyboard::Modifiers::ALT | keyboard::Modifiers::LOGO
+        ),
+        parse_key("<M-D-Up>").unwrap()
+    );
+    assert!(Key::from_iced(&keyboard::Key::Unidentified, keyboard::Modifiers::empty()).is_err());
+    assert!(Key::from_iced(&character("ab"), keyboard::Modifiers::empty()).is_err());
+
+    let modifiers = parse_key("<C-S-M-D-a>").unwrap().modifiers;
+    assert_eq!(
+        Modifiers::from(keyboard::Modifiers::try_from(modifiers).unwrap()),
+        modifiers
+    );
+    let primary = parse_key("<P-a>").unwrap().modifiers;
+    assert_eq!(
+        keyboard::Modifiers::try_from(primary),
+        Err(ConversionError::UnresolvedPrimary)
+    );
+    assert_eq!(
+        keyboard::Modifiers::try_from(primary.resolve_platform(Platform::Linux)),
+        Ok(keyboard::Modifiers::CTRL)
+    );
+
+    let press = |text: &str, status| subscription::Event::Interaction {
+        window: window::Id::unique(),
+        event: Event::Keyboard(keyboard::Event::KeyPressed {
+            key: character(text),
+            modified_key: character(text),
+            physical_key: key::Physical::Unidentified(key::NativeCode::Unidentified),
+            location: Location::Standard,
+            modifiers: keyboard::Modifiers::empty(),
+            text: None,
+        }),
+        status,
+    };
+    let typed = |keys: &str| {
+        keys.chars()
+            .map(|ch| press(&ch.to_string(), event::Status::Ignored))
+            .collect::<Vec<_>>()
+    };
+    let mut events = typed("xggdgd");
+    // Keys which a widget used are not bindings
+    events.insert(1, press("g", event::Status::Captured));
+    let bindings = key_bindings([
+        (parse_keys("gg").unwrap(), "top"),
+        (parse_keys("dd").unwrap(), "delete"),
+        (parse_keys("gd").unwrap(), "definition"),
+    ]);
+    let messages: Vec<_> = subscription::into_recipes(bindings)
+        .into_iter()
+        .flat_map(|recipe| {
+            executor::block_on(
+                recipe
+                    .stream(stream::iter(events.clone()).boxed())
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect();
+    assert_eq!(messages, ["top", "definition"]);
+}
+
+#[cfg(feature = "sdl2")]
+#[test]
+fn sdl2_works() {
+    use ::sdl2::keyboard::{Keycode, Mod};
+
+    let key = |keycode, keymod| Key::try_from((keycode, keymod)).unwrap();
+    assert_eq!(
+        key(Keycode::C, Mod::LCTRLMOD | Mod::NUMMOD),
+        parse_key("<C-c>").unwrap()
+    );
+    assert_eq!(key(Keycode::A, Mod::RSHIFTMOD), parse_key("A").unwrap());
+    assert_eq!(key(Keycode::NUM_1, Mod::LSHIFTMOD), parse_key("!").unwrap());
+    assert_eq!(
+        key(Keycode::QUESTION, Mod::LSHIFTMOD),
+        parse_key("?").unwrap()
+    );
+    assert_eq!(
+        key(Keycode::RETURN, Mod::LALTMOD | Mod::RGUIMOD),
+        parse_key("<M-D-CR>").unwrap()
+    );
+    assert!(Key::try_from((Keycode::KP_ENTER, Mod::NOMOD)).is_err());
+
+    let keycode = |input| <(Keycode, Mod)>::try_from(parse_key(input).unwrap()).unwrap();
+    assert_eq!(keycode("<C-c>"), (Keycode::C, Mod::LCTRLMOD));
+    assert_eq!(keycode("#"), (Keycode::HASH, Mod::NOMOD));
+    assert_eq!(keycode("<S-1>"), (Keycode::EXCLAIM, Mod::NOMOD));
+    assert_eq!(keycode("<RCtrl>"), (Keycode::RCTRL, Mod::NOMOD));
+    assert!(<(Keycode, Mod)>::try_from(parse_key("~").unwrap()).is_err());
+    assert_eq!(
+        <(Keycode, Mod)>::try_from(parse_key("<P-c>").unwrap()),
+        Err(ConversionError::UnresolvedPrimary)
+    );
+
+    for input in ["<C-c>", "A", "<M-?>", "<D-F5>", "&", "<Space>"] {
+        let key = parse_key(input).unwrap();
+        assert_eq!(
+            Key::try_from(<(Keycode, Mod)>::try_from(key).unwrap()),
+            Ok(key)
+        );
+    }
+}
+
+#[cfg(feature = "macroquad")]
+#[test]
+fn macroquad_works() {
+    use ::macroquad::input::KeyCode;
+    use ::macroquad::miniquad::KeyMods;
+
+    let mods = |shift, ctrl, alt, logo| KeyMods {
+        shift,
+        ctrl,
+        alt,
+        logo,
+    };
+    let key = |code, keymods| Key::try_from((code, keymods)).unwrap();
+    assert_eq!(
+        key(KeyCode::W, mods(false, true, false, false)),
+        parse_key("<C-w>").unwrap()
+    );
+    assert_eq!(
+        key(KeyCode::A, mods(true, false, false, false)),
+        parse_key("A").unwrap()
+    );
+    assert_eq!(
+        key(KeyCode::Key1, mods(true, false, true, false)),
+        parse_key("<M-S-1>").unwrap()
+    );
+    assert_eq!(
+        key(KeyCode::Enter, mods(false, false, false, true)),
+        parse_key("<D-CR>").unwrap()
+    );
+    assert!(Key::try_from((KeyCode::KpEnter, KeyMods::default())).is_err());
+
+    assert_eq!(
+        KeyName::try_from(KeyCode::GraveAccent),
+        Ok(KeyName::Backtick)
+    );
+    assert_eq!(
+        KeyCode::try_from(KeyName::SingleQuote),
+        Ok(KeyCode::Apostrophe)
+    );
+    assert!(KeyCode::try_from(KeyName::Bang).is_err());
+
+    assert_eq!(
+        KeyMods::try_from(parse_key("<C-M-x>").unwrap().modifiers),
+        Ok(mods(false, true, true, false))
+    );
+    assert_eq!(
+        KeyMods::try_from(parse_key("<P-x>").unwrap().modifiers),
+        Err(ConversionError::UnresolvedPrimary)
+    );
+}
@@ -0,0 +1,133 @@
+//! Tokens of key notation, with their position in the input
+//!
+//! [`tokenize`] never fails, so it can be used to highlight input which does
+//! not parse. Dashes between the parts of a group are not tokens.
+
+use std::ops::Range;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum TokenKind {
+    /// `<`
+    GroupOpen,
+    /// Modifier in a group, such as `C` in `<C-a>`
+    Modifier,
+    /// Key name, such as `a`, `Space`, or `Char-65`
+    KeyName,
+    /// Escaped character used as a key name, such as `\<`
+    Escape,
+    /// `>`
+    GroupClose,
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Token {
+    pub kind: TokenKind,
+    /// Byte range of the token in the input
+    pub span: Range<usize>,
+}
+
+/// Split key notation into tokens, using the default parser options
+///
+/// Mismatched delimiters are still tokens, and an unclosed group at the end
+/// of the input has no [`TokenKind::GroupClose`].
+pub fn tokenize(input: &str) -> impl Iterator<Item = Token> {
+    let mut tokens = Vec::new();
+    // Parts of the current group, if inside one
+    let mut group: Option<Vec<Range<usize>>> = None;
+    let mut part_start = 0;
+    let mut chars = input.char_indices().peekable();
+
+    while let Some((i, ch)) = chars.next() {
+        let end = i + ch.len_utf8();
+        let Some(parts) = &mut group else {
+            match ch {
+                '<' => {
+                    tokens.push(Token::new(TokenKind::GroupOpen, i..end));
+                    group = Some(Vec::new());
+                    part_start = end;
+                }
+                '>' => tokens.push(Token::new(TokenKind::GroupClose, i..end)),
+                '\\' => {
+                    let end = chars.next().map_or(end, |(i, ch)| i + ch.len_utf8());
+                    tokens.push(Token::new(TokenKind::Escape, i..end));
+                }
+                _ => tokens.push(Token::new(TokenKind::KeyName, i..end)),
+            }
+            continue;
+        };
+
+        match ch {
+            '\\' => {
+                chars.next();
+            }
+            '-' => {
+                if part_start != i {
+                    parts.push(part_start..i);
+                }
+                part_start = end;
+            }
+            // Mismatched group delimiters
+            // End the unclosed group, and start a new one here
+            '<' => {
+                if part_start != i {
+                    parts.push(part_start..i);
+                }
+                push_group(&mut tokens, input, parts);
+                tokens.push(Token::new(TokenKind::GroupOpen, i..end));
+                parts.clear();
+                part_start = end;
+            }
+            '>' => {
+                if part_start != i {
+                    parts.push(part_start..i);
+                }
+                push_group(&mut tokens, input, parts);
+                tokens.push(Token::new(TokenKind::GroupClose, i..end));
+                group = None;
+            }
+            _ => (),
+        }
+    }
+
+    // Unclosed group
+    if let Some(mut parts) = group {
+        if part_start < input.len() {
+            parts.push(part_start..input.len());
+        }
+        push_group(&mut tokens, input, &parts);
+    }
+
+    tokens.into_iter()
+}
+
+impl Token {
+    fn new(kind: TokenKind, span: Range<usize>) -> Self {
+        Self { kind, span }
+    }
+}
+
+/// Push the parts of a group, where the last part is the key name
+fn push_group(tokens: &mut Vec<Token>, input: &str, parts: &[Range<usize>]) {
+    let Some((name, mut modifiers)) = parts.split_last() else {
+        return;
+    };
+    let mut name = name.clone();
+
+    // `<C-Char-65>`
+    if let Some((char_code, rest)) = modifiers.split_last() {
+        if input[char_code.clone()].eq_ignore_ascii_case("Char") {
+            name.start = char_code.start;
+            modifiers = rest;
+        }
+    }
+
+    for modifier in modifiers {
+        tokens.push(Token::new(TokenKind::Modifier, modifier.clone()));
+    }
+    let kind = if input[name.clone()].starts_with('\\') {
+        TokenKind::Escape
+    } else {
+        TokenKind::KeyName
+    };
+    tokens.push(Token::new(kind, name));
+}
@@ -0,0 +1,160 @@
+//! Reading mappings from Vim `:map` commands, such as in a vimrc
+
+use crate::{parse_keys_with, Error, Key, KeyName, Keys, Modifiers, ParserOptions};
+
+/// Modes that a Vim mapping applies to
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum MapMode {
+    /// Normal, visual, select and operator-pending, as with `:map`
+    NormalVisualOperator,
+    /// `:nmap`
+    Normal,
+    /// Visual and select, as with `:vmap`
+    VisualSelect,
+    /// `:xmap`
+    Visual,
+    /// `:smap`
+    Select,
+    /// `:omap`
+    OperatorPending,
+    /// Insert and command-line, as with `:map!`
+    InsertCommandLine,
+    /// `:imap`
+    Insert,
+    /// Insert, command-line and lang-arg, as with `:lmap`
+    LangArg,
+    /// `:cmap`
+    CommandLine,
+    /// `:tmap`
+    Terminal,
+}
+
+/// Mapping defined by a Vim `:map` command
+#[derive(Clone, Debug, PartialEq)]
+pub struct MapCommand<'a> {
+    pub mode: MapMode,
+    /// Whether the rhs is remapped, which is false for `:noremap` commands
+    pub recursive: bool,
+    pub lhs: Keys,
+    /// Unparsed rhs, such as `:vsplit<CR>`
+    pub rhs: &'a str,
+}
+
+/// Commands, with the shortest abbreviation, and whether they are recursive
+const COMMANDS: &[(&str, usize, MapMode, bool)] = &[
+    ("map", 3, MapMode::NormalVisualOperator, true),
+    ("nmap", 2, MapMode::Normal, true),
+    ("vmap", 2, MapMode::VisualSelect, true),
+    ("xmap", 2, MapMode::Visual, true),
+    ("smap", 4, MapMode::Select, true),
+    ("omap", 2, MapMode::OperatorPending, true),
+    ("imap", 2, MapMode::Insert, true),
+    ("lmap", 2, MapMode::LangArg, true),
+    ("cmap", 2, MapMode::CommandLine, true),
+    ("tmap", 3, MapMode::Terminal, true),
+    ("noremap", 2, MapMode::NormalVisualOperator, false),
+    ("nnoremap", 2, MapMode::Normal, false),
+    ("vnoremap", 2, MapMode::VisualSelect, false),
+    ("xnoremap", 2, MapMode::Visual, false),
+    ("snoremap", 4, MapMode::Select, false),
+    ("onoremap", 3, MapMode::OperatorPending, false),
+    ("inoremap", 3, MapMode::Insert, false),
+    ("lnoremap", 2, MapMode::LangArg, false),
+    ("cnoremap", 3, MapMode::CommandLine, false),
+    ("tnoremap", 3, MapMode::Terminal, false),
+];
+
+/// Arguments which may come before the lhs
+const ARGUMENTS: &[&str] = &[
+    "<buffer>",
+    "<nowait>",
+    "<silent>",
+    "<special>",
+    "<script>",
+    "<expr>",
+    "<unique>",
+];
+
+/// Parse a Vim `:map` command, such as `nnoremap <C-w>v :vsplit<CR>`
+///
+/// Returns `None` if the line does not define a mapping, such as
+/// `set number` or `nmap` with no rhs. As in Vim, a `<...>` group which is
+/// not a key, such as `<Foo>`, is read as its characters. `<Leader>` and
+/// `<LocalLeader>` depend on the user's config, so they are an error, and
+/// should be replaced before parsing
+pub fn parse_map_command(line: &str) -> Option<Result<MapCommand<'_>, Error>> {
+    let line = line.trim_start().trim_start_matches(':');
+    let length = line
+        .find(|ch: char| !ch.is_ascii_alphabetic())
+        .unwrap_or(line.len());
+    let (command, mut rest) = line.split_at(length);
+
+    let (_, _, mut mode, recursive) = COMMANDS
+        .iter()
+        .find(|(name, min_length, ..)| command.len() >= *min_length && name.starts_with(command))?;
+    // `:map!` and `:noremap!`
+    if let Some(after) = rest.strip_prefix('!') {
+        if mode != MapMode::NormalVisualOperator {
+            return None;
+        }
+        mode = MapMode::InsertCommandLine;
+        rest = after;
+    } else if !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+
+    rest = rest.trim_start();
+    while let Some(argument) = ARGUMENTS.iter().find(|argument| {
+        rest.get(..argument.len())
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(argument))
+    }) {
+        rest = rest[argument.len()..].trim_start();
+    }
+
+    let (lhs, rhs) = rest.split_once(char::is_whitespace)?;
+    let rhs = rhs.trim_start();
+    if rhs.is_empty() {
+        return None;
+    }
+
+    Some(parse_lhs(lhs).map(|lhs| MapCommand {
+        mode,
+        recursive: *recursive,
+        lhs,
+        rhs,
+    }))
+}
+
+/// Parse keys written as in Vim, where `<...>` groups are keys and every
+/// other character is a key
+fn parse_lhs(input: &str) -> Result<Keys, Error> {
+    let options = ParserOptions::default();
+
+    let mut keys = Vec::new();
+    let mut rest = input;
+    while let Some(ch) = rest.chars().next() {
+        if ch == '<' {
+            if let Some(end) = rest.find('>') {
+                let group = &rest[..=end];
+                let name = &group[1..end];
+                if name.eq_ignore_ascii_case("Leader") || name.eq_ignore_ascii_case("LocalLeader") {
+                    return Err(Error::InvalidKeyName(name.to_string()));
+                }
+                if let Ok(group_keys) = parse_keys_with(group, &options) {
+                    keys.extend_from_slice(group_keys.as_slice());
+                    rest = &rest[end + 1..];
+                    continue;
+                }
+            }
+        }
+
+        let (name, shift) = KeyName::from_char(ch);
+        let modifiers = Modifiers {
+            shift,
+            ..Default::default()
+        };
+        keys.push(Key { modifiers, name });
+        rest = &rest[ch.len_utf8()..];
+    }
+    Ok(Keys::from(keys))
+}
@@ -0,0 +1,200 @@
+//! Conversion to and from W3C UI Events values, as used by browsers and
+//! webviews
+
+use crate::notation::literal_char;
+use crate::{Key, KeyName, Modifiers};
+
+/// `code` values of physical keys on a US keyboard
+const CODES: &[(KeyName, &str)] = &[
+    (KeyName::A, "KeyA"),
+    (KeyName::B, "KeyB"),
+    (KeyName::C, "KeyC"),
+    (KeyName::D, "KeyD"),
+    (KeyName::E, "KeyE"),
+    (KeyName::F, "KeyF"),
+    (KeyName::G, "KeyG"),
+    (KeyName::H, "KeyH"),
+    (KeyName::I, "KeyI"),
+    (KeyName::J, "KeyJ"),
+    (KeyName::K, "KeyK"),
+    (KeyName::L, "KeyL"),
+    (KeyName::M, "KeyM"),
+    (KeyName::N, "KeyN"),
+    (KeyName::O, "KeyO"),
+    (KeyName::P, "KeyP"),
+    (KeyName::Q, "KeyQ"),
+    (KeyName::R, "KeyR"),
+    (KeyName::S, "KeyS"),
+    (KeyName::T, "KeyT"),
+    (KeyName::U, "KeyU"),
+    (KeyName::V, "KeyV"),
+    (KeyName::W, "KeyW"),
+    (KeyName::X, "KeyX"),
+    (KeyName::Y, "KeyY"),
+    (KeyName::Z, "KeyZ"),
+    (KeyName::Number0, "Digit0"),
+    (KeyName::Number1, "Digit1"),
+    (KeyName::Number2, "Digit2"),
+    (KeyName::Number3, "Digit3"),
+    (KeyName::Number4, "Digit4"),
+    (KeyName::Number5, "Digit5"),
+    (KeyName::Number6, "Digit6"),
+    (KeyName::Number7, "Digit7"),
+    (KeyName::Number8, "Digit8"),
+    (KeyName::Number9, "Digit9"),
+    (KeyName::Dash, "Minus"),
+    (KeyName::Equals, "Equal"),
+    (KeyName::BracketLeft, "BracketLeft"),
+    (KeyName::BracketRight, "BracketRight"),
+    (KeyName::Backslash, "Backslash"),
+    (KeyName::Semicolon, "Semicolon"),
+    (KeyName::SingleQuote, "Quote"),
+    (KeyName::Comma, "Comma"),
+    (KeyName::Period, "Period"),
+    (KeyName::ForwardSlash, "Slash"),
+    (KeyName::Backtick, "Backquote"),
+    (KeyName::Space, "Space"),
+    (KeyName::Enter, "Enter"),
+    (KeyName::Tab, "Tab"),
+    (KeyName::Escape, "Escape"),
+    (KeyName::Backspace, "Backspace"),
+    (KeyName::Delete, "Delete"),
+    (KeyName::Insert, "Insert"),
+    (KeyName::Home, "Home"),
+    (KeyName::End, "End"),
+    (KeyName::PageUp, "PageUp"),
+    (KeyName::PageDown, "PageDown"),
+    (KeyName::Up, "ArrowUp"),
+    (KeyName::Down, "ArrowDown"),
+    (KeyName::Left, "ArrowLeft"),
+    (KeyName::Right, "ArrowRight"),
+    (KeyName::F1, "F1"),
+    (KeyName::F2, "F2"),
+    (KeyName::F3, "F3"),
+    (KeyName::F4, "F4"),
+    (KeyName::F5, "F5"),
+    (KeyName::F6, "F6"),
+    (KeyName::F7, "F7"),
+    (KeyName::F8, "F8"),
+    (KeyName::F9, "F9"),
+    (KeyName::F10, "F10"),
+    (KeyName::F11, "F11"),
+    (KeyName::F12, "F12"),
+    (KeyName::CapsLock, "CapsLock"),
+    (KeyName::LeftShift, "ShiftLeft"),
+    (KeyName::RightShift, "ShiftRight"),
+    (KeyName::LeftControl, "ControlLeft"),
+    (KeyName::RightControl, "ControlRight"),
+    (KeyName::LeftAlt, "AltLeft"),
+    (KeyName::RightAlt, "AltRight"),
+    (KeyName::LeftSuper, "MetaLeft"),
+    (KeyName::RightSuper, "MetaRight"),
+];
+
+/// `key` values of keys which are not characters
+const KEYS: &[(KeyName, &str)] = &[
+    (KeyName::Enter, "Enter"),
+    (KeyName::Tab, "Tab"),
+    (KeyName::Escape, "Escape"),
+    (KeyName::Backspace, "Backspace"),
+    (KeyName::Delete, "Delete"),
+    (KeyName::Insert, "Insert"),
+    (KeyName::Home, "Home"),
+    (KeyName::End, "End"),
+    (KeyName::PageUp, "PageUp"),
+    (KeyName::PageDown, "PageDown"),
+    (KeyName::Up, "ArrowUp"),
+    (KeyName::Down, "ArrowDown"),
+    (KeyName::Left, "ArrowLeft"),
+    (KeyName::Right, "ArrowRight"),
+    (KeyName::F1, "F1"),
+    (KeyName::F2, "F2"),
+    (KeyName::F3, "F3"),
+    (KeyName::F4, "F4"),
+    (KeyName::F5, "F5"),
+    (KeyName::F6, "F6"),
+    (KeyName::F7, "F7"),
+    (KeyName::F8, "F8"),
+    (KeyName::F9, "F9"),
+    (KeyName::F10, "F10"),
+    (KeyName::F11, "F11"),
+    (KeyName::F12, "F12"),
+    (KeyName::CapsLock, "CapsLock"),
+    // `key` does not say which side, so left is used
+    (KeyName::LeftShift, "Shift"),
+    (KeyName::LeftControl, "Control"),
+    (KeyName::LeftAlt, "Alt"),
+    (KeyName::LeftSuper, "Meta"),
+];
+
+impl KeyName {
+    /// Key name for a UI Events `code` value, such as `KeyA` or `Digit1`
+    ///
+    /// Codes are physical keys, so `Digit1` is [`KeyName::Number1`] whether
+    /// or not shift is held.
+    pub fn from_w3c_code(code: &str) -> Option<Self> {
+        CODES
+            .iter()
+            .find(|(_, value)| *value == code)
+            .map(|(name, _)| *name)
+    }
+
+    /// UI Events `code` value of the key, such as `KeyA`
+    ///
+    /// Shifted symbols such as [`KeyName::Bang`] have no key of their own,
+    /// and can be converted with [`ShiftedSymbols::ToShifted`](crate::ShiftedSymbols::ToShifted)
+    /// first.
+    pub fn to_w3c_code(self) -> Option<&'static str> {
+        CODES
+            .iter()
+            .find(|(name, _)| *name == self)
+            .map(|(_, code)| *code)
+    }
+}
+
+impl Key {
+    /// Key for a UI Events `key` value, such as `a`, `A` or `ArrowLeft`, and
+    /// the modifiers of the event
+    ///
+    /// The value of a character key already includes shift, so shift from
+    /// the event is only kept for other keys, such as `<S-Tab>`. Values with
+    /// no key name, such as `Dead` and `Unidentified`, give `None`.
+    pub fn from_w3c_key(key: &str, mut modifiers: Modifiers) -> Option<Self> {
+        let mut chars = key.chars();
+        let name = match (chars.next(), chars.next()) {
+            (Some(ch), None) => {
+                let (name, shift) = KeyName::from_char(ch);
+                modifiers.shift = shift;
+                name
+            }
+            _ => KEYS
+                .iter()
+                .find(|(_, value)| *value == key)
+                .map(|(name, _)| *name)?,
+        };
+        Some(Key { modifiers, name })
+    }
+
+    /// UI Events `key` value of the key, such as `A` for `<S-a>`
+    ///
+    /// Modifiers other than shift are not part of the value. Right modifier
+    /// keys have the same value as left ones, and keys with no value, such
+    /// as [`KeyName::Unknown`], give `None`.
+    pub fn to_w3c_key(&self) -> Option<String> {
+        let name = match self.name {
+            KeyName::RightShift => KeyName::LeftShift,
+            KeyName::RightControl => KeyName::LeftControl,
+            KeyName::RightAlt => KeyName::LeftAlt,
+            KeyName::RightSuper => KeyName::LeftSuper,
+            KeyName::Space => return Some(" ".to_string()),
+            name => name,
+        };
+        if let Some((_, value)) = KEYS.iter().find(|(key, _)| *key == name) {
+            return Some(value.to_string());
+        }
+        match (self.modifiers.shift, name.upper_str()) {
+            (true, Some(upper)) => Some(upper.to_string()),
+            _ => literal_char(name).map(String::from),
+        }
+    }
+}
@@ -0,0 +1,222 @@
+//! Conversion from [`winit`] key events, with the logical or physical key
+
+use crate::{ConversionError, Key, KeyName, KeyPolicy, Modifiers};
+use winit::event::{self, KeyEvent};
+use winit::keyboard::{self, KeyCode, ModifiersState, NamedKey, PhysicalKey};
+
+/// Physical keys of a US keyboard
+const CODES: &[(KeyName, KeyCode)] = &[
+    (KeyName::A, KeyCode::KeyA),
+    (KeyName::B, KeyCode::KeyB),
+    (KeyName::C, KeyCode::KeyC),
+    (KeyName::D, KeyCode::KeyD),
+    (KeyName::E, KeyCode::KeyE),
+    (KeyName::F, KeyCode::KeyF),
+    (KeyName::G, KeyCode::KeyG),
+    (KeyName::H, KeyCode::KeyH),
+    (KeyName::I, KeyCode::KeyI),
+    (KeyName::J, KeyCode::KeyJ),
+    (KeyName::K, KeyCode::KeyK),
+    (KeyName::L, KeyCode::KeyL),
+    (KeyName::M, KeyCode::KeyM),
+    (KeyName::N, KeyCode::KeyN),
+    (KeyName::O, KeyCode::KeyO),
+    (KeyName::P, KeyCode::KeyP),
+    (KeyName::Q, KeyCode::KeyQ),
+    (KeyName::R, KeyCode::KeyR),
+    (KeyName::S, KeyCode::KeyS),
+    (KeyName::T, KeyCode::KeyT),
+    (KeyName::U, KeyCode::KeyU),
+    (KeyName::V, KeyCode::KeyV),
+    (KeyName::W, KeyCode::KeyW),
+    (KeyName::X, KeyCode::KeyX),
+    (KeyName::Y, KeyCode::KeyY),
+    (KeyName::Z, KeyCode::KeyZ),
+    (KeyName::Number0, KeyCode::Digit0),
+    (KeyName::Number1, KeyCode::Digit1),
+    (KeyName::Number2, KeyCode::Digit2),
+    (KeyName::Number3, KeyCode::Digit3),
+    (KeyName::Number4, KeyCode::Digit4),
+    (KeyName::Number5, KeyCode::Digit5),
+    (KeyName::Number6, KeyCode::Digit6),
+    (KeyName::Number7, KeyCode::Digit7),
+    (KeyName::Number8, KeyCode::Digit8),
+    (KeyName::Number9, KeyCode::Digit9),
+    (KeyName::Dash, KeyCode::Minus),
+    (KeyName::Equals, KeyCode::Equal),
+    (KeyName::BracketLeft, KeyCode::BracketLeft),
+    (KeyName::BracketRight, KeyCode::BracketRight),
+    (KeyName::Backslash, KeyCode::Backslash),
+    (KeyName::Semicolon, KeyCode::Semicolon),
+    (KeyName::SingleQuote, KeyCode::Quote),
+    (KeyName::Comma, KeyCode::Comma),
+    (KeyName::Period, KeyCode::Period),
+    (KeyName::ForwardSlash, KeyCode::Slash),
+    (KeyName::Backtick, KeyCode::Backquote),
+    (KeyName::Space, KeyCode::Space),
+    (KeyName::Enter, KeyCode::Enter),
+    (KeyName::Tab, KeyCode::Tab),
+    (KeyName::Escape, KeyCode::Escape),
+    (KeyName::Backspace, KeyCode::Backspace),
+    (KeyName::Delete, KeyCode::Delete),
+    (KeyName::Insert, KeyCode::Insert),
+    (KeyName::Home, KeyCode::Home),
+    (KeyName::End, KeyCode::End),
+    (KeyName::PageUp, KeyCode::PageUp),
+    (KeyName::PageDown, KeyCode::PageDown),
+    (KeyName::Up, KeyCode::ArrowUp),
+    (KeyName::Down, KeyCode::ArrowDown),
+    (KeyName::Left, KeyCode::ArrowLeft),
+    (KeyName::Right, KeyCode::ArrowRight),
+    (KeyName::F1, KeyCode::F1),
+    (KeyName::F2, KeyCode::F2),
+    (KeyName::F3, KeyCode::F3),
+    (KeyName::F4, KeyCode::F4),
+    (KeyName::F5, KeyCode::F5),
+    (KeyName::F6, KeyCode::F6),
+    (KeyName::F7, KeyCode::F7),
+    (KeyName::F8, KeyCode::F8),
+    (KeyName::F9, KeyCode::F9),
+    (KeyName::F10, KeyCode::F10),
+    (KeyName::F11, KeyCode::F11),
+    (KeyName::F12, KeyCode::F12),
+    (KeyName::CapsLock, KeyCode::CapsLock),
+    (KeyName::LeftShift, KeyCode::ShiftLeft),
+    (KeyName::RightShift, KeyCode::ShiftRight),
+    (KeyName::LeftControl, KeyCode::ControlLeft),
+    (KeyName::RightControl, KeyCode::ControlRight),
+    (KeyName::LeftAlt, KeyCode::AltLeft),
+    (KeyName::RightAlt, KeyCode::AltRight),
+    (KeyName::LeftSuper, KeyCode::SuperLeft),
+    (KeyName::RightSuper, KeyCode::SuperRight),
+];
+
+/// Logical keys which are not characters
+const NAMED: &[(KeyName, NamedKey)] = &[
+    (KeyName::Space, NamedKey::Space),
+    (KeyName::Enter, NamedKey::Enter),
+    (KeyName::Tab, NamedKey::Tab),
+    (KeyName::Escape, NamedKey::Escape),
+    (KeyName::Backspace, NamedKey::Backspace),
+    (KeyName::Delete, NamedKey::Delete),
+    (KeyName::Insert, NamedKey::Insert),
+    (KeyName::Home, NamedKey::Home),
+    (KeyName::End, NamedKey::End),
+    (KeyName::PageUp, NamedKey::PageUp),
+    (KeyName::PageDown, NamedKey::PageDown),
+    (KeyName::Up, NamedKey::ArrowUp),
+    (KeyName::Down, NamedKey::ArrowDown),
+    (KeyName::Left, NamedKey::ArrowLeft),
+    (KeyName::Right, NamedKey::ArrowRight),
+    (KeyName::F1, NamedKey::F1),
+    (KeyName::F2, NamedKey::F2),
+    (KeyName::F3, NamedKey::F3),
+    (KeyName::F4, NamedKey::F4),
+    (KeyName::F5, NamedKey::F5),
+    (KeyName::F6, NamedKey::F6),
+    (KeyName::F7, NamedKey::F7),
+    (KeyName::F8, NamedKey::F8),
+    (KeyName::F9, NamedKey::F9),
+    (KeyName::F10, NamedKey::F10),
+    (KeyName::F11, NamedKey::F11),
+    (KeyName::F12, NamedKey::F12),
+    (KeyName::CapsLock, NamedKey::CapsLock),
+    // Logical keys do not say which side, so left is used
+    (KeyName::LeftShift, NamedKey::Shift),
+    (KeyName::LeftControl, NamedKey::Control),
+    (KeyName::LeftAlt, NamedKey::Alt),
+    (KeyName::LeftSuper, NamedKey::Super),
+];
+
+impl Key {
+    /// Key of a winit event with the modifiers which are held, using the
+    /// logical or physical key as chosen by the policy
+    ///
+    /// The logical key already includes shift, so shift and `1` is `!` on a
+    /// US keyboard. The physical key keeps shift, so it is `<S-1>`, which
+    /// can be normalized with [`ShiftedSymbols`](crate::ShiftedSymbols).
+    pub fn from_winit(
+        event: &KeyEvent,
+        modifiers: ModifiersState,
+        policy: KeyPolicy,
+    ) -> Result<Key, ConversionError> {
+        Key::from_winit_keys(&event.logical_key, event.physical_key, modifiers, policy)
+    }
+
+    /// Key of a logical and physical key, as with [`Key::from_winit`]
+    pub fn from_winit_keys(
+        logical: &keyboard::Key,
+        physical: PhysicalKey,
+        modifiers: ModifiersState,
+        policy: KeyPolicy,
+    ) -> Result<Key, ConversionError> {
+        let modifiers = modifiers.into();
+        let logical_key = || logical_key(logical, modifiers);
+        let physical_key = || physical_key(physical, modifiers);
+        match policy {
+            KeyPolicy::Logical => logical_key().or_else(physical_key),
+            KeyPolicy::Physical => physical_key().or_else(logical_key),
+        }
+        .ok_or_else(|| ConversionError::UnsupportedKey(format!("{:?}", logical)))
+    }
+}
+
+impl From<ModifiersState> for Modifiers {
+    fn from(state: ModifiersState) -> Self {
+        Modifiers {
+            shift: state.shift_key(),
+            control: state.control_key(),
+            alt: state.alt_key(),
+            super_key: state.super_key(),
+            primary: false,
+        }
+    }
+}
+
+/// Modifiers of a [`ModifiersChanged`](winit::event::WindowEvent::ModifiersChanged)
+/// event, where which side is held is not kept
+impl From<event::Modifiers> for Modifiers {
+    fn from(modifiers: event::Modifiers) -> Self {
+        modifiers.state().into()
+    }
+}
+
+/// [`Modifiers::primary`] must be resolved first with
+/// [`Modifiers::resolve_platform`]
+impl TryFrom<Modifiers> for ModifiersState {
+    type Error = ConversionError;
+    fn try_from(modifiers: Modifiers) -> Result<Self, Self::Error> {
+        let modifiers = modifiers.require_resolved()?;
+        let mut state = ModifiersState::empty();
+        state.set(ModifiersState::SHIFT, modifiers.shift);
+        state.set(ModifiersState::CONTROL, modifiers.control);
+        state.set(ModifiersState::ALT, modifiers.alt);
+        state.set(ModifiersState::SUPER, modifiers.super_key);
+        Ok(state)
+    }
+}
+
+fn logical_key(logical: &keyboard::Key, modifiers: Modifiers) -> Option<Key> {
+    match logical {
+        keyboard::Key::Character(text) => Key::from_w3c_key(text, modifiers),
+        keyboard::Key::Named(named) => {
+            let (name, _) = NAMED.iter().find(|(_, known)| known == named)?;
+            Some(Key {
+                modifiers,
+                name: *name,
+            })
+        }
+        _ => None,
+    }
+}
+
+fn physical_key(physical: PhysicalKey, modifiers: Modifiers) -> Option<Key> {
+    let PhysicalKey::Code(code) = physical else {
+        return None;
+    };
+    let (name, _) = CODES.iter().find(|(_, known)| *known == code)?;
+    Some(Key {
+        modifiers,
+        name: *name,
+    })
+}